@@ -20,7 +20,7 @@ _just() {
 
     case "${cmd}" in
         just)
-            opts=" -n -q -u -v -e -l -h -V -f -d -c -s  --check --yes --dry-run --highlight --no-dotenv --no-highlight --quiet --shell-command --clear-shell-args --unsorted --unstable --verbose --changelog --choose --dump --edit --evaluate --fmt --init --list --summary --variables --help --version --chooser --color --command-color --dump-format --list-heading --list-prefix --justfile --set --shell --shell-arg --working-directory --command --completions --show --dotenv-filename --dotenv-path  <ARGUMENTS>... "
+            opts=" -n -g -q -u -v -e -l -h -V -j -f -d -c -s  --check --yes --dry-run --force --highlight --no-dotenv --no-highlight --global-justfile --quiet --shell-command --clear-shell-args --timings --unsorted --unstable --verbose --changelog --choose --clear-cache --dump --edit --evaluate --fmt --init --groups --list --lsp --summary --variables --help --version --chooser --color --command-color --dump-format --jobs --list-heading --list-prefix --output-format --events-file --justfile --justfile-names --root-markers --set --shell --shell-arg --timings-trace --working-directory --command --completions --show --dotenv-filename --dotenv-path  <ARGUMENTS>... "
                 if [[ ${cur} == -* ]] ; then
                     COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                     return 0
@@ -37,6 +37,25 @@ _just() {
                         COMPREPLY=( $(compgen -W "${recipes}" -- "${cur}") )
                         return 0
                     fi
+                else
+                    local choices=$(just --show "${COMP_WORDS[1]}" 2> /dev/null | awk '
+                        /^[#[]/ { next }
+                        {
+                            while (match($0, /: \([^)]*\)/)) {
+                                n = split(substr($0, RSTART + 3, RLENGTH - 4), values, "|");
+                                for (i = 1; i <= n; i++) {
+                                    gsub(/^[[:space:]]*["\047]|["\047][[:space:]]*$/, "", values[i]);
+                                    print values[i];
+                                }
+                                $0 = substr($0, RSTART + RLENGTH);
+                            }
+                            exit;
+                        }')
+
+                    if [[ -n "${choices}" ]]; then
+                        COMPREPLY=( $(compgen -W "${choices}" -- "${cur}") )
+                        return 0
+                    fi
                 fi
             case "${prev}" in
                 
@@ -56,6 +75,14 @@ _just() {
                     COMPREPLY=($(compgen -W "just json" -- "${cur}"))
                     return 0
                     ;;
+                --jobs)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                    -j)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --list-heading)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -64,6 +91,14 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --output-format)
+                    COMPREPLY=($(compgen -W "text json-events" -- "${cur}"))
+                    return 0
+                    ;;
+                --events-file)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --justfile)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -72,6 +107,14 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --justfile-names)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --root-markers)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --set)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
@@ -84,6 +127,10 @@ _just() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --timings-trace)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 --working-directory)
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
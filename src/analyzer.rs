@@ -4,46 +4,151 @@ use {super::*, CompileErrorKind::*};
 pub(crate) struct Analyzer<'src> {
   assignments: Table<'src, Assignment<'src>>,
   aliases: Table<'src, Alias<'src, Name<'src>>>,
+  functions: Table<'src, UserFunction<'src>>,
   sets: Table<'src, Set<'src>>,
 }
 
 impl<'src> Analyzer<'src> {
-  pub(crate) fn analyze(ast: &Ast<'src>) -> CompileResult<'src, Justfile<'src>> {
-    Analyzer::default().justfile(ast)
+  /// Analyze the justfile at `root`, along with any files it imports. `paths`
+  /// maps each loaded source file to its path relative to the directory of the
+  /// root justfile. Submodules are analyzed recursively, with their recipes
+  /// namespaced under `namepath`.
+  pub(crate) fn analyze(
+    paths: &HashMap<PathBuf, PathBuf>,
+    asts: &HashMap<PathBuf, Ast<'src>>,
+    root: &Path,
+    name: Option<Name<'src>>,
+    namepath: &str,
+  ) -> CompileResult<'src, Justfile<'src>> {
+    Analyzer::default().justfile(paths, asts, root, name, namepath)
   }
 
-  fn justfile(mut self, ast: &Ast<'src>) -> CompileResult<'src, Justfile<'src>> {
+  fn justfile(
+    mut self,
+    paths: &HashMap<PathBuf, PathBuf>,
+    asts: &HashMap<PathBuf, Ast<'src>>,
+    root: &Path,
+    name: Option<Name<'src>>,
+    namepath: &str,
+  ) -> CompileResult<'src, Justfile<'src>> {
     let mut recipes = Vec::new();
 
-    for item in &ast.items {
-      match item {
-        Item::Alias(alias) => {
-          self.analyze_alias(alias)?;
-          self.aliases.insert(alias.clone());
-        }
-        Item::Assignment(assignment) => {
-          self.analyze_assignment(assignment)?;
-          self.assignments.insert(assignment.clone());
-        }
-        Item::Comment(_) => (),
-        Item::Recipe(recipe) => {
-          if recipe.enabled() {
-            Self::analyze_recipe(recipe)?;
-            recipes.push(recipe);
+    let mut expressions = Vec::new();
+
+    let mut loaded = Vec::new();
+
+    let mut modules: Table<Justfile> = Table::new();
+
+    let mut warnings = Vec::new();
+
+    let mut unexports: BTreeMap<&'src str, Name<'src>> = BTreeMap::new();
+
+    let mut seen = HashSet::new();
+
+    let mut stack = vec![root];
+
+    while let Some(path) = stack.pop() {
+      // a file imported more than once, as in a diamond of imports, only
+      // contributes its items once
+      if !seen.insert(path) {
+        continue;
+      }
+
+      let ast = asts.get(path).unwrap();
+
+      loaded.push(paths.get(path).unwrap().clone());
+
+      warnings.extend(ast.warnings.iter().cloned());
+
+      let mut imports = Vec::new();
+
+      for item in &ast.items {
+        match item {
+          Item::Alias(alias) => {
+            self.analyze_alias(alias)?;
+            self.aliases.insert(alias.clone());
+          }
+          Item::Assignment(assignment) => {
+            self.analyze_assignment(assignment)?;
+            self.assignments.insert(assignment.clone());
+            expressions.push(&assignment.value);
+          }
+          Item::Comment(_) => (),
+          Item::Function(function) => {
+            self.analyze_function(function)?;
+            self.functions.insert(function.clone());
+          }
+          Item::Import { absolute, .. } => {
+            if let Some(absolute) = absolute {
+              imports.push(absolute.as_path());
+            }
+          }
+          Item::Module { absolute, name, .. } => {
+            if let Some(absolute) = absolute {
+              if let Some(original) = modules.get(name.lexeme()) {
+                return Err(name.token().error(DuplicateModule {
+                  module: name.lexeme(),
+                  first: original.name.map_or(0, |name| name.line),
+                  first_path: original.name.map_or(name.path, |name| name.path),
+                }));
+              }
+
+              let namepath = if namepath.is_empty() {
+                name.lexeme().to_owned()
+              } else {
+                format!("{namepath}::{name}")
+              };
+
+              modules.insert(Self::analyze(
+                paths,
+                asts,
+                absolute,
+                Some(*name),
+                &namepath,
+              )?);
+            }
+          }
+          Item::Recipe(recipe) => {
+            expressions.extend(Self::recipe_expressions(recipe));
+
+            if recipe.enabled() {
+              Self::analyze_recipe(recipe)?;
+              recipes.push(recipe);
+            }
+          }
+          Item::Set(set) => {
+            self.analyze_set(set)?;
+            self.sets.insert(set.clone());
+          }
+          Item::Unexport { name } => {
+            if let Some(first) = unexports.insert(name.lexeme(), *name) {
+              return Err(name.token().error(DuplicateUnexport {
+                variable: name.lexeme(),
+                first: first.line,
+              }));
+            }
           }
-        }
-        Item::Set(set) => {
-          self.analyze_set(set)?;
-          self.sets.insert(set.clone());
         }
       }
+
+      stack.extend(imports.into_iter().rev());
     }
 
     let settings = Settings::from_setting_iter(self.sets.into_iter().map(|(_, set)| set.value));
 
     let mut recipe_table: Table<'src, UnresolvedRecipe<'src>> = Table::default();
 
-    AssignmentResolver::resolve_assignments(&self.assignments)?;
+    FunctionResolver::resolve_functions(&self.functions, &self.assignments, &expressions)?;
+
+    AssignmentResolver::resolve_assignments(&self.assignments, &self.functions)?;
+
+    for assignment in self.assignments.values() {
+      if assignment.export && unexports.contains_key(assignment.name.lexeme()) {
+        return Err(assignment.name.token().error(ExportUnexported {
+          variable: assignment.name.lexeme(),
+        }));
+      }
+    }
 
     for recipe in recipes {
       if let Some(original) = recipe_table.get(recipe.name.lexeme()) {
@@ -51,10 +156,18 @@ impl<'src> Analyzer<'src> {
           return Err(recipe.name.token().error(DuplicateRecipe {
             recipe: original.name(),
             first: original.line_number(),
+            first_path: original.name.path,
           }));
         }
       }
-      recipe_table.insert(recipe.clone());
+
+      let mut recipe = recipe.clone();
+
+      if !namepath.is_empty() {
+        recipe.namepath = format!("{namepath}::{}", recipe.name);
+      }
+
+      recipe_table.insert(recipe);
     }
 
     let recipes = RecipeResolver::resolve_recipes(recipe_table, &self.assignments)?;
@@ -64,22 +177,35 @@ impl<'src> Analyzer<'src> {
       aliases.insert(Self::resolve_alias(&recipes, alias)?);
     }
 
+    let source_order = |recipe: &Recipe<'src>| {
+      (
+        loaded.iter().position(|path| path == recipe.name.path),
+        recipe.line_number(),
+      )
+    };
+
     Ok(Justfile {
-      warnings: ast.warnings.clone(),
       first: recipes
         .values()
         .fold(None, |accumulator, next| match accumulator {
-          None => Some(Rc::clone(next)),
-          Some(previous) => Some(if previous.line_number() < next.line_number() {
+          None => Some(Arc::clone(next)),
+          Some(previous) => Some(if source_order(&previous) < source_order(next) {
             previous
           } else {
-            Rc::clone(next)
+            Arc::clone(next)
           }),
         }),
       aliases,
       assignments: self.assignments,
+      functions: self.functions,
+      loaded,
+      modules,
+      name,
       recipes,
       settings,
+      source: root.into(),
+      unexports: unexports.into_keys().map(str::to_owned).collect(),
+      warnings,
     })
   }
 
@@ -110,9 +236,71 @@ impl<'src> Analyzer<'src> {
       }
     }
 
+    if !recipe.change_directory()
+      && recipe
+        .attributes
+        .iter()
+        .any(|attribute| matches!(attribute, Attribute::WorkingDirectory(_)))
+    {
+      return Err(recipe.name.error(NoCdAndWorkingDirectoryAttribute {
+        recipe: recipe.name.lexeme(),
+      }));
+    }
+
+    if !recipe.is_script() {
+      for attribute in &recipe.attributes {
+        if matches!(attribute, Attribute::Extension(_)) {
+          return Err(recipe.name.error(LinewiseRecipeAttribute {
+            attribute: attribute.name(),
+            recipe: recipe.name.lexeme(),
+          }));
+        }
+      }
+    }
+
+    if recipe.script().is_some() && recipe.shell().is_some() {
+      return Err(recipe.name.error(ScriptAndShellAttribute {
+        recipe: recipe.name.lexeme(),
+      }));
+    }
+
+    // the output of a recipe skipped because it is fresh is not available
+    // to be captured
+    if recipe.capture() {
+      for attribute in &recipe.attributes {
+        if matches!(attribute, Attribute::Outputs(_) | Attribute::Sources(_)) {
+          return Err(recipe.name.error(CaptureAndFreshnessAttribute {
+            attribute: attribute.name(),
+            recipe: recipe.name.lexeme(),
+          }));
+        }
+      }
+    }
+
+    if recipe.shebang {
+      for attribute in &recipe.attributes {
+        if matches!(attribute, Attribute::Script(_) | Attribute::Shell(_)) {
+          return Err(recipe.name.error(ShebangRecipeAttribute {
+            attribute: attribute.name(),
+            recipe: recipe.name.lexeme(),
+          }));
+        }
+      }
+    }
+
+    let mut environment = BTreeSet::new();
+
+    for (variable, _value) in recipe.environment() {
+      if !environment.insert(variable) {
+        return Err(recipe.name.error(DuplicateEnvAttribute {
+          variable: variable.to_owned(),
+        }));
+      }
+    }
+
     let mut continued = false;
     for line in &recipe.body {
-      if !recipe.shebang && !continued {
+      if !recipe.is_script() && !continued {
         if let Some(Fragment::Text { token }) = line.fragments.first() {
           let text = token.lexeme();
 
@@ -129,14 +317,77 @@ impl<'src> Analyzer<'src> {
   }
 
   fn analyze_assignment(&self, assignment: &Assignment<'src>) -> CompileResult<'src, ()> {
-    if self.assignments.contains_key(assignment.name.lexeme()) {
+    if let Some(original) = self.assignments.get(assignment.name.lexeme()) {
       return Err(assignment.name.token().error(DuplicateVariable {
         variable: assignment.name.lexeme(),
+        first: original.name.line,
+        first_path: original.name.path,
       }));
     }
     Ok(())
   }
 
+  fn analyze_function(&self, function: &UserFunction<'src>) -> CompileResult<'src, ()> {
+    let name = function.name.lexeme();
+
+    if let Some(original) = self.functions.get(name) {
+      return Err(function.name.token().error(DuplicateFunction {
+        function: name,
+        first: original.name.line,
+        first_path: original.name.path,
+      }));
+    }
+
+    if function::get(name).is_some() || Keyword::Cached == name {
+      return Err(
+        function
+          .name
+          .token()
+          .error(FunctionShadowsBuiltin { function: name }),
+      );
+    }
+
+    let mut parameters = BTreeSet::new();
+
+    for parameter in &function.parameters {
+      if !parameters.insert(parameter.lexeme()) {
+        return Err(parameter.token().error(DuplicateFunctionParameter {
+          function: name,
+          parameter: parameter.lexeme(),
+        }));
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Parameter defaults, dependency arguments, and interpolations in `recipe`
+  fn recipe_expressions<'a>(
+    recipe: &'a UnresolvedRecipe<'src>,
+  ) -> impl Iterator<Item = &'a Expression<'src>> {
+    recipe
+      .parameters
+      .iter()
+      .filter_map(|parameter| parameter.default.as_ref())
+      .chain(
+        recipe
+          .dependencies
+          .iter()
+          .flat_map(|dependency| &dependency.arguments),
+      )
+      .chain(
+        recipe
+          .body
+          .iter()
+          .flat_map(|line| &line.fragments)
+          .filter_map(|fragment| match fragment {
+            Fragment::Interpolation { expression, .. } => Some(expression),
+            Fragment::Text { .. } => None,
+          }),
+      )
+      .chain(recipe.confirm_prompt())
+  }
+
   fn analyze_alias(&self, alias: &Alias<'src, Name<'src>>) -> CompileResult<'src, ()> {
     let name = alias.name.lexeme();
 
@@ -144,6 +395,7 @@ impl<'src> Analyzer<'src> {
       return Err(alias.name.token().error(DuplicateAlias {
         alias: name,
         first: original.line_number(),
+        first_path: original.name.path,
       }));
     }
 
@@ -151,7 +403,7 @@ impl<'src> Analyzer<'src> {
       if *attr != Attribute::Private {
         return Err(alias.name.token().error(AliasInvalidAttribute {
           alias: name,
-          attr: *attr,
+          attr: attr.clone(),
         }));
       }
     }
@@ -164,6 +416,7 @@ impl<'src> Analyzer<'src> {
       return Err(set.name.error(DuplicateSet {
         setting: original.name.lexeme(),
         first: original.name.line,
+        first_path: original.name.path,
       }));
     }
 
@@ -171,7 +424,7 @@ impl<'src> Analyzer<'src> {
   }
 
   fn resolve_alias(
-    recipes: &Table<'src, Rc<Recipe<'src>>>,
+    recipes: &Table<'src, Arc<Recipe<'src>>>,
     alias: Alias<'src, Name<'src>>,
   ) -> CompileResult<'src, Alias<'src>> {
     let token = alias.name.token();
@@ -185,7 +438,7 @@ impl<'src> Analyzer<'src> {
 
     // Make sure the target recipe exists
     match recipes.get(alias.target.lexeme()) {
-      Some(target) => Ok(alias.resolve(Rc::clone(target))),
+      Some(target) => Ok(alias.resolve(Arc::clone(target))),
       None => Err(token.error(UnknownAliasTarget {
         alias: alias.name.lexeme(),
         target: alias.target.lexeme(),
@@ -205,7 +458,7 @@ mod tests {
     line: 1,
     column: 6,
     width: 3,
-    kind: DuplicateAlias { alias: "foo", first: 0 },
+    kind: DuplicateAlias { alias: "foo", first: 0, first_path: Path::new("justfile") },
   }
 
   analysis_error! {
@@ -275,7 +528,7 @@ mod tests {
     line:   2,
     column: 0,
     width:  1,
-    kind:   DuplicateRecipe{recipe: "a", first: 0},
+    kind:   DuplicateRecipe{recipe: "a", first: 0, first_path: Path::new("justfile")},
   }
 
   analysis_error! {
@@ -285,7 +538,37 @@ mod tests {
     line:   1,
     column: 0,
     width:  1,
-    kind:   DuplicateVariable{variable: "a"},
+    kind:   DuplicateVariable{variable: "a", first: 0, first_path: Path::new("justfile")},
+  }
+
+  analysis_error! {
+    name:   duplicate_function,
+    input:  "fn f() := 'a'\nfn f() := 'b'",
+    offset: 17,
+    line:   1,
+    column: 3,
+    width:  1,
+    kind:   DuplicateFunction{function: "f", first: 0, first_path: Path::new("justfile")},
+  }
+
+  analysis_error! {
+    name:   duplicate_function_parameter,
+    input:  "fn f(a, a) := a",
+    offset: 8,
+    line:   0,
+    column: 8,
+    width:  1,
+    kind:   DuplicateFunctionParameter{function: "f", parameter: "a"},
+  }
+
+  analysis_error! {
+    name:   function_shadows_builtin,
+    input:  "fn uppercase(s) := s",
+    offset: 3,
+    line:   0,
+    column: 3,
+    width:  9,
+    kind:   FunctionShadowsBuiltin{function: "uppercase"},
   }
 
   analysis_error! {
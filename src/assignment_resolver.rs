@@ -2,6 +2,7 @@ use {super::*, CompileErrorKind::*};
 
 pub(crate) struct AssignmentResolver<'src: 'run, 'run> {
   assignments: &'run Table<'src, Assignment<'src>>,
+  functions: &'run Table<'src, UserFunction<'src>>,
   stack: Vec<&'src str>,
   evaluated: BTreeSet<&'src str>,
 }
@@ -9,11 +10,13 @@ pub(crate) struct AssignmentResolver<'src: 'run, 'run> {
 impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
   pub(crate) fn resolve_assignments(
     assignments: &Table<'src, Assignment<'src>>,
+    functions: &Table<'src, UserFunction<'src>>,
   ) -> CompileResult<'src, ()> {
     let mut resolver = AssignmentResolver {
       stack: Vec::new(),
       evaluated: BTreeSet::new(),
       assignments,
+      functions,
     };
 
     for name in assignments.keys() {
@@ -37,6 +40,7 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
       let message = format!("attempted to resolve unknown assignment `{name}`");
       let token = Token {
         src: "",
+        path: "".as_ref(),
         offset: 0,
         line: 0,
         column: 0,
@@ -51,28 +55,49 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
     Ok(())
   }
 
+  fn resolve_variable(&mut self, name: Token<'src>) -> CompileResult<'src, ()> {
+    let variable = name.lexeme();
+    if self.evaluated.contains(variable) {
+      Ok(())
+    } else if self.stack.contains(&variable) {
+      let token = self.assignments[variable].name.token();
+      self.stack.push(variable);
+      Err(token.error(CircularVariableDependency {
+        variable,
+        circle: self.stack.clone(),
+      }))
+    } else if self.assignments.contains_key(variable) {
+      self.resolve_assignment(variable)
+    } else {
+      Err(name.error(UndefinedVariable { variable }))
+    }
+  }
+
+  /// Resolve the assignments referenced by the body of user-defined function
+  /// `name`, and by the bodies of the functions it calls. Function recursion
+  /// has already been ruled out by the `FunctionResolver`.
+  fn resolve_function(&mut self, name: &str) -> CompileResult<'src, ()> {
+    let Some(function) = self.functions.get(name) else {
+      return Ok(());
+    };
+
+    for variable in function.globals() {
+      self.resolve_variable(variable)?;
+    }
+
+    for (name, _arguments) in function.body.calls() {
+      self.resolve_function(name.lexeme())?;
+    }
+
+    Ok(())
+  }
+
   fn resolve_expression(&mut self, expression: &Expression<'src>) -> CompileResult<'src, ()> {
     match expression {
-      Expression::Variable { name } => {
-        let variable = name.lexeme();
-        if self.evaluated.contains(variable) {
-          Ok(())
-        } else if self.stack.contains(&variable) {
-          let token = self.assignments[variable].name.token();
-          self.stack.push(variable);
-          Err(token.error(CircularVariableDependency {
-            variable,
-            circle: self.stack.clone(),
-          }))
-        } else if self.assignments.contains_key(variable) {
-          self.resolve_assignment(variable)
-        } else {
-          Err(name.token().error(UndefinedVariable { variable }))
-        }
-      }
+      Expression::Variable { name } => self.resolve_variable(name.token()),
       Expression::Call { thunk } => match thunk {
         Thunk::Nullary { .. } => Ok(()),
-        Thunk::Unary { arg, .. } => self.resolve_expression(arg),
+        Thunk::Unary { arg, .. } | Thunk::UnaryValue { arg, .. } => self.resolve_expression(arg),
         Thunk::UnaryOpt { args: (a, b), .. } => {
           self.resolve_expression(a)?;
           if let Some(b) = b.as_ref() {
@@ -80,7 +105,7 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
           }
           Ok(())
         }
-        Thunk::Binary { args: [a, b], .. } => {
+        Thunk::Binary { args: [a, b], .. } | Thunk::BinaryValue { args: [a, b], .. } => {
           self.resolve_expression(a)?;
           self.resolve_expression(b)
         }
@@ -102,6 +127,18 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
           self.resolve_expression(b)?;
           self.resolve_expression(c)
         }
+        Thunk::Cached { files, .. } => {
+          for file in files {
+            self.resolve_expression(file)?;
+          }
+          Ok(())
+        }
+        Thunk::User { name, args } => {
+          for arg in args {
+            self.resolve_expression(arg)?;
+          }
+          self.resolve_function(name.lexeme())
+        }
       },
       Expression::Concatenation { lhs, rhs } => {
         self.resolve_expression(lhs)?;
@@ -114,14 +151,11 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
         self.resolve_expression(rhs)
       }
       Expression::Conditional {
-        lhs,
-        rhs,
+        condition,
         then,
         otherwise,
-        ..
       } => {
-        self.resolve_expression(lhs)?;
-        self.resolve_expression(rhs)?;
+        self.resolve_condition(condition)?;
         self.resolve_expression(then)?;
         self.resolve_expression(otherwise)
       }
@@ -129,6 +163,28 @@ impl<'src: 'run, 'run> AssignmentResolver<'src, 'run> {
       Expression::Group { contents } => self.resolve_expression(contents),
     }
   }
+
+  fn resolve_condition(&mut self, condition: &Condition<'src>) -> CompileResult<'src, ()> {
+    match condition {
+      Condition::And { lhs, rhs } | Condition::Or { lhs, rhs } => {
+        self.resolve_condition(lhs)?;
+        self.resolve_condition(rhs)
+      }
+      Condition::Comparison { lhs, rhs, .. } => {
+        self.resolve_expression(lhs)?;
+        self.resolve_expression(rhs)
+      }
+      Condition::Group { contents } => self.resolve_condition(contents),
+      Condition::In { lhs, list } => {
+        self.resolve_expression(lhs)?;
+        for expression in list {
+          self.resolve_expression(expression)?;
+        }
+        Ok(())
+      }
+      Condition::Not { condition } => self.resolve_condition(condition),
+    }
+  }
 }
 
 #[cfg(test)]
@@ -155,6 +211,16 @@ mod tests {
     kind:   CircularVariableDependency{variable: "a", circle: vec!["a", "a"]},
   }
 
+  analysis_error! {
+    name:   circular_variable_dependency_through_function,
+    input:  "a := f()\nfn f() := g(b)\nfn g(x) := x\nb := a",
+    offset: 0,
+    line:   0,
+    column: 0,
+    width:  1,
+    kind:   CircularVariableDependency{variable: "a", circle: vec!["a", "b", "a"]},
+  }
+
   analysis_error! {
     name:   unknown_expression_variable,
     input:  "x := yy",
@@ -7,6 +7,8 @@ use super::*;
 pub(crate) struct Ast<'src> {
   /// Items in the justfile
   pub(crate) items: Vec<Item<'src>>,
+  /// Source layout of each item in `items`, used by the formatter
+  pub(crate) layout: Vec<Layout<'src>>,
   /// Non-fatal warnings encountered during parsing
   pub(crate) warnings: Vec<Warning>,
 }
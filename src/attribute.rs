@@ -1,29 +1,314 @@
 use super::*;
 
-#[derive(
-  EnumString, PartialEq, Debug, Copy, Clone, Serialize, Ord, PartialOrd, Eq, IntoStaticStr,
+#[derive(Derivative, EnumDiscriminants, Debug, Clone, Serialize, IntoStaticStr)]
+#[derivative(
+  PartialEq,
+  Eq,
+  PartialOrd = "feature_allow_slow_enum",
+  Ord = "feature_allow_slow_enum"
 )]
 #[strum(serialize_all = "kebab-case")]
 #[serde(rename_all = "kebab-case")]
-pub(crate) enum Attribute {
-  Confirm,
+#[strum_discriminants(name(AttributeDiscriminant))]
+#[strum_discriminants(derive(EnumString))]
+#[strum_discriminants(strum(serialize_all = "kebab-case"))]
+pub(crate) enum Attribute<'src> {
+  Arg {
+    long: Option<StringLiteral<'src>>,
+    name: StringLiteral<'src>,
+    pattern: Option<StringLiteral<'src>>,
+    short: Option<StringLiteral<'src>>,
+    /// The attribute name, where errors in the attribute are reported, boxed
+    /// to keep `Attribute` small
+    #[derivative(PartialEq = "ignore", PartialOrd = "ignore", Ord = "ignore")]
+    #[serde(skip)]
+    token: Box<Token<'src>>,
+    value: Option<StringLiteral<'src>>,
+  },
+  Capture,
+  Confirm(
+    #[derivative(PartialEq = "ignore", PartialOrd = "ignore", Ord = "ignore")]
+    Option<Expression<'src>>,
+  ),
+  Env(StringLiteral<'src>, StringLiteral<'src>),
+  Extension(StringLiteral<'src>),
+  Group(StringLiteral<'src>),
   Linux,
   Macos,
   NoCd,
   NoExitMessage,
+  Outputs(Vec<StringLiteral<'src>>),
+  Parallel,
   Private,
+  Script(Shell<'src>),
+  Shell(Shell<'src>),
+  Sources(Vec<StringLiteral<'src>>),
   Unix,
   Windows,
+  WorkingDirectory(StringLiteral<'src>),
 }
 
-impl Attribute {
-  pub(crate) fn from_name(name: Name) -> Option<Attribute> {
-    name.lexeme().parse().ok()
+impl AttributeDiscriminant {
+  /// Whether a recipe may have more than one attribute of this kind, with
+  /// different arguments
+  pub(crate) fn repeatable(self) -> bool {
+    matches!(
+      self,
+      Self::Arg | Self::Env | Self::Group | Self::Outputs | Self::Sources
+    )
   }
 
-  pub(crate) fn to_str(self) -> &'static str {
+  fn argument_range(self) -> RangeInclusive<usize> {
+    match self {
+      Self::Arg | Self::Extension | Self::Group | Self::WorkingDirectory => 1..=1,
+      Self::Confirm => 0..=1,
+      Self::Env => 2..=2,
+      Self::Outputs | Self::Script | Self::Shell | Self::Sources => 1..=usize::MAX,
+      Self::Capture
+      | Self::Linux
+      | Self::Macos
+      | Self::NoCd
+      | Self::NoExitMessage
+      | Self::Parallel
+      | Self::Private
+      | Self::Unix
+      | Self::Windows => 0..=0,
+    }
+  }
+}
+
+impl<'src> Attribute<'src> {
+  pub(crate) fn new(
+    name: Name<'src>,
+    arguments: Vec<Expression<'src>>,
+    mut keyword_arguments: BTreeMap<&'src str, (Name<'src>, StringLiteral<'src>)>,
+  ) -> CompileResult<'src, Self> {
+    let discriminant = name
+      .lexeme()
+      .parse::<AttributeDiscriminant>()
+      .map_err(|_| {
+        name.error(CompileErrorKind::UnknownAttribute {
+          attribute: name.lexeme(),
+        })
+      })?;
+
+    let expected = discriminant.argument_range();
+
+    if !expected.range_contains(&arguments.len()) {
+      return Err(
+        name.error(CompileErrorKind::AttributeArgumentCountMismatch {
+          attribute: name.lexeme(),
+          found: arguments.len(),
+          expected,
+        }),
+      );
+    }
+
+    let mut prompt = None;
+    let mut literals = Vec::new();
+
+    for argument in arguments {
+      match argument {
+        // only the prompt of `confirm` may be an arbitrary expression
+        argument if discriminant == AttributeDiscriminant::Confirm => {
+          if Self::interpolates(&argument) {
+            return Err(name.error(CompileErrorKind::ConfirmPromptInterpolation));
+          }
+          prompt = Some(argument);
+        }
+        Expression::StringLiteral { string_literal } => literals.push(string_literal),
+        _ => {
+          return Err(
+            name.error(CompileErrorKind::AttributeArgumentNotStringLiteral {
+              attribute: name.lexeme(),
+            }),
+          )
+        }
+      }
+    }
+
+    let mut arguments = literals.into_iter();
+
+    let attribute = match discriminant {
+      AttributeDiscriminant::Arg => {
+        let long = keyword_arguments.remove("long");
+        let short = keyword_arguments.remove("short");
+        let value = keyword_arguments.remove("value");
+
+        if let Some((keyword, long)) = &long {
+          if long.cooked.is_empty()
+            || long.cooked.starts_with('-')
+            || long
+              .cooked
+              .contains(|c: char| c == '=' || c.is_whitespace())
+          {
+            return Err(keyword.error(CompileErrorKind::InvalidLongOption {
+              option: long.cooked.clone(),
+            }));
+          }
+        }
+
+        if let Some((keyword, short)) = &short {
+          let mut chars = short.cooked.chars();
+          if !matches!((chars.next(), chars.next()), (Some(c), None) if c != '-' && !c.is_whitespace())
+          {
+            return Err(keyword.error(CompileErrorKind::InvalidShortOption {
+              option: short.cooked.clone(),
+            }));
+          }
+        }
+
+        if let Some((keyword, _value)) = &value {
+          if long.is_none() && short.is_none() {
+            return Err(keyword.error(CompileErrorKind::ArgAttributeValueRequiresOption));
+          }
+        }
+
+        Self::Arg {
+          long: long.map(|(_keyword, literal)| literal),
+          name: arguments.next().unwrap(),
+          pattern: keyword_arguments
+            .remove("pattern")
+            .map(|(_keyword, literal)| literal),
+          short: short.map(|(_keyword, literal)| literal),
+          token: Box::new(name.token()),
+          value: value.map(|(_keyword, literal)| literal),
+        }
+      }
+      AttributeDiscriminant::Capture => Self::Capture,
+      AttributeDiscriminant::Confirm => Self::Confirm(prompt),
+      AttributeDiscriminant::Env => Self::Env(arguments.next().unwrap(), arguments.next().unwrap()),
+      AttributeDiscriminant::Extension => Self::Extension(arguments.next().unwrap()),
+      AttributeDiscriminant::Group => Self::Group(arguments.next().unwrap()),
+      AttributeDiscriminant::Linux => Self::Linux,
+      AttributeDiscriminant::Macos => Self::Macos,
+      AttributeDiscriminant::NoCd => Self::NoCd,
+      AttributeDiscriminant::NoExitMessage => Self::NoExitMessage,
+      AttributeDiscriminant::Outputs => Self::Outputs(Self::globs(name, arguments)?),
+      AttributeDiscriminant::Parallel => Self::Parallel,
+      AttributeDiscriminant::Private => Self::Private,
+      AttributeDiscriminant::Script => Self::Script(Shell {
+        command: arguments.next().unwrap(),
+        arguments: arguments.collect(),
+      }),
+      AttributeDiscriminant::Shell => Self::Shell(Shell {
+        command: arguments.next().unwrap(),
+        arguments: arguments.collect(),
+      }),
+      AttributeDiscriminant::Sources => Self::Sources(Self::globs(name, arguments)?),
+      AttributeDiscriminant::Unix => Self::Unix,
+      AttributeDiscriminant::Windows => Self::Windows,
+      AttributeDiscriminant::WorkingDirectory => Self::WorkingDirectory(arguments.next().unwrap()),
+    };
+
+    if let Some((keyword, _literal)) = keyword_arguments.into_values().next() {
+      return Err(keyword.error(CompileErrorKind::UnknownAttributeKeyword {
+        attribute: name.lexeme(),
+        keyword: keyword.lexeme(),
+      }));
+    }
+
+    Ok(attribute)
+  }
+
+  pub(crate) fn name(&self) -> &'static str {
     self.into()
   }
+
+  fn globs(
+    name: Name<'src>,
+    arguments: impl Iterator<Item = StringLiteral<'src>>,
+  ) -> CompileResult<'src, Vec<StringLiteral<'src>>> {
+    arguments
+      .map(|argument| match glob::Pattern::new(&argument.cooked) {
+        Ok(_) => Ok(argument),
+        Err(error) => Err(name.error(CompileErrorKind::InvalidGlob {
+          glob: argument.cooked.clone(),
+          message: error.msg,
+        })),
+      })
+      .collect()
+  }
+
+  /// Whether a string literal in `expression` looks like it uses `{{…}}`
+  /// interpolation, which is only supported in recipe bodies
+  fn interpolates(expression: &Expression) -> bool {
+    match expression {
+      Expression::Concatenation { lhs, rhs } => Self::interpolates(lhs) || Self::interpolates(rhs),
+      Expression::Group { contents } => Self::interpolates(contents),
+      Expression::StringLiteral { string_literal } => string_literal.cooked.contains("{{"),
+      _ => false,
+    }
+  }
+}
+
+impl Display for Attribute<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.name())?;
+
+    match self {
+      Self::Arg {
+        long,
+        name,
+        pattern,
+        short,
+        value,
+        ..
+      } => {
+        write!(f, "({name}")?;
+        if let Some(long) = long {
+          write!(f, ", long={long}")?;
+        }
+        if let Some(short) = short {
+          write!(f, ", short={short}")?;
+        }
+        if let Some(value) = value {
+          write!(f, ", value={value}")?;
+        }
+        if let Some(pattern) = pattern {
+          write!(f, ", pattern={pattern}")?;
+        }
+        write!(f, ")")?;
+      }
+      Self::Confirm(prompt) => {
+        if let Some(prompt) = prompt {
+          write!(f, "({prompt})")?;
+        }
+      }
+      Self::Env(key, value) => write!(f, "({key}, {value})")?,
+      Self::Extension(argument) | Self::Group(argument) | Self::WorkingDirectory(argument) => {
+        write!(f, "({argument})")?;
+      }
+      Self::Outputs(arguments) | Self::Sources(arguments) => {
+        write!(f, "(")?;
+        for (i, argument) in arguments.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{argument}")?;
+        }
+        write!(f, ")")?;
+      }
+      Self::Script(Shell { arguments, command }) | Self::Shell(Shell { arguments, command }) => {
+        write!(f, "({command}")?;
+        for argument in arguments {
+          write!(f, ", {argument}")?;
+        }
+        write!(f, ")")?;
+      }
+      Self::Capture
+      | Self::Linux
+      | Self::Macos
+      | Self::NoCd
+      | Self::NoExitMessage
+      | Self::Parallel
+      | Self::Private
+      | Self::Unix
+      | Self::Windows => {}
+    }
+
+    Ok(())
+  }
 }
 
 #[cfg(test)]
@@ -31,7 +316,7 @@ mod tests {
   use super::*;
 
   #[test]
-  fn to_str() {
-    assert_eq!(Attribute::NoExitMessage.to_str(), "no-exit-message");
+  fn name() {
+    assert_eq!(Attribute::NoExitMessage.name(), "no-exit-message");
   }
 }
@@ -0,0 +1,850 @@
+use {
+  super::*,
+  std::{
+    fs::{File, OpenOptions},
+    iter::Peekable,
+  },
+};
+
+/// Exit code used when a program can't be run, as in POSIX shells
+const CANNOT_RUN_EXIT_CODE: i32 = 127;
+
+/// A minimal shell built into `just`, which runs recipe lines and backticks
+/// without spawning a shell process.
+///
+/// Lines are tokenized with POSIX-like quoting and split into simple commands
+/// joined by `&&` and `||`. Simple commands may be prefixed with environment
+/// variable assignments, and may redirect stdin, stdout, and stderr to files.
+/// `$NAME` and `${NAME}` are expanded, and it is an error if `NAME` is not set.
+/// If positional arguments are given, `$0`…`$9`, `${N}`, `$#`, `$*`, and `$@`
+/// are expanded as in POSIX shells. There are no pipes, globs, subshells, or
+/// command substitution.
+#[derive(Debug, PartialEq)]
+pub(crate) struct BuiltinShell {
+  commands: Vec<(Option<Connector>, SimpleCommand)>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Connector {
+  And,
+  Or,
+}
+
+impl Display for Connector {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::And => write!(f, "&&"),
+      Self::Or => write!(f, "||"),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Default)]
+struct SimpleCommand {
+  assignments: Vec<(String, String)>,
+  redirections: Vec<Redirection>,
+  words: Vec<String>,
+}
+
+impl SimpleCommand {
+  fn is_empty(&self) -> bool {
+    self.assignments.is_empty() && self.redirections.is_empty() && self.words.is_empty()
+  }
+}
+
+#[derive(Debug, PartialEq)]
+struct Redirection {
+  fd: u32,
+  target: Target,
+}
+
+#[derive(Debug, PartialEq)]
+enum Target {
+  Append(String),
+  Duplicate(u32),
+  Read(String),
+  Write(String),
+}
+
+#[derive(Debug, PartialEq)]
+enum ShellToken {
+  Connector(Connector),
+  Redirection {
+    fd: u32,
+    operator: &'static str,
+  },
+  Word {
+    text: String,
+    assignment: Option<usize>,
+  },
+}
+
+/// Where a child process's stdout or stderr is sent
+enum Destination {
+  Capture,
+  File(File),
+  Null,
+  Stderr,
+  Stdout,
+}
+
+impl Destination {
+  fn try_clone(&self) -> io::Result<Self> {
+    Ok(match self {
+      Self::Capture => Self::Capture,
+      Self::File(file) => Self::File(file.try_clone()?),
+      Self::Null => Self::Null,
+      Self::Stderr => Self::Stderr,
+      Self::Stdout => Self::Stdout,
+    })
+  }
+
+  fn stdio(&self, fd: u32) -> Result<Stdio, String> {
+    match (self, fd) {
+      (Self::Capture, 1) => Ok(Stdio::piped()),
+      (Self::Capture, _) => Err("stderr may not be redirected to captured stdout".into()),
+      (Self::File(file), _) => file
+        .try_clone()
+        .map(Stdio::from)
+        .map_err(|io_error| io_error.to_string()),
+      (Self::Null, _) => Ok(Stdio::null()),
+      (Self::Stderr, 2) | (Self::Stdout, 1) => Ok(Stdio::inherit()),
+      (Self::Stderr, _) => Platform::duplicate_stderr().map_err(|io_error| io_error.to_string()),
+      (Self::Stdout, _) => Platform::duplicate_stdout().map_err(|io_error| io_error.to_string()),
+    }
+  }
+}
+
+impl BuiltinShell {
+  /// Run `line`, returning the exit status of the last command run. Commands
+  /// are created with `configure`, which should set the working directory and
+  /// environment. `positional` contains `$0` followed by the positional
+  /// arguments, if they are available.
+  pub(crate) fn run(
+    line: &str,
+    quiet: bool,
+    positional: Option<&[String]>,
+    configure: impl Fn(&mut Command),
+  ) -> Result<ExitStatus, String> {
+    let (stdout, stderr) = if quiet {
+      (Destination::Null, Destination::Null)
+    } else {
+      (Destination::Stdout, Destination::Stderr)
+    };
+
+    Self::new(line, positional, &configure)?.execute(&configure, &stdout, &stderr, &mut Vec::new())
+  }
+
+  /// Run `line`, capturing stdout
+  pub(crate) fn output(
+    line: &str,
+    quiet: bool,
+    positional: Option<&[String]>,
+    configure: impl Fn(&mut Command),
+  ) -> Result<process::Output, String> {
+    let stderr = if quiet {
+      Destination::Null
+    } else {
+      Destination::Stderr
+    };
+
+    let mut captured = Vec::new();
+
+    let status = Self::new(line, positional, &configure)?.execute(
+      &configure,
+      &Destination::Capture,
+      &stderr,
+      &mut captured,
+    )?;
+
+    Ok(process::Output {
+      status,
+      stdout: captured,
+      stderr: Vec::new(),
+    })
+  }
+
+  /// Parse `line`, expanding variables from the environment that commands
+  /// created with `configure` would have
+  fn new(
+    line: &str,
+    positional: Option<&[String]>,
+    configure: &dyn Fn(&mut Command),
+  ) -> Result<Self, String> {
+    let mut probe = Command::new("");
+
+    configure(&mut probe);
+
+    Self::parse(
+      line,
+      &|name| match probe
+        .get_envs()
+        .find(|(key, _value)| *key == OsStr::new(name))
+      {
+        Some((_key, value)) => value.and_then(OsStr::to_str).map(str::to_owned),
+        None => env::var(name).ok(),
+      },
+      positional,
+    )
+  }
+
+  fn parse(
+    line: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    positional: Option<&[String]>,
+  ) -> Result<Self, String> {
+    let mut commands = Vec::new();
+    let mut connector = None;
+    let mut command = SimpleCommand::default();
+
+    let mut tokens = Self::tokenize(line, lookup, positional)?.into_iter();
+
+    while let Some(token) = tokens.next() {
+      match token {
+        ShellToken::Connector(next) => {
+          if command.is_empty() {
+            return Err(format!("expected command before `{next}`"));
+          }
+          commands.push((connector, mem::take(&mut command)));
+          connector = Some(next);
+        }
+        ShellToken::Redirection { fd, operator } => {
+          let Some(ShellToken::Word { text, .. }) = tokens.next() else {
+            return Err(format!("expected file after `{operator}`"));
+          };
+
+          let target = match operator {
+            "<" => Target::Read(text),
+            ">" => Target::Write(text),
+            ">>" => Target::Append(text),
+            ">&" => Target::Duplicate(
+              text
+                .parse()
+                .ok()
+                .filter(|fd| *fd == 1 || *fd == 2)
+                .ok_or_else(|| format!("cannot duplicate file descriptor `{text}`"))?,
+            ),
+            _ => unreachable!(),
+          };
+
+          match (fd, &target) {
+            (0, Target::Read(_))
+            | (1 | 2, Target::Append(_) | Target::Duplicate(_) | Target::Write(_)) => {}
+            _ => {
+              return Err(format!(
+                "cannot redirect file descriptor {fd} with `{operator}`"
+              ))
+            }
+          }
+
+          command.redirections.push(Redirection { fd, target });
+        }
+        ShellToken::Word {
+          text,
+          assignment: Some(i),
+        } if command.words.is_empty() => {
+          command
+            .assignments
+            .push((text[..i].to_owned(), text[i + 1..].to_owned()));
+        }
+        ShellToken::Word { text, .. } => command.words.push(text),
+      }
+    }
+
+    if command.is_empty() {
+      if let Some(connector) = connector {
+        return Err(format!("expected command after `{connector}`"));
+      }
+    } else {
+      commands.push((connector, command));
+    }
+
+    Ok(Self { commands })
+  }
+
+  fn tokenize(
+    line: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    positional: Option<&[String]>,
+  ) -> Result<Vec<ShellToken>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    // The current word, whether it has started, which is needed for empty
+    // quoted words, whether it contains only unquoted text, and the index of
+    // the `=` if it is an assignment
+    let mut word = String::new();
+    let mut started = false;
+    let mut literal = true;
+    let mut assignment = None;
+
+    macro_rules! finish {
+      () => {{
+        if started {
+          tokens.push(ShellToken::Word {
+            text: mem::take(&mut word),
+            assignment: assignment.take(),
+          });
+          started = false;
+        }
+        literal = true;
+      }};
+    }
+
+    while let Some(c) = chars.next() {
+      match c {
+        ' ' | '\t' | '\n' | '\r' => finish!(),
+        '#' if !started => break,
+        '\'' => {
+          started = true;
+          literal = false;
+          loop {
+            match chars.next() {
+              Some('\'') => break,
+              Some(c) => word.push(c),
+              None => return Err("unterminated single quote".into()),
+            }
+          }
+        }
+        '"' => {
+          literal = false;
+          // `"$@"` expands to nothing if there are no positional arguments
+          let length = word.len();
+          let mut empty = false;
+          loop {
+            match chars.next() {
+              Some('"') => {
+                started |= !empty || word.len() > length;
+                break;
+              }
+              Some('\\') => match chars.peek().copied() {
+                Some(c @ ('$' | '`' | '"' | '\\')) => {
+                  chars.next();
+                  word.push(c);
+                }
+                Some('\n') => {
+                  chars.next();
+                }
+                _ => word.push('\\'),
+              },
+              Some('$') => {
+                let fields = Self::expand(&mut chars, lookup, positional)?;
+                empty |= fields.is_empty();
+                for (i, field) in fields.into_iter().enumerate() {
+                  if i > 0 {
+                    tokens.push(ShellToken::Word {
+                      text: mem::take(&mut word),
+                      assignment: assignment.take(),
+                    });
+                  }
+                  word.push_str(&field);
+                }
+              }
+              Some('`') => return Err(Self::unsupported("`")),
+              Some(c) => word.push(c),
+              None => return Err("unterminated double quote".into()),
+            }
+          }
+        }
+        '\\' => match chars.next() {
+          Some('\n') => {}
+          Some(c) => {
+            started = true;
+            literal = false;
+            word.push(c);
+          }
+          None => {
+            started = true;
+            word.push('\\');
+          }
+        },
+        '$' => {
+          literal = false;
+          // as in POSIX shells, a word consisting only of unquoted
+          // expansions which are empty is dropped
+          for (i, field) in Self::expand(&mut chars, lookup, positional)?
+            .into_iter()
+            .enumerate()
+          {
+            if i > 0 && started {
+              tokens.push(ShellToken::Word {
+                text: mem::take(&mut word),
+                assignment: assignment.take(),
+              });
+              started = false;
+            }
+            started |= !field.is_empty();
+            word.push_str(&field);
+          }
+        }
+        '&' | '|' => {
+          if chars.peek() != Some(&c) {
+            return Err(Self::unsupported(&c.to_string()));
+          }
+          chars.next();
+          finish!();
+          tokens.push(ShellToken::Connector(if c == '&' {
+            Connector::And
+          } else {
+            Connector::Or
+          }));
+        }
+        '<' | '>' => {
+          let fd = if started && literal && word.len() == 1 && word.as_bytes()[0].is_ascii_digit() {
+            let fd = word.parse().unwrap();
+            word.clear();
+            started = false;
+            assignment = None;
+            fd
+          } else {
+            finish!();
+            u32::from(c == '>')
+          };
+
+          let operator = match (c, chars.peek()) {
+            ('<', Some('<')) => return Err(Self::unsupported("<<")),
+            ('<', _) => "<",
+            ('>', Some('>')) => ">>",
+            ('>', Some('&')) => ">&",
+            _ => ">",
+          };
+
+          if operator.len() == 2 {
+            chars.next();
+          }
+
+          tokens.push(ShellToken::Redirection { fd, operator });
+        }
+        // `*`, `?`, and `[` are globs, and `~` at the start of a word or of
+        // the value of an assignment is expanded to the home directory
+        ';' | '(' | ')' | '`' | '*' | '?' => return Err(Self::unsupported(&c.to_string())),
+        '['
+          if chars
+            .clone()
+            .take_while(|c| !c.is_whitespace())
+            .any(|c| c == ']') =>
+        {
+          return Err(Self::unsupported("["))
+        }
+        '~' if !started || assignment.map_or(false, |i| i + 1 == word.len()) => {
+          return Err(Self::unsupported("~"))
+        }
+        '=' if literal && assignment.is_none() && Self::is_name(&word) => {
+          assignment = Some(word.len());
+          word.push('=');
+        }
+        c => {
+          started = true;
+          word.push(c);
+        }
+      }
+    }
+
+    if started {
+      tokens.push(ShellToken::Word {
+        text: word,
+        assignment,
+      });
+    }
+
+    Ok(tokens)
+  }
+
+  /// Expand the parameter following a `$`, returning its fields. All
+  /// parameters expand to a single field, except for `$@`, which expands to
+  /// one field for each positional argument.
+  fn expand(
+    chars: &mut Peekable<Chars>,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    positional: Option<&[String]>,
+  ) -> Result<Vec<String>, String> {
+    let name = match chars.peek().copied() {
+      Some('{') => {
+        chars.next();
+        let mut name = String::new();
+        loop {
+          match chars.next() {
+            Some('}') => break,
+            Some(c) => name.push(c),
+            None => return Err("unterminated `${`".into()),
+          }
+        }
+        if !Self::is_name(&name) && !Self::is_positional(&name) {
+          return Err(format!("bad substitution `${{{name}}}`"));
+        }
+        name
+      }
+      Some(c) if c == '_' || c.is_ascii_alphabetic() => {
+        let mut name = String::new();
+        while let Some(c) = chars.peek().copied() {
+          if c == '_' || c.is_ascii_alphanumeric() {
+            name.push(c);
+            chars.next();
+          } else {
+            break;
+          }
+        }
+        name
+      }
+      Some(c) if c.is_ascii_digit() || "#*@".contains(c) => {
+        chars.next();
+        c.to_string()
+      }
+      Some('(') => return Err(Self::unsupported("$(")),
+      Some(c) if "!$-?".contains(c) => {
+        return Err(Self::unsupported(&format!("${c}")));
+      }
+      _ => return Ok(vec!["$".into()]),
+    };
+
+    if !Self::is_positional(&name) {
+      return Ok(vec![
+        lookup(&name).ok_or_else(|| format!("`{name}` is not set"))?
+      ]);
+    }
+
+    let Some(positional) = positional else {
+      return Err(format!(
+        "`${name}` is not supported by the builtin shell without `set positional-arguments`"
+      ));
+    };
+
+    let arguments = positional.get(1..).unwrap_or_default();
+
+    Ok(match name.as_str() {
+      "#" => vec![arguments.len().to_string()],
+      "*" => vec![arguments.join(" ")],
+      "@" => arguments.to_vec(),
+      _ => vec![name
+        .parse::<usize>()
+        .ok()
+        .and_then(|i| positional.get(i))
+        .cloned()
+        .unwrap_or_default()],
+    })
+  }
+
+  /// Whether `name` is a positional parameter, like `1`, or a special
+  /// parameter that expands to positional arguments, like `@`
+  fn is_positional(name: &str) -> bool {
+    matches!(name, "#" | "*" | "@")
+      || (!name.is_empty() && name.chars().all(|c| c.is_ascii_digit()))
+  }
+
+  fn is_name(text: &str) -> bool {
+    let mut chars = text.chars();
+
+    chars
+      .next()
+      .map_or(false, |c| c == '_' || c.is_ascii_alphabetic())
+      && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+  }
+
+  fn unsupported(syntax: &str) -> String {
+    format!("`{syntax}` is not supported by the builtin shell")
+  }
+
+  fn execute(
+    &self,
+    configure: &dyn Fn(&mut Command),
+    stdout: &Destination,
+    stderr: &Destination,
+    captured: &mut Vec<u8>,
+  ) -> Result<ExitStatus, String> {
+    let mut status = Platform::exit_status(0);
+
+    for (connector, command) in &self.commands {
+      match connector {
+        Some(Connector::And) if !status.success() => continue,
+        Some(Connector::Or) if status.success() => continue,
+        _ => {}
+      }
+
+      status = command.execute(configure, stdout, stderr, captured)?;
+    }
+
+    Ok(status)
+  }
+}
+
+impl SimpleCommand {
+  fn execute(
+    &self,
+    configure: &dyn Fn(&mut Command),
+    stdout: &Destination,
+    stderr: &Destination,
+    captured: &mut Vec<u8>,
+  ) -> Result<ExitStatus, String> {
+    let mut command = Command::new(self.words.first().map_or("", String::as_str));
+
+    configure(&mut command);
+
+    let working_directory = command.get_current_dir().map(Path::to_owned);
+
+    let open = |path: &str, options: &OpenOptions| {
+      let resolved = match &working_directory {
+        Some(working_directory) => working_directory.join(path),
+        None => PathBuf::from(path),
+      };
+      options
+        .open(resolved)
+        .map_err(|io_error| format!("could not open `{path}`: {io_error}"))
+    };
+
+    let clone = |destination: &Destination| {
+      destination
+        .try_clone()
+        .map_err(|io_error| io_error.to_string())
+    };
+
+    let mut stdin = None;
+    let mut stdout = clone(stdout)?;
+    let mut stderr = clone(stderr)?;
+
+    for Redirection { fd, target } in &self.redirections {
+      let destination = match target {
+        Target::Read(path) => {
+          stdin = Some(open(path, OpenOptions::new().read(true))?);
+          continue;
+        }
+        Target::Write(path) => Destination::File(open(
+          path,
+          OpenOptions::new().write(true).create(true).truncate(true),
+        )?),
+        Target::Append(path) => {
+          Destination::File(open(path, OpenOptions::new().append(true).create(true))?)
+        }
+        Target::Duplicate(1) => clone(&stdout)?,
+        Target::Duplicate(_) => clone(&stderr)?,
+      };
+
+      if *fd == 1 {
+        stdout = destination;
+      } else {
+        stderr = destination;
+      }
+    }
+
+    let Some(program) = self.words.first() else {
+      return Ok(Platform::exit_status(0));
+    };
+
+    command.args(&self.words[1..]);
+
+    command.envs(self.assignments.iter().map(|(name, value)| (name, value)));
+
+    if let Some(stdin) = stdin {
+      command.stdin(stdin);
+    }
+
+    command.stdout(stdout.stdio(1)?);
+    command.stderr(stderr.stdio(2)?);
+
+    let child = match command.spawn() {
+      Ok(child) => child,
+      Err(io_error) => {
+        if !matches!(stderr, Destination::Null) {
+          eprintln!("{program}: {io_error}");
+        }
+        return Ok(Platform::exit_status(CANNOT_RUN_EXIT_CODE));
+      }
+    };
+
+    let output = child
+      .wait_with_output()
+      .map_err(|io_error| io_error.to_string())?;
+
+    captured.extend(output.stdout);
+
+    Ok(output.status)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(line: &str) -> Result<BuiltinShell, String> {
+    parse_with_positional(line, None)
+  }
+
+  fn parse_with_positional(
+    line: &str,
+    positional: Option<&[String]>,
+  ) -> Result<BuiltinShell, String> {
+    BuiltinShell::parse(
+      line,
+      &|name| match name {
+        "FOO" => Some("foo".into()),
+        "EMPTY" => Some(String::new()),
+        "SPACES" => Some("a b".into()),
+        _ => None,
+      },
+      positional,
+    )
+  }
+
+  fn words(line: &str) -> Vec<String> {
+    let shell = parse(line).unwrap();
+    assert_eq!(shell.commands.len(), 1);
+    shell.commands.into_iter().next().unwrap().1.words
+  }
+
+  #[test]
+  fn quoting() {
+    assert_eq!(words("echo a  b"), ["echo", "a", "b"]);
+    assert_eq!(words("echo 'a  b' \"c  d\""), ["echo", "a  b", "c  d"]);
+    assert_eq!(words("echo a\\ b"), ["echo", "a b"]);
+    assert_eq!(words("echo '' \"\""), ["echo", "", ""]);
+    assert_eq!(words("echo \"\\\"\\$\\a\""), ["echo", "\"$\\a"]);
+    assert_eq!(words("echo 'a'\"b\"c"), ["echo", "abc"]);
+    assert_eq!(words("echo '$FOO' \\$FOO"), ["echo", "$FOO", "$FOO"]);
+    assert_eq!(words("[ '*' \\? a~ ]"), ["[", "*", "?", "a~", "]"]);
+  }
+
+  #[test]
+  fn comments() {
+    assert_eq!(words("echo a # b"), ["echo", "a"]);
+    assert_eq!(words("echo a#b"), ["echo", "a#b"]);
+    assert_eq!(parse("# comment").unwrap().commands, []);
+  }
+
+  #[test]
+  fn expansion() {
+    assert_eq!(
+      words("echo $FOO ${FOO}bar \"$SPACES\""),
+      ["echo", "foo", "foobar", "a b"]
+    );
+    assert_eq!(words("echo $SPACES"), ["echo", "a b"]);
+    assert_eq!(words("echo $EMPTY x"), ["echo", "x"]);
+    assert_eq!(words("echo \"$EMPTY\" ''$EMPTY"), ["echo", "", ""]);
+    assert_eq!(words("echo a$EMPTY"), ["echo", "a"]);
+    assert_eq!(words("echo $ a$"), ["echo", "$", "a$"]);
+    assert_eq!(parse("echo $BAR").unwrap_err(), "`BAR` is not set");
+    assert_eq!(
+      parse("echo ${FOO:-bar}").unwrap_err(),
+      "bad substitution `${FOO:-bar}`"
+    );
+  }
+
+  #[test]
+  fn positional_arguments() {
+    fn words(line: &str, arguments: &[&str]) -> Vec<String> {
+      let positional = iter::once("foo")
+        .chain(arguments.iter().copied())
+        .map(str::to_owned)
+        .collect::<Vec<String>>();
+      let shell = parse_with_positional(line, Some(&positional)).unwrap();
+      shell.commands.into_iter().next().unwrap().1.words
+    }
+
+    assert_eq!(
+      words("echo $0 $1 ${2} $3 $#", &["a b", "c"]),
+      ["echo", "foo", "a b", "c", "2"]
+    );
+    assert_eq!(
+      words("echo $@ \"$*\"", &["a b", "c"]),
+      ["echo", "a b", "c", "a b c"]
+    );
+    assert_eq!(words("echo x\"$@\"y", &["a", "b"]), ["echo", "xa", "by"]);
+    assert_eq!(words("echo \"$@\" $@", &[]), ["echo"]);
+    assert_eq!(words("echo $@", &["", "a"]), ["echo", "a"]);
+    assert_eq!(words("echo \"$@\"", &["", "a"]), ["echo", "", "a"]);
+    assert_eq!(
+      parse("echo $1").unwrap_err(),
+      "`$1` is not supported by the builtin shell without `set positional-arguments`"
+    );
+  }
+
+  #[test]
+  fn assignments() {
+    let shell = parse("A=a B='b c' echo C=c").unwrap();
+    let command = &shell.commands[0].1;
+    assert_eq!(
+      command.assignments,
+      [("A".into(), "a".into()), ("B".into(), "b c".into())]
+    );
+    assert_eq!(command.words, ["echo", "C=c"]);
+    assert_eq!(words("'A'=a"), ["A=a"]);
+  }
+
+  #[test]
+  fn connectors() {
+    let shell = parse("a && b || c").unwrap();
+    assert_eq!(
+      shell
+        .commands
+        .iter()
+        .map(|(connector, command)| (*connector, command.words[0].as_str()))
+        .collect::<Vec<(Option<Connector>, &str)>>(),
+      [
+        (None, "a"),
+        (Some(Connector::And), "b"),
+        (Some(Connector::Or), "c")
+      ]
+    );
+    assert_eq!(parse("&& a").unwrap_err(), "expected command before `&&`");
+    assert_eq!(parse("a ||").unwrap_err(), "expected command after `||`");
+  }
+
+  #[test]
+  fn redirections() {
+    let shell = parse("a < in > out 2>> err >&2 2>&1").unwrap();
+    assert_eq!(
+      shell.commands[0].1.redirections,
+      [
+        Redirection {
+          fd: 0,
+          target: Target::Read("in".into())
+        },
+        Redirection {
+          fd: 1,
+          target: Target::Write("out".into())
+        },
+        Redirection {
+          fd: 2,
+          target: Target::Append("err".into())
+        },
+        Redirection {
+          fd: 1,
+          target: Target::Duplicate(2)
+        },
+        Redirection {
+          fd: 2,
+          target: Target::Duplicate(1)
+        },
+      ]
+    );
+    assert_eq!(words("echo '2'>x"), ["echo", "2"]);
+    assert_eq!(parse("a >").unwrap_err(), "expected file after `>`");
+    assert_eq!(
+      parse("a 3> x").unwrap_err(),
+      "cannot redirect file descriptor 3 with `>`"
+    );
+  }
+
+  #[test]
+  fn unsupported() {
+    for line in [
+      "a | b",
+      "a; b",
+      "a &",
+      "(a)",
+      "echo `a`",
+      "echo $(a)",
+      "cat << EOF",
+      "echo *.txt",
+      "echo a?",
+      "echo a[bc]",
+      "echo ~",
+      "A=~/a echo",
+    ] {
+      assert!(
+        parse(line)
+          .unwrap_err()
+          .ends_with("is not supported by the builtin shell"),
+        "{line}"
+      );
+    }
+  }
+}
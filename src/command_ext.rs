@@ -1,13 +1,25 @@
 use super::*;
 
 pub(crate) trait CommandExt {
-  fn export(&mut self, settings: &Settings, dotenv: &BTreeMap<String, String>, scope: &Scope);
+  fn export(
+    &mut self,
+    settings: &Settings,
+    dotenv: &BTreeMap<String, String>,
+    scope: &Scope,
+    unexports: &BTreeSet<String>,
+  );
 
   fn export_scope(&mut self, settings: &Settings, scope: &Scope);
 }
 
 impl CommandExt for Command {
-  fn export(&mut self, settings: &Settings, dotenv: &BTreeMap<String, String>, scope: &Scope) {
+  fn export(
+    &mut self,
+    settings: &Settings,
+    dotenv: &BTreeMap<String, String>,
+    scope: &Scope,
+    unexports: &BTreeSet<String>,
+  ) {
     for (name, value) in dotenv {
       self.env(name, value);
     }
@@ -15,6 +27,10 @@ impl CommandExt for Command {
     if let Some(parent) = scope.parent() {
       self.export_scope(settings, parent);
     }
+
+    for name in unexports {
+      self.env_remove(name);
+    }
   }
 
   fn export_scope(&mut self, settings: &Settings, scope: &Scope) {
@@ -24,7 +40,7 @@ impl CommandExt for Command {
 
     for binding in scope.bindings() {
       if settings.export || binding.export {
-        self.env(binding.name.lexeme(), &binding.value);
+        self.env(binding.name.lexeme(), binding.value.to_string());
       }
     }
   }
@@ -17,6 +17,35 @@ impl<'src> CompileError<'src> {
       kind: Box::new(kind),
     }
   }
+
+  /// Write that `item`, first `verb` on line `first` of `first_path`, is
+  /// redefined, including the paths of both definitions if they differ
+  fn redefinition(
+    &self,
+    f: &mut Formatter,
+    item: &str,
+    verb: &str,
+    first: usize,
+    first_path: &Path,
+  ) -> fmt::Result {
+    if first_path == self.token.path {
+      write!(
+        f,
+        "{item} first {verb} on line {} is redefined on line {}",
+        first.ordinal(),
+        self.token.line.ordinal(),
+      )
+    } else {
+      write!(
+        f,
+        "{item} first {verb} at {}:{} is redefined at {}:{}",
+        first_path.display(),
+        first.ordinal(),
+        self.token.path.display(),
+        self.token.line.ordinal(),
+      )
+    }
+  }
 }
 
 impl Display for CompileError<'_> {
@@ -27,7 +56,7 @@ impl Display for CompileError<'_> {
       AliasInvalidAttribute { alias, attr } => write!(
         f,
         "Alias {alias} has an invalid attribute `{}`",
-        attr.to_str(),
+        attr.name(),
       ),
       AliasShadowsRecipe { alias, recipe_line } => write!(
         f,
@@ -35,7 +64,47 @@ impl Display for CompileError<'_> {
         self.token.line.ordinal(),
         recipe_line.ordinal(),
       ),
+      ArgAttributeValueRequiresOption => write!(
+        f,
+        "Argument attribute `value` requires a `long` or `short` option"
+      ),
+      ArgumentPattern { pattern, source } => {
+        write!(f, "Failed to parse argument pattern `{pattern}`: {source}")
+      }
+      AttributeArgumentCountMismatch {
+        attribute,
+        found,
+        expected,
+      } => write!(
+        f,
+        "Attribute `{attribute}` called with {found} {} but takes {} {}",
+        Count("argument", *found),
+        expected.display(),
+        Count("argument", *expected.end()),
+      ),
+      AttributeArgumentNotStringLiteral { attribute } => {
+        write!(f, "Arguments of attribute `{attribute}` must be string literals")
+      }
       BacktickShebang => write!(f, "Backticks may not start with `#!`"),
+      CachedWithoutBacktick => write!(f, "Function `cached` must be called with a backtick as its first argument"),
+      CaptureAndFreshnessAttribute { attribute, recipe } => write!(
+        f,
+        "Recipe `{recipe}` has both `[capture]` and `[{attribute}]` attributes"
+      ),
+      CircularFunctionDependency {
+        function,
+        ref circle,
+      } => {
+        if circle.len() == 2 {
+          write!(f, "Function `{function}` calls itself")
+        } else {
+          write!(
+            f,
+            "Function `{function}` has circular dependency `{}`",
+            circle.join(" -> ")
+          )
+        }
+      }
       CircularRecipeDependency { recipe, ref circle } => {
         if circle.len() == 2 {
           write!(f, "Recipe `{recipe}` depends on itself")
@@ -61,6 +130,11 @@ impl Display for CompileError<'_> {
           )
         }
       }
+      ConfirmPromptInterpolation => write!(
+        f,
+        "`[confirm]` prompts do not support `{{{{…}}}}` interpolation, use concatenation instead, \
+         for example `[confirm('Deploy ' + env + '?')]`"
+      ),
       DependencyArgumentCountMismatch {
         dependency,
         found,
@@ -82,35 +156,96 @@ impl Display for CompileError<'_> {
           write!(f, "at most {max} {}", Count("argument", *max))
         }
       }
-      DuplicateAlias { alias, first } => write!(
+      DependencyRequiresOption { dependency, option } => write!(
         f,
-        "Alias `{alias}` first defined on line {} is redefined on line {}",
-        first.ordinal(),
-        self.token.line.ordinal(),
+        "Dependency `{dependency}` requires option `{option}`, but options cannot be passed to dependencies",
       ),
+      DuplicateAlias {
+        alias,
+        first,
+        first_path,
+      } => self.redefinition(f, &format!("Alias `{alias}`"), "defined", *first, first_path),
       DuplicateAttribute { attribute, first } => write!(
         f,
         "Recipe attribute `{attribute}` first used on line {} is duplicated on line {}",
         first.ordinal(),
         self.token.line.ordinal(),
       ),
+      DuplicateArgAttribute { parameter } => write!(
+        f,
+        "Recipe parameter `{parameter}` has multiple `[arg]` attributes"
+      ),
+      DuplicateAttributeKeyword { attribute, keyword } => write!(
+        f,
+        "Attribute `{attribute}` keyword `{keyword}` is given more than once"
+      ),
+      DuplicateEnvAttribute { variable } => write!(
+        f,
+        "Recipe has multiple `[env]` attributes for environment variable `{variable}`"
+      ),
+      DuplicateFunction {
+        function,
+        first,
+        first_path,
+      } => self.redefinition(
+        f,
+        &format!("Function `{function}`"),
+        "defined",
+        *first,
+        first_path,
+      ),
+      DuplicateFunctionParameter {
+        function,
+        parameter,
+      } => write!(
+        f,
+        "Function `{function}` has duplicate parameter `{parameter}`"
+      ),
+      DuplicateModule {
+        module,
+        first,
+        first_path,
+      } => self.redefinition(f, &format!("Module `{module}`"), "defined", *first, first_path),
+      DuplicateOption { option } => write!(f, "Recipe option `{option}` is defined more than once"),
       DuplicateParameter { recipe, parameter } => {
         write!(f, "Recipe `{recipe}` has duplicate parameter `{parameter}`")
       }
-      DuplicateRecipe { recipe, first } => write!(
+      DuplicateRecipe {
+        recipe,
+        first,
+        first_path,
+      } => self.redefinition(f, &format!("Recipe `{recipe}`"), "defined", *first, first_path),
+      DuplicateSet {
+        setting,
+        first,
+        first_path,
+      } => self.redefinition(f, &format!("Setting `{setting}`"), "set", *first, first_path),
+      DuplicateUnexport { variable, first } => write!(
         f,
-        "Recipe `{recipe}` first defined on line {} is redefined on line {}",
+        "Variable `{variable}` first unexported on line {} is unexported again on line {}",
         first.ordinal(),
         self.token.line.ordinal(),
       ),
-      DuplicateSet { setting, first } => write!(
-        f,
-        "Setting `{setting}` first set on line {} is redefined on line {}",
-        first.ordinal(),
-        self.token.line.ordinal(),
-      ),
-      DuplicateVariable { variable } => {
-        write!(f, "Variable `{variable}` has multiple definitions")
+      DuplicateVariable {
+        variable,
+        first,
+        first_path,
+      } => {
+        if *first_path == self.token.path {
+          write!(f, "Variable `{variable}` has multiple definitions")
+        } else {
+          write!(
+            f,
+            "Variable `{variable}` first defined at {}:{} is redefined at {}:{}",
+            first_path.display(),
+            first.ordinal(),
+            self.token.path.display(),
+            self.token.line.ordinal(),
+          )
+        }
+      }
+      ExportUnexported { variable } => {
+        write!(f, "Variable `{variable}` is both exported and unexported")
       }
       ExpectedKeyword { expected, found } => {
         let expected = List::or_ticked(expected);
@@ -135,6 +270,10 @@ impl Display for CompileError<'_> {
         Count("argument", *found),
         expected.display(),
       ),
+      FunctionShadowsBuiltin { function } => write!(
+        f,
+        "Function `{function}` shadows built-in function of the same name"
+      ),
       InconsistentLeadingWhitespace { expected, found } => write!(
         f,
         "Recipe line has inconsistent leading whitespace. Recipe started with `{}` but found \
@@ -158,6 +297,19 @@ impl Display for CompileError<'_> {
           _ => character.escape_default().collect(),
         }
       ),
+      InvalidGlob { glob, message } => write!(f, "Invalid glob `{glob}`: {message}"),
+      InvalidLongOption { option } => write!(
+        f,
+        "Long option `{option}` must be non-empty and may not start with `-` or contain `=` or whitespace"
+      ),
+      InvalidShortOption { option } => write!(
+        f,
+        "Short option `{option}` must be a single character other than `-`"
+      ),
+      LinewiseRecipeAttribute { attribute, recipe } => write!(
+        f,
+        "Recipe `{recipe}` has an `[{attribute}]` attribute but is not a script or shebang recipe"
+      ),
       MismatchedClosingDelimiter {
         open,
         open_line,
@@ -175,6 +327,10 @@ impl Display for CompileError<'_> {
            consist of tabs or spaces, but not both",
         ShowWhitespace(whitespace)
       ),
+      NoCdAndWorkingDirectoryAttribute { recipe } => write!(
+        f,
+        "Recipe `{recipe}` has both `[no-cd]` and `[working-directory]` attributes"
+      ),
       ParameterFollowsVariadicParameter { parameter } => {
         write!(f, "Parameter `{parameter}` follows variadic parameter")
       }
@@ -183,6 +339,17 @@ impl Display for CompileError<'_> {
         f,
         "Non-default parameter `{parameter}` follows default parameter"
       ),
+      ScriptAndShellAttribute { recipe } => write!(
+        f,
+        "Recipe `{recipe}` has both `[script]` and `[shell]` attributes"
+      ),
+      ShebangRecipeAttribute { attribute, recipe } => write!(
+        f,
+        "Recipe `{recipe}` has a shebang and a `[{attribute}]` attribute"
+      ),
+      UndefinedArgAttribute { argument } => {
+        write!(f, "Argument attribute for undefined parameter `{argument}`")
+      }
       UndefinedVariable { variable } => write!(f, "Variable `{variable}` not defined"),
       UnexpectedCharacter { expected } => write!(f, "Expected character `{expected}`"),
       UnexpectedClosingDelimiter { close } => {
@@ -199,6 +366,9 @@ impl Display for CompileError<'_> {
         write!(f, "Alias `{alias}` has an unknown target `{target}`")
       }
       UnknownAttribute { attribute } => write!(f, "Unknown attribute `{attribute}`"),
+      UnknownAttributeKeyword { attribute, keyword } => {
+        write!(f, "Unknown keyword `{keyword}` for `{attribute}` attribute")
+      }
       UnknownDependency { recipe, unknown } => {
         write!(f, "Recipe `{recipe}` has unknown dependency `{unknown}`")
       }
@@ -209,6 +379,9 @@ impl Display for CompileError<'_> {
       UnterminatedBacktick => write!(f, "Unterminated backtick"),
       UnterminatedInterpolation => write!(f, "Unterminated interpolation"),
       UnterminatedString => write!(f, "Unterminated string"),
+      VariadicOption { parameter } => {
+        write!(f, "Variadic parameter `{parameter}` may not be an option")
+      }
     }
   }
 }
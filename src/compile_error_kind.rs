@@ -4,13 +4,35 @@ use super::*;
 pub(crate) enum CompileErrorKind<'src> {
   AliasInvalidAttribute {
     alias: &'src str,
-    attr: Attribute,
+    attr: Attribute<'src>,
   },
   AliasShadowsRecipe {
     alias: &'src str,
     recipe_line: usize,
   },
+  ArgAttributeValueRequiresOption,
+  ArgumentPattern {
+    pattern: String,
+    source: regex::Error,
+  },
+  AttributeArgumentCountMismatch {
+    attribute: &'src str,
+    found: usize,
+    expected: RangeInclusive<usize>,
+  },
+  AttributeArgumentNotStringLiteral {
+    attribute: &'src str,
+  },
   BacktickShebang,
+  CachedWithoutBacktick,
+  CaptureAndFreshnessAttribute {
+    attribute: &'static str,
+    recipe: &'src str,
+  },
+  CircularFunctionDependency {
+    function: &'src str,
+    circle: Vec<&'src str>,
+  },
   CircularRecipeDependency {
     recipe: &'src str,
     circle: Vec<&'src str>,
@@ -19,20 +41,53 @@ pub(crate) enum CompileErrorKind<'src> {
     variable: &'src str,
     circle: Vec<&'src str>,
   },
+  ConfirmPromptInterpolation,
   DependencyArgumentCountMismatch {
     dependency: &'src str,
     found: usize,
     min: usize,
     max: usize,
   },
+  DependencyRequiresOption {
+    dependency: &'src str,
+    option: String,
+  },
   DuplicateAlias {
     alias: &'src str,
     first: usize,
+    first_path: &'src Path,
   },
   DuplicateAttribute {
     attribute: &'src str,
     first: usize,
   },
+  DuplicateArgAttribute {
+    parameter: &'src str,
+  },
+  DuplicateAttributeKeyword {
+    attribute: &'src str,
+    keyword: &'src str,
+  },
+  DuplicateEnvAttribute {
+    variable: String,
+  },
+  DuplicateFunction {
+    function: &'src str,
+    first: usize,
+    first_path: &'src Path,
+  },
+  DuplicateFunctionParameter {
+    function: &'src str,
+    parameter: &'src str,
+  },
+  DuplicateModule {
+    module: &'src str,
+    first: usize,
+    first_path: &'src Path,
+  },
+  DuplicateOption {
+    option: String,
+  },
   DuplicateParameter {
     recipe: &'src str,
     parameter: &'src str,
@@ -40,24 +95,38 @@ pub(crate) enum CompileErrorKind<'src> {
   DuplicateRecipe {
     recipe: &'src str,
     first: usize,
+    first_path: &'src Path,
   },
   DuplicateSet {
     setting: &'src str,
     first: usize,
+    first_path: &'src Path,
+  },
+  DuplicateUnexport {
+    variable: &'src str,
+    first: usize,
   },
   DuplicateVariable {
     variable: &'src str,
+    first: usize,
+    first_path: &'src Path,
   },
   ExpectedKeyword {
     expected: Vec<Keyword>,
     found: Token<'src>,
   },
+  ExportUnexported {
+    variable: &'src str,
+  },
   ExtraLeadingWhitespace,
   FunctionArgumentCountMismatch {
     function: &'src str,
     found: usize,
     expected: Range<usize>,
   },
+  FunctionShadowsBuiltin {
+    function: &'src str,
+  },
   InconsistentLeadingWhitespace {
     expected: &'src str,
     found: &'src str,
@@ -68,6 +137,20 @@ pub(crate) enum CompileErrorKind<'src> {
   InvalidEscapeSequence {
     character: char,
   },
+  InvalidGlob {
+    glob: String,
+    message: &'static str,
+  },
+  InvalidLongOption {
+    option: String,
+  },
+  InvalidShortOption {
+    option: String,
+  },
+  LinewiseRecipeAttribute {
+    attribute: &'static str,
+    recipe: &'src str,
+  },
   MismatchedClosingDelimiter {
     close: Delimiter,
     open: Delimiter,
@@ -76,6 +159,9 @@ pub(crate) enum CompileErrorKind<'src> {
   MixedLeadingWhitespace {
     whitespace: &'src str,
   },
+  NoCdAndWorkingDirectoryAttribute {
+    recipe: &'src str,
+  },
   ParameterFollowsVariadicParameter {
     parameter: &'src str,
   },
@@ -83,6 +169,16 @@ pub(crate) enum CompileErrorKind<'src> {
   RequiredParameterFollowsDefaultParameter {
     parameter: &'src str,
   },
+  ScriptAndShellAttribute {
+    recipe: &'src str,
+  },
+  ShebangRecipeAttribute {
+    attribute: &'static str,
+    recipe: &'src str,
+  },
+  UndefinedArgAttribute {
+    argument: &'src str,
+  },
   UndefinedVariable {
     variable: &'src str,
   },
@@ -106,6 +202,10 @@ pub(crate) enum CompileErrorKind<'src> {
   UnknownAttribute {
     attribute: &'src str,
   },
+  UnknownAttributeKeyword {
+    attribute: &'src str,
+    keyword: &'src str,
+  },
   UnknownDependency {
     recipe: &'src str,
     unknown: &'src str,
@@ -121,4 +221,7 @@ pub(crate) enum CompileErrorKind<'src> {
   UnterminatedBacktick,
   UnterminatedInterpolation,
   UnterminatedString,
+  VariadicOption {
+    parameter: &'src str,
+  },
 }
@@ -3,11 +3,156 @@ use super::*;
 pub(crate) struct Compiler;
 
 impl Compiler {
-  pub(crate) fn compile(src: &str) -> CompileResult<(Ast, Justfile)> {
-    let tokens = Lexer::lex(src)?;
-    let ast = Parser::parse(&tokens)?;
-    let justfile = Analyzer::analyze(&ast)?;
+  /// Compile the justfile at `root`, loading, lexing, and parsing any files
+  /// that it or its imports reference with `import` or `mod` items.
+  pub(crate) fn compile<'src>(
+    loader: &'src Loader,
+    root: &Path,
+  ) -> RunResult<'src, Compilation<'src>> {
+    let mut asts = HashMap::new();
+    let mut children = HashMap::<PathBuf, Vec<PathBuf>>::new();
+    let mut paths = HashMap::new();
+    let mut srcs = HashMap::new();
+
+    // Each entry is a file to load and the chain of files that led to it,
+    // which is used to detect circular imports
+    let mut stack = vec![(root.to_owned(), Vec::new())];
+
+    while let Some((current, mut chain)) = stack.pop() {
+      chain.push(current.clone());
+
+      // Files reachable by more than one path, as in a diamond of imports,
+      // are only loaded once, but their imports are still followed so that
+      // cycles are detected
+      if let Some(children) = children.get(&current) {
+        for child in children {
+          Self::check_circular(&chain, child)?;
+          stack.push((child.clone(), chain.clone()));
+        }
+        continue;
+      }
+
+      let (relative, src) = loader.load(root, &current)?;
+      let tokens = Lexer::lex(relative, src)?;
+      let mut ast = Parser::parse(&tokens)?;
+
+      let directory = current.parent().unwrap();
+
+      let mut imports = Vec::new();
+
+      for item in &mut ast.items {
+        match item {
+          Item::Import {
+            absolute,
+            path,
+            relative,
+          } => {
+            let import = directory.join(&relative.cooked).lexiclean();
+
+            if !import.is_file() {
+              return Err(Error::MissingImportFile { path: *path });
+            }
+
+            Self::check_circular(&chain, &import)?;
+            *absolute = Some(import.clone());
+            imports.push(import);
+          }
+          Item::Module {
+            absolute,
+            name,
+            path,
+          } => {
+            let module = match path {
+              Some(path) => {
+                let module = directory.join(&path.cooked).lexiclean();
+
+                if !module.is_file() {
+                  return Err(Error::MissingModuleFile { module: *name });
+                }
+
+                module
+              }
+              None => Self::find_module_file(directory, *name)?,
+            };
+
+            Self::check_circular(&chain, &module)?;
+            *absolute = Some(module.clone());
+            imports.push(module);
+          }
+          _ => {}
+        }
+      }
 
-    Ok((ast, justfile))
+      for import in &imports {
+        stack.push((import.clone(), chain.clone()));
+      }
+
+      children.insert(current.clone(), imports);
+      paths.insert(current.clone(), relative.to_owned());
+      srcs.insert(current.clone(), src);
+      asts.insert(current, ast);
+    }
+
+    let justfile = Analyzer::analyze(&paths, &asts, root, None, "")?;
+
+    Ok(Compilation {
+      asts,
+      justfile,
+      root: root.into(),
+      srcs,
+    })
+  }
+
+  /// Find the source file of module `name`, declared in a file in
+  /// `directory`, which may be `NAME.just`, `NAME/mod.just`, `NAME/justfile`,
+  /// or `NAME/.justfile`.
+  fn find_module_file<'src>(directory: &Path, name: Name<'src>) -> RunResult<'src, PathBuf> {
+    let candidates = [
+      format!("{name}.just"),
+      format!("{name}/mod.just"),
+      format!("{name}/justfile"),
+      format!("{name}/.justfile"),
+    ];
+
+    let mut found = candidates
+      .iter()
+      .map(|candidate| directory.join(candidate))
+      .filter(|path| path.is_file())
+      .collect::<Vec<PathBuf>>();
+
+    match found.len() {
+      0 => Err(Error::MissingModuleFile { module: name }),
+      1 => Ok(found.pop().unwrap()),
+      _ => Err(Error::AmbiguousModuleFile {
+        module: name,
+        found: found
+          .into_iter()
+          .map(|path| path.strip_prefix(directory).unwrap().into())
+          .collect(),
+      }),
+    }
+  }
+
+  fn check_circular<'src>(chain: &[PathBuf], import: &Path) -> RunResult<'src, ()> {
+    if chain.iter().any(|path| path == import) {
+      return Err(Error::CircularImport {
+        current: chain.last().unwrap().clone(),
+        import: import.into(),
+      });
+    }
+
+    Ok(())
+  }
+
+  #[cfg(test)]
+  pub(crate) fn test_compile(src: &str) -> CompileResult<Justfile> {
+    let tokens = Lexer::test_lex(src)?;
+    let ast = Parser::parse(&tokens)?;
+    let root = PathBuf::from("justfile");
+    let mut asts = HashMap::new();
+    asts.insert(root.clone(), ast);
+    let mut paths = HashMap::new();
+    paths.insert(root.clone(), root.clone());
+    Analyzer::analyze(&paths, &asts, &root, None, "")
   }
 }
@@ -13,6 +13,8 @@ pub(crate) const FISH_RECIPE_COMPLETIONS: &str = r#"function __fish_just_complet
           gsub(/^[[:space:]]+|[[:space:]]+$/, "", args);
         }
 
+        gsub(/: \(/, "=(", args);
+        gsub(/ \| /, "|", args);
         gsub(/\+|=[`\'"][^`\'"]*[`\'"]/, "", args);
         gsub(/ /, ",", args);
 
@@ -204,6 +206,25 @@ pub(crate) const BASH_COMPLETION_REPLACEMENTS: &[(&str, &str)] = &[
                         COMPREPLY=( $(compgen -W "${recipes}" -- "${cur}") )
                         return 0
                     fi
+                else
+                    local choices=$(just --show "${COMP_WORDS[1]}" 2> /dev/null | awk '
+                        /^[#[]/ { next }
+                        {
+                            while (match($0, /: \([^)]*\)/)) {
+                                n = split(substr($0, RSTART + 3, RLENGTH - 4), values, "|");
+                                for (i = 1; i <= n; i++) {
+                                    gsub(/^[[:space:]]*["\047]|["\047][[:space:]]*$/, "", values[i]);
+                                    print values[i];
+                                }
+                                $0 = substr($0, RSTART + RLENGTH);
+                            }
+                            exit;
+                        }')
+
+                    if [[ -n "${choices}" ]]; then
+                        COMPREPLY=( $(compgen -W "${choices}" -- "${cur}") )
+                        return 0
+                    fi
                 fi"#,
   ),
   (r"            just)", r#"            "$1")"#),
@@ -0,0 +1,98 @@
+use super::*;
+
+/// The condition of a conditional expression, e.g. `a == b && c in ["d"]`.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) enum Condition<'src> {
+  /// `lhs && rhs`
+  And {
+    lhs: Box<Condition<'src>>,
+    rhs: Box<Condition<'src>>,
+  },
+  /// `lhs == rhs`, `lhs != rhs`, `lhs =~ rhs`, or `lhs !~ rhs`
+  Comparison {
+    lhs: Box<Expression<'src>>,
+    rhs: Box<Expression<'src>>,
+    operator: ConditionalOperator,
+  },
+  /// `(contents)`
+  Group { contents: Box<Condition<'src>> },
+  /// `lhs in [a, b]`
+  In {
+    lhs: Box<Expression<'src>>,
+    list: Vec<Expression<'src>>,
+  },
+  /// `!condition`
+  Not { condition: Box<Condition<'src>> },
+  /// `lhs || rhs`
+  Or {
+    lhs: Box<Condition<'src>>,
+    rhs: Box<Condition<'src>>,
+  },
+}
+
+impl Display for Condition<'_> {
+  fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+    match self {
+      Self::And { lhs, rhs } => write!(f, "{lhs} && {rhs}"),
+      Self::Comparison { lhs, rhs, operator } => write!(f, "{lhs} {operator} {rhs}"),
+      Self::Group { contents } => write!(f, "({contents})"),
+      Self::In { lhs, list } => {
+        write!(f, "{lhs} {} [", Keyword::In)?;
+        for (i, expression) in list.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{expression}")?;
+        }
+        write!(f, "]")
+      }
+      Self::Not { condition } => write!(f, "!{condition}"),
+      Self::Or { lhs, rhs } => write!(f, "{lhs} || {rhs}"),
+    }
+  }
+}
+
+impl Serialize for Condition<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    match self {
+      Self::And { lhs, rhs } => {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element("&&")?;
+        seq.serialize_element(lhs)?;
+        seq.serialize_element(rhs)?;
+        seq.end()
+      }
+      Self::Comparison { lhs, rhs, operator } => {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element(&operator.to_string())?;
+        seq.serialize_element(lhs)?;
+        seq.serialize_element(rhs)?;
+        seq.end()
+      }
+      Self::Group { contents } => contents.serialize(serializer),
+      Self::In { lhs, list } => {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element(Keyword::In.lexeme())?;
+        seq.serialize_element(lhs)?;
+        seq.serialize_element(list)?;
+        seq.end()
+      }
+      Self::Not { condition } => {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element("!")?;
+        seq.serialize_element(condition)?;
+        seq.end()
+      }
+      Self::Or { lhs, rhs } => {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element("||")?;
+        seq.serialize_element(lhs)?;
+        seq.serialize_element(rhs)?;
+        seq.end()
+      }
+    }
+  }
+}
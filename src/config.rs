@@ -10,6 +10,9 @@ pub(crate) const CHOOSE_HELP: &str = "Select one or more recipes to run using a
                                       `--chooser` is not passed the chooser defaults to the value \
                                       of $JUST_CHOOSER, falling back to `fzf`";
 
+const JUSTFILE_NAMES_ENVIRONMENT_KEY: &str = "JUST_JUSTFILE_NAMES";
+const ROOT_MARKERS_ENVIRONMENT_KEY: &str = "JUST_ROOT_MARKERS";
+
 #[derive(Debug, PartialEq)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct Config {
@@ -20,16 +23,24 @@ pub(crate) struct Config {
   pub(crate) dotenv_path: Option<PathBuf>,
   pub(crate) dry_run: bool,
   pub(crate) dump_format: DumpFormat,
+  pub(crate) events_file: Option<PathBuf>,
+  pub(crate) force: bool,
   pub(crate) highlight: bool,
   pub(crate) invocation_directory: PathBuf,
+  pub(crate) jobs: Option<usize>,
+  pub(crate) justfile_names: Vec<String>,
   pub(crate) list_heading: String,
   pub(crate) list_prefix: String,
   pub(crate) load_dotenv: bool,
+  pub(crate) output_format: OutputFormat,
+  pub(crate) root_markers: Vec<String>,
   pub(crate) search_config: SearchConfig,
   pub(crate) shell: Option<String>,
   pub(crate) shell_args: Option<Vec<String>>,
   pub(crate) shell_command: bool,
   pub(crate) subcommand: Subcommand,
+  pub(crate) timings: bool,
+  pub(crate) timings_trace: Option<PathBuf>,
   pub(crate) unsorted: bool,
   pub(crate) unstable: bool,
   pub(crate) verbosity: Verbosity,
@@ -39,14 +50,17 @@ pub(crate) struct Config {
 mod cmd {
   pub(crate) const CHANGELOG: &str = "CHANGELOG";
   pub(crate) const CHOOSE: &str = "CHOOSE";
+  pub(crate) const CLEAR_CACHE: &str = "CLEAR-CACHE";
   pub(crate) const COMMAND: &str = "COMMAND";
   pub(crate) const COMPLETIONS: &str = "COMPLETIONS";
   pub(crate) const DUMP: &str = "DUMP";
   pub(crate) const EDIT: &str = "EDIT";
   pub(crate) const EVALUATE: &str = "EVALUATE";
   pub(crate) const FORMAT: &str = "FORMAT";
+  pub(crate) const GROUPS: &str = "GROUPS";
   pub(crate) const INIT: &str = "INIT";
   pub(crate) const LIST: &str = "LIST";
+  pub(crate) const LSP: &str = "LSP";
   pub(crate) const SHOW: &str = "SHOW";
   pub(crate) const SUMMARY: &str = "SUMMARY";
   pub(crate) const VARIABLES: &str = "VARIABLES";
@@ -54,14 +68,17 @@ mod cmd {
   pub(crate) const ALL: &[&str] = &[
     CHANGELOG,
     CHOOSE,
+    CLEAR_CACHE,
     COMMAND,
     COMPLETIONS,
     DUMP,
     EDIT,
     EVALUATE,
     FORMAT,
+    GROUPS,
     INIT,
     LIST,
+    LSP,
     SHOW,
     SUMMARY,
     VARIABLES,
@@ -69,12 +86,14 @@ mod cmd {
 
   pub(crate) const ARGLESS: &[&str] = &[
     CHANGELOG,
+    CLEAR_CACHE,
     COMPLETIONS,
     DUMP,
     EDIT,
-    FORMAT,
+    GROUPS,
     INIT,
     LIST,
+    LSP,
     SHOW,
     SUMMARY,
     VARIABLES,
@@ -92,17 +111,26 @@ mod arg {
   pub(crate) const DOTENV_PATH: &str = "DOTENV-PATH";
   pub(crate) const DRY_RUN: &str = "DRY-RUN";
   pub(crate) const DUMP_FORMAT: &str = "DUMP-FORMAT";
+  pub(crate) const EVENTS_FILE: &str = "EVENTS-FILE";
+  pub(crate) const FORCE: &str = "FORCE";
+  pub(crate) const GLOBAL_JUSTFILE: &str = "GLOBAL-JUSTFILE";
   pub(crate) const HIGHLIGHT: &str = "HIGHLIGHT";
+  pub(crate) const JOBS: &str = "JOBS";
   pub(crate) const JUSTFILE: &str = "JUSTFILE";
+  pub(crate) const JUSTFILE_NAMES: &str = "JUSTFILE-NAMES";
   pub(crate) const LIST_HEADING: &str = "LIST-HEADING";
   pub(crate) const LIST_PREFIX: &str = "LIST-PREFIX";
   pub(crate) const NO_DOTENV: &str = "NO-DOTENV";
   pub(crate) const NO_HIGHLIGHT: &str = "NO-HIGHLIGHT";
+  pub(crate) const OUTPUT_FORMAT: &str = "OUTPUT-FORMAT";
   pub(crate) const QUIET: &str = "QUIET";
+  pub(crate) const ROOT_MARKERS: &str = "ROOT-MARKERS";
   pub(crate) const SET: &str = "SET";
   pub(crate) const SHELL: &str = "SHELL";
   pub(crate) const SHELL_ARG: &str = "SHELL-ARG";
   pub(crate) const SHELL_COMMAND: &str = "SHELL-COMMAND";
+  pub(crate) const TIMINGS: &str = "TIMINGS";
+  pub(crate) const TIMINGS_TRACE: &str = "TIMINGS-TRACE";
   pub(crate) const UNSORTED: &str = "UNSORTED";
   pub(crate) const UNSTABLE: &str = "UNSTABLE";
   pub(crate) const VERBOSE: &str = "VERBOSE";
@@ -134,6 +162,10 @@ mod arg {
   pub(crate) const DUMP_FORMAT_JSON: &str = "json";
   pub(crate) const DUMP_FORMAT_JUST: &str = "just";
   pub(crate) const DUMP_FORMAT_VALUES: &[&str] = &[DUMP_FORMAT_JUST, DUMP_FORMAT_JSON];
+
+  pub(crate) const OUTPUT_FORMAT_JSON_EVENTS: &str = "json-events";
+  pub(crate) const OUTPUT_FORMAT_TEXT: &str = "text";
+  pub(crate) const OUTPUT_FORMAT_VALUES: &[&str] = &[OUTPUT_FORMAT_TEXT, OUTPUT_FORMAT_JSON_EVENTS];
 }
 
 impl Config {
@@ -147,7 +179,7 @@ impl Config {
         Arg::with_name(arg::CHECK)
           .long("check")
           .requires(cmd::FORMAT)
-          .help("Run `--fmt` in 'check' mode. Exits with 0 if justfile is formatted correctly. Exits with 1 and prints a diff if formatting is required."),
+          .help("Run `--fmt` in 'check' mode. Exits with 0 if justfiles are formatted correctly. Exits with 1 and prints a diff if formatting is required."),
       )
       .arg(
         Arg::with_name(arg::CHOOSER)
@@ -187,12 +219,25 @@ impl Config {
           .value_name("FORMAT")
           .help("Dump justfile as <FORMAT>"),
       )
+      .arg(
+        Arg::with_name(arg::FORCE)
+          .long("force")
+          .help("Run recipes with `[sources]` or `[outputs]` even if they are up to date"),
+      )
       .arg(
         Arg::with_name(arg::HIGHLIGHT)
           .long("highlight")
           .help("Highlight echoed recipe lines in bold")
           .overrides_with(arg::NO_HIGHLIGHT),
       )
+      .arg(
+        Arg::with_name(arg::JOBS)
+          .short("j")
+          .long("jobs")
+          .takes_value(true)
+          .value_name("N")
+          .help("Run up to <N> recipes at once, running dependencies in parallel"),
+      )
       .arg(
         Arg::with_name(arg::LIST_HEADING)
           .long("list-heading")
@@ -218,6 +263,30 @@ impl Config {
           .help("Don't highlight echoed recipe lines in bold")
           .overrides_with(arg::HIGHLIGHT),
       )
+      .arg(
+        Arg::with_name(arg::OUTPUT_FORMAT)
+          .long("output-format")
+          .takes_value(true)
+          .possible_values(arg::OUTPUT_FORMAT_VALUES)
+          .default_value(arg::OUTPUT_FORMAT_TEXT)
+          .value_name("FORMAT")
+          .help("Report recipe execution as <FORMAT>. `json-events` writes newline-delimited JSON events to `--events-file`"),
+      )
+      .arg(
+        Arg::with_name(arg::EVENTS_FILE)
+          .long("events-file")
+          .takes_value(true)
+          .value_name("PATH")
+          .help("Write `--output-format json-events` events to <PATH>"),
+      )
+      .arg(
+        Arg::with_name(arg::GLOBAL_JUSTFILE)
+          .short("g")
+          .long("global-justfile")
+          .conflicts_with(arg::JUSTFILE)
+          .conflicts_with(arg::WORKING_DIRECTORY)
+          .help("Use global justfile"),
+      )
       .arg(
         Arg::with_name(arg::JUSTFILE)
           .short("f")
@@ -225,6 +294,25 @@ impl Config {
           .takes_value(true)
           .help("Use <JUSTFILE> as justfile"),
       )
+      .arg(
+        Arg::with_name(arg::JUSTFILE_NAMES)
+          .long("justfile-names")
+          .takes_value(true)
+          .value_name("NAMES")
+          .env(JUSTFILE_NAMES_ENVIRONMENT_KEY)
+          .help("Also search for justfiles named one of <NAMES>, a comma-separated list"),
+      )
+      .arg(
+        Arg::with_name(arg::ROOT_MARKERS)
+          .long("root-markers")
+          .takes_value(true)
+          .value_name("MARKERS")
+          .env(ROOT_MARKERS_ENVIRONMENT_KEY)
+          .help(
+            "Stop searching for a justfile at directories containing one of <MARKERS>, a \
+             comma-separated list",
+          ),
+      )
       .arg(
         Arg::with_name(arg::QUIET)
           .short("q")
@@ -269,6 +357,19 @@ impl Config {
           .overrides_with(arg::SHELL_ARG)
           .help("Clear shell arguments"),
       )
+      .arg(
+        Arg::with_name(arg::TIMINGS)
+          .long("timings")
+          .help("Print how long each recipe and recipe line took to run"),
+      )
+      .arg(
+        Arg::with_name(arg::TIMINGS_TRACE)
+          .long("timings-trace")
+          .takes_value(true)
+          .value_name("PATH")
+          .requires(arg::TIMINGS)
+          .help("Write timings to <PATH> in Chrome trace event format"),
+      )
       .arg(
         Arg::with_name(arg::UNSORTED)
           .long("unsorted")
@@ -301,6 +402,11 @@ impl Config {
           .help("Print changelog"),
       )
       .arg(Arg::with_name(cmd::CHOOSE).long("choose").help(CHOOSE_HELP))
+      .arg(
+        Arg::with_name(cmd::CLEAR_CACHE)
+          .long("clear-cache")
+          .help("Clear cached backtick output"),
+      )
       .arg(
         Arg::with_name(cmd::COMMAND)
           .long("command")
@@ -339,19 +445,29 @@ impl Config {
       .arg(
         Arg::with_name(cmd::FORMAT)
           .long("fmt")
-          .help("Format and overwrite justfile"),
+          .help("Format and overwrite justfile, or the justfiles given as arguments"),
       )
       .arg(
         Arg::with_name(cmd::INIT)
           .long("init")
           .help("Initialize new justfile in project root"),
       )
+      .arg(
+        Arg::with_name(cmd::GROUPS)
+          .long("groups")
+          .help("List recipe groups"),
+      )
       .arg(
         Arg::with_name(cmd::LIST)
           .short("l")
           .long("list")
           .help("List available recipes and their arguments"),
       )
+      .arg(
+        Arg::with_name(cmd::LSP)
+          .long("lsp")
+          .help("Run language server, communicating over stdin and stdout"),
+      )
       .arg(
         Arg::with_name(cmd::SHOW)
           .short("s")
@@ -460,6 +576,49 @@ impl Config {
     }
   }
 
+  fn jobs_from_matches(matches: &ArgMatches) -> ConfigResult<Option<usize>> {
+    matches
+      .value_of(arg::JOBS)
+      .map(|value| match value.parse::<usize>() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => Err(ConfigError::Jobs {
+          value: value.to_owned(),
+        }),
+      })
+      .transpose()
+  }
+
+  /// Comma-separated names in the value of `arg`
+  fn names_from_matches(matches: &ArgMatches, arg: &str) -> Vec<String> {
+    matches
+      .value_of(arg)
+      .unwrap_or_default()
+      .split(',')
+      .map(str::trim)
+      .filter(|name| !name.is_empty())
+      .map(str::to_owned)
+      .collect()
+  }
+
+  fn output_format_from_matches(matches: &ArgMatches) -> ConfigResult<OutputFormat> {
+    let value = matches
+      .value_of(arg::OUTPUT_FORMAT)
+      .ok_or_else(|| ConfigError::Internal {
+        message: "`--output-format` had no value".to_string(),
+      })?;
+
+    match value {
+      arg::OUTPUT_FORMAT_JSON_EVENTS if !matches.is_present(arg::EVENTS_FILE) => {
+        Err(ConfigError::EventsFileRequired)
+      }
+      arg::OUTPUT_FORMAT_JSON_EVENTS => Ok(OutputFormat::JsonEvents),
+      arg::OUTPUT_FORMAT_TEXT => Ok(OutputFormat::Text),
+      _ => Err(ConfigError::Internal {
+        message: format!("Invalid argument `{value}` to --output-format."),
+      }),
+    }
+  }
+
   pub(crate) fn from_matches(matches: &ArgMatches) -> ConfigResult<Self> {
     let invocation_directory = env::current_dir().context(config_error::CurrentDirContext)?;
 
@@ -484,7 +643,13 @@ impl Config {
       }
     }
 
-    let positional = Positional::from_values(matches.values_of(arg::ARGUMENTS));
+    // Arguments to `--fmt` are justfile paths, not overrides, search
+    // directories, or recipes
+    let positional = Positional::from_values(
+      matches
+        .values_of(arg::ARGUMENTS)
+        .filter(|_| !matches.is_present(cmd::FORMAT)),
+    );
 
     for (name, value) in positional.overrides {
       overrides.insert(name.clone(), value.clone());
@@ -495,10 +660,15 @@ impl Config {
       let working_directory = matches.value_of(arg::WORKING_DIRECTORY).map(PathBuf::from);
 
       if let Some(search_directory) = positional.search_directory.map(PathBuf::from) {
-        if justfile.is_some() || working_directory.is_some() {
+        if justfile.is_some()
+          || working_directory.is_some()
+          || matches.is_present(arg::GLOBAL_JUSTFILE)
+        {
           return Err(ConfigError::SearchDirConflict);
         }
         SearchConfig::FromSearchDirectory { search_directory }
+      } else if matches.is_present(arg::GLOBAL_JUSTFILE) {
+        SearchConfig::GlobalJustfile
       } else {
         match (justfile, working_directory) {
           (None, None) => SearchConfig::FromInvocationDirectory,
@@ -552,6 +722,8 @@ impl Config {
         chooser: matches.value_of(arg::CHOOSER).map(str::to_owned),
         overrides,
       }
+    } else if matches.is_present(cmd::CLEAR_CACHE) {
+      Subcommand::ClearCache
     } else if let Some(values) = matches.values_of_os(cmd::COMMAND) {
       let mut arguments = values.map(OsStr::to_owned).collect::<Vec<OsString>>();
       Subcommand::Command {
@@ -570,11 +742,22 @@ impl Config {
     } else if matches.is_present(cmd::DUMP) {
       Subcommand::Dump
     } else if matches.is_present(cmd::FORMAT) {
-      Subcommand::Format
+      Subcommand::Format {
+        justfiles: matches
+          .values_of_os(arg::ARGUMENTS)
+          .into_iter()
+          .flatten()
+          .map(PathBuf::from)
+          .collect(),
+      }
+    } else if matches.is_present(cmd::GROUPS) {
+      Subcommand::Groups
     } else if matches.is_present(cmd::INIT) {
       Subcommand::Init
     } else if matches.is_present(cmd::LIST) {
       Subcommand::List
+    } else if matches.is_present(cmd::LSP) {
+      Subcommand::Lsp
     } else if let Some(name) = matches.value_of(cmd::SHOW) {
       Subcommand::Show {
         name: name.to_owned(),
@@ -631,8 +814,12 @@ impl Config {
       dotenv_path: matches.value_of(arg::DOTENV_PATH).map(PathBuf::from),
       dry_run: matches.is_present(arg::DRY_RUN),
       dump_format: Self::dump_format_from_matches(matches)?,
+      events_file: matches.value_of(arg::EVENTS_FILE).map(PathBuf::from),
+      force: matches.is_present(arg::FORCE),
       highlight: !matches.is_present(arg::NO_HIGHLIGHT),
       invocation_directory,
+      jobs: Self::jobs_from_matches(matches)?,
+      justfile_names: Self::names_from_matches(matches, arg::JUSTFILE_NAMES),
       list_heading: matches
         .value_of(arg::LIST_HEADING)
         .unwrap_or("Available recipes:\n")
@@ -642,11 +829,15 @@ impl Config {
         .unwrap_or("    ")
         .to_owned(),
       load_dotenv: !matches.is_present(arg::NO_DOTENV),
+      output_format: Self::output_format_from_matches(matches)?,
+      root_markers: Self::names_from_matches(matches, arg::ROOT_MARKERS),
       search_config,
       shell: matches.value_of(arg::SHELL).map(str::to_owned),
       shell_args,
       shell_command: matches.is_present(arg::SHELL_COMMAND),
       subcommand,
+      timings: matches.is_present(arg::TIMINGS),
+      timings_trace: matches.value_of(arg::TIMINGS_TRACE).map(PathBuf::from),
       unsorted: matches.is_present(arg::UNSORTED),
       unstable,
       verbosity,
@@ -654,16 +845,6 @@ impl Config {
     })
   }
 
-  pub(crate) fn require_unstable(&self, message: &str) -> Result<(), Error<'static>> {
-    if self.unstable {
-      Ok(())
-    } else {
-      Err(Error::Unstable {
-        message: message.to_owned(),
-      })
-    }
-  }
-
   pub(crate) fn run(self, loader: &Loader) -> Result<(), Error> {
     if let Err(error) = InterruptHandler::install(self.verbosity) {
       warn!("Failed to set CTRL-C handler: {error}");
@@ -686,7 +867,10 @@ mod tests {
       $(color: $color:expr,)?
       $(dry_run: $dry_run:expr,)?
       $(dump_format: $dump_format:expr,)?
+      $(events_file: $events_file:expr,)?
       $(highlight: $highlight:expr,)?
+      $(jobs: $jobs:expr,)?
+      $(output_format: $output_format:expr,)?
       $(search_config: $search_config:expr,)?
       $(shell: $shell:expr,)?
       $(shell_args: $shell_args:expr,)?
@@ -705,7 +889,10 @@ mod tests {
           $(color: $color,)?
           $(dry_run: $dry_run,)?
           $(dump_format: $dump_format,)?
+          $(events_file: $events_file,)?
           $(highlight: $highlight,)?
+          $(jobs: $jobs,)?
+          $(output_format: $output_format,)?
           $(search_config: $search_config,)?
           $(shell: $shell,)?
           $(shell_args: $shell_args,)?
@@ -881,6 +1068,42 @@ mod tests {
     highlight: false,
   }
 
+  test! {
+    name: jobs_default,
+    args: [],
+    jobs: None,
+  }
+
+  test! {
+    name: jobs_long,
+    args: ["--jobs", "4"],
+    jobs: Some(4),
+  }
+
+  test! {
+    name: jobs_short,
+    args: ["-j", "2"],
+    jobs: Some(2),
+  }
+
+  error! {
+    name: jobs_zero,
+    args: ["--jobs", "0"],
+    error: ConfigError::Jobs { value },
+    check: {
+      assert_eq!(value, "0");
+    },
+  }
+
+  error! {
+    name: jobs_invalid,
+    args: ["--jobs", "many"],
+    error: ConfigError::Jobs { value },
+    check: {
+      assert_eq!(value, "many");
+    },
+  }
+
   test! {
     name: unsorted_default,
     args: [],
@@ -1110,6 +1333,19 @@ mod tests {
     dump_format: DumpFormat::Json,
   }
 
+  test! {
+    name: output_format,
+    args: ["--output-format", "json-events", "--events-file", "events.json"],
+    events_file: Some(PathBuf::from("events.json")),
+    output_format: OutputFormat::JsonEvents,
+  }
+
+  error! {
+    name: output_format_json_events_requires_events_file,
+    args: ["--output-format", "json-events"],
+    error: ConfigError::EventsFileRequired,
+  }
+
   test! {
     name: subcommand_edit,
     args: ["--edit"],
@@ -1306,6 +1542,18 @@ mod tests {
     },
   }
 
+  test! {
+    name: search_config_global_justfile_long,
+    args: ["--global-justfile"],
+    search_config: SearchConfig::GlobalJustfile,
+  }
+
+  test! {
+    name: search_config_global_justfile_short,
+    args: ["-g"],
+    search_config: SearchConfig::GlobalJustfile,
+  }
+
   test! {
     name: search_directory_parent,
     args: ["../"],
@@ -1348,6 +1596,12 @@ mod tests {
     subcommand: Subcommand::Run { arguments: vec!["build".to_owned()], overrides: BTreeMap::new() },
   }
 
+  error! {
+    name: search_directory_conflict_global_justfile,
+    args: ["--global-justfile", "foo/build"],
+    error: ConfigError::SearchDirConflict,
+  }
+
   error! {
     name: search_directory_conflict_justfile,
     args: ["--justfile", "bar", "foo/build"],
@@ -1380,6 +1634,16 @@ mod tests {
     },
   }
 
+  error! {
+    name: clear_cache_arguments,
+    args: ["--clear-cache", "bar"],
+    error: ConfigError::SubcommandArguments { subcommand, arguments },
+    check: {
+      assert_eq!(subcommand, cmd::CLEAR_CACHE);
+      assert_eq!(arguments, &["bar"]);
+    },
+  }
+
   error! {
     name: list_arguments,
     args: ["--list", "bar"],
@@ -1410,13 +1674,11 @@ mod tests {
     },
   }
 
-  error! {
+  test! {
     name: fmt_arguments,
-    args: ["--fmt", "bar"],
-    error: ConfigError::SubcommandArguments { subcommand, arguments },
-    check: {
-      assert_eq!(subcommand, cmd::FORMAT);
-      assert_eq!(arguments, &["bar"]);
+    args: ["--fmt", "bar", "baz/justfile", "x=y"],
+    subcommand: Subcommand::Format {
+      justfiles: vec!["bar".into(), "baz/justfile".into(), "x=y".into()],
     },
   }
 
@@ -5,14 +5,19 @@ use super::*;
 pub(crate) enum ConfigError {
   #[snafu(display("Failed to get current directory: {}", source))]
   CurrentDir { source: io::Error },
+  #[snafu(display("`--output-format json-events` requires `--events-file`"))]
+  EventsFileRequired,
   #[snafu(display(
     "Internal config error, this may indicate a bug in just: {} \
      consider filing an issue: https://github.com/casey/just/issues/new",
     message
   ))]
   Internal { message: String },
+  #[snafu(display("Invalid value `{}` for `--jobs`, expected a positive integer", value))]
+  Jobs { value: String },
   #[snafu(display(
-    "Path-prefixed recipes may not be used with `--working-directory` or `--justfile`."
+    "Path-prefixed recipes may not be used with `--working-directory`, `--justfile`, or \
+     `--global-justfile`."
   ))]
   SearchDirConflict,
   #[snafu(display(
@@ -2,6 +2,10 @@ use super::*;
 
 #[derive(Debug)]
 pub(crate) enum Error<'src> {
+  AmbiguousModuleFile {
+    module: Name<'src>,
+    found: Vec<PathBuf>,
+  },
   ArgumentCountMismatch {
     recipe: &'src str,
     parameters: Vec<Parameter<'src>>,
@@ -9,10 +13,33 @@ pub(crate) enum Error<'src> {
     min: usize,
     max: usize,
   },
+  ArgumentNotChoice {
+    recipe: &'src str,
+    parameter: &'src str,
+    argument: String,
+    choices: Vec<String>,
+  },
+  ArgumentPatternMismatch {
+    recipe: &'src str,
+    parameter: &'src str,
+    argument: String,
+    pattern: String,
+    // boxed to keep `Error` small
+    token: Box<Token<'src>>,
+  },
   Backtick {
     token: Token<'src>,
     output_error: OutputError,
   },
+  BuiltinShell {
+    recipe: &'src str,
+    line_number: Option<usize>,
+    message: String,
+  },
+  Cache {
+    path: PathBuf,
+    io_error: io::Error,
+  },
   ChooserInvoke {
     shell_binary: String,
     shell_arguments: String,
@@ -31,6 +58,10 @@ pub(crate) enum Error<'src> {
     chooser: OsString,
     io_error: io::Error,
   },
+  CircularImport {
+    current: PathBuf,
+    import: PathBuf,
+  },
   CircularInclude {
     current: PathBuf,
     include: PathBuf,
@@ -57,6 +88,9 @@ pub(crate) enum Error<'src> {
   Config {
     config_error: ConfigError,
   },
+  ConfirmationRequiresTerminal {
+    recipe: &'src str,
+  },
   Cygpath {
     recipe: &'src str,
     output_error: OutputError,
@@ -65,7 +99,11 @@ pub(crate) enum Error<'src> {
     recipe: &'src str,
     min_arguments: usize,
   },
+  DependencyFailed {
+    recipe: String,
+  },
   Dotenv {
+    path: PathBuf,
     dotenv_error: dotenvy::Error,
   },
   DumpJson {
@@ -83,6 +121,14 @@ pub(crate) enum Error<'src> {
     variable: String,
     suggestion: Option<Suggestion<'src>>,
   },
+  EventsFile {
+    path: PathBuf,
+    io_error: io::Error,
+  },
+  FlagWithValue {
+    recipe: &'src str,
+    option: String,
+  },
   FormatCheckFoundDiff,
   FunctionCall {
     function: Name<'src>,
@@ -108,21 +154,43 @@ pub(crate) enum Error<'src> {
     recipe: &'src str,
     io_error: io::Error,
   },
+  LanguageServerIo {
+    io_error: io::Error,
+  },
   Load {
     path: PathBuf,
     io_error: io::Error,
   },
+  MissingImportFile {
+    path: Token<'src>,
+  },
+  MissingModuleFile {
+    module: Name<'src>,
+  },
+  MissingOption {
+    recipe: &'src str,
+    option: String,
+  },
   NoChoosableRecipes,
   NoRecipes,
   NotConfirmed {
     recipe: &'src str,
   },
+  OptionMissingValue {
+    recipe: &'src str,
+    option: String,
+  },
   RegexCompile {
     source: regex::Error,
   },
   Search {
     search_error: SearchError,
   },
+  Script {
+    recipe: &'src str,
+    command: String,
+    io_error: io::Error,
+  },
   Shebang {
     recipe: &'src str,
     command: String,
@@ -134,6 +202,23 @@ pub(crate) enum Error<'src> {
     line_number: Option<usize>,
     signal: i32,
   },
+  SplatShell {
+    shell: Option<String>,
+  },
+  Sources {
+    recipe: &'src str,
+    path: PathBuf,
+    io_error: io::Error,
+  },
+  SourcesGlob {
+    recipe: &'src str,
+    glob: String,
+    pattern_error: glob::PatternError,
+  },
+  TimingsTrace {
+    path: PathBuf,
+    io_error: io::Error,
+  },
   TmpdirIo {
     recipe: &'src str,
     io_error: io::Error,
@@ -142,6 +227,10 @@ pub(crate) enum Error<'src> {
     recipe: &'src str,
     line_number: Option<usize>,
   },
+  UnknownOption {
+    recipe: &'src str,
+    option: String,
+  },
   UnknownOverrides {
     overrides: Vec<String>,
   },
@@ -171,11 +260,16 @@ impl<'src> Error<'src> {
     }
   }
 
-  fn context(&self) -> Option<Token<'src>> {
+  pub(crate) fn context(&self) -> Option<Token<'src>> {
     match self {
+      Self::AmbiguousModuleFile { module, .. } | Self::MissingModuleFile { module, .. } => {
+        Some(module.token())
+      }
+      Self::ArgumentPatternMismatch { token, .. } => Some(**token),
       Self::Backtick { token, .. } => Some(*token),
       Self::Compile { compile_error } => Some(compile_error.context()),
       Self::FunctionCall { function, .. } => Some(function.token()),
+      Self::MissingImportFile { path } => Some(*path),
       _ => None,
     }
   }
@@ -209,12 +303,6 @@ impl<'src> From<ConfigError> for Error<'src> {
   }
 }
 
-impl<'src> From<dotenvy::Error> for Error<'src> {
-  fn from(dotenv_error: dotenvy::Error) -> Error<'src> {
-    Self::Dotenv { dotenv_error }
-  }
-}
-
 impl<'src> From<SearchError> for Error<'src> {
   fn from(search_error: SearchError) -> Self {
     Self::Search { search_error }
@@ -230,6 +318,10 @@ impl<'src> ColorDisplay for Error<'src> {
     write!(f, "{error}: {message}")?;
 
     match self {
+      AmbiguousModuleFile { module, found } => {
+        let found = List::and_ticked(found.iter().map(|path| path.display()));
+        write!(f, "Found multiple source files for module `{module}`: {found}")?;
+      }
       ArgumentCountMismatch { recipe, found, min, max, .. } => {
         let count = Count("argument", *found);
         if min == max {
@@ -242,8 +334,15 @@ impl<'src> ColorDisplay for Error<'src> {
           write!(f, "Recipe `{recipe}` got {found} {count} but takes at most {max}")?;
         }
       }
+      ArgumentNotChoice { recipe, parameter, argument, choices } => {
+        write!(f, "Argument `{argument}` passed to recipe `{recipe}` parameter `{parameter}` is not {}", List::or_ticked(choices))?;
+      }
+      ArgumentPatternMismatch { recipe, parameter, argument, pattern, .. } => {
+        write!(f, "Argument `{argument}` passed to recipe `{recipe}` parameter `{parameter}` does not match pattern `{pattern}`")?;
+      }
       Backtick { output_error, .. } => match output_error {
         OutputError::Code(code) => write!(f, "Backtick failed with exit code {code}")?,
+        OutputError::Shell(message) => write!(f, "Backtick could not be run by the builtin shell: {message}")?,
         OutputError::Signal(signal) => write!(f, "Backtick was terminated by signal {signal}")?,
         OutputError::Unknown => write!(f, "Backtick failed for an unknown reason")?,
         OutputError::Io(io_error) => match io_error.kind() {
@@ -253,6 +352,16 @@ impl<'src> ColorDisplay for Error<'src> {
           }?,
         OutputError::Utf8(utf8_error) => write!(f, "Backtick succeeded but stdout was not utf8: {utf8_error}")?,
       }
+      BuiltinShell { recipe, line_number, message } => {
+        if let Some(n) = line_number {
+          write!(f, "Recipe `{recipe}` line {n} could not be run by the builtin shell: {message}")?;
+        } else {
+          write!(f, "Recipe `{recipe}` could not be run by the builtin shell: {message}")?;
+        }
+      }
+      Cache { path, io_error } => {
+        write!(f, "I/O error in cache directory `{}`: {io_error}", path.display())?;
+      }
       ChooserInvoke { shell_binary, shell_arguments, chooser, io_error} => {
         let chooser = chooser.to_string_lossy();
         write!(f, "Chooser `{shell_binary} {shell_arguments} {chooser}` invocation failed: {io_error}")?;
@@ -269,6 +378,11 @@ impl<'src> ColorDisplay for Error<'src> {
         let chooser = chooser.to_string_lossy();
         write!(f, "Failed to write to chooser `{chooser}`: {io_error}")?;
       }
+      CircularImport { current, import } => {
+        let import = import.display();
+        let current = current.display();
+        write!(f, "Import `{import}` in `{current}` is circular")?;
+      }
       CircularInclude { current, include } => {
         let include = include.display();
         let current = current.display();
@@ -291,8 +405,12 @@ impl<'src> ColorDisplay for Error<'src> {
       }
       Compile { compile_error } => Display::fmt(compile_error, f)?,
       Config { config_error } => Display::fmt(config_error, f)?,
+      ConfirmationRequiresTerminal { recipe } => {
+        write!(f, "Recipe `{recipe}` requires confirmation, but standard input is not a terminal. Use `--yes` to confirm automatically.")?;
+      }
       Cygpath { recipe, output_error} => match output_error {
         OutputError::Code(code) => write!(f, "Cygpath failed with exit code {code} while translating recipe `{recipe}` shebang interpreter path")?,
+        OutputError::Shell(message) => write!(f, "Cygpath could not be run while translating recipe `{recipe}` shebang interpreter path: {message}")?,
         OutputError::Signal(signal) => write!(f, "Cygpath terminated by signal {signal} while translating recipe `{recipe}` shebang interpreter path")?,
         OutputError::Unknown => write!(f, "Cygpath experienced an unknown failure while translating recipe `{recipe}` shebang interpreter path")?,
         OutputError::Io(io_error) => {
@@ -308,8 +426,11 @@ impl<'src> ColorDisplay for Error<'src> {
         let count = Count("argument", *min_arguments);
         write!(f, "Recipe `{recipe}` cannot be used as default recipe since it requires at least {min_arguments} {count}.")?;
       }
-      Dotenv { dotenv_error } => {
-        write!(f, "Failed to load environment file: {dotenv_error}")?;
+      DependencyFailed { recipe } => {
+        write!(f, "Recipe `{recipe}` already failed while running as a dependency")?;
+      }
+      Dotenv { path, dotenv_error } => {
+        write!(f, "Failed to load environment file `{}`: {dotenv_error}", path.display())?;
       }
       DumpJson { serde_json_error } => {
         write!(f, "Failed to dump JSON to stdout: {serde_json_error}")?;
@@ -328,6 +449,13 @@ impl<'src> ColorDisplay for Error<'src> {
           write!(f, "\n{suggestion}")?;
         }
       }
+      EventsFile { path, io_error } => {
+        let path = path.display();
+        write!(f, "Failed to create events file `{path}`: {io_error}")?;
+      }
+      FlagWithValue { recipe, option } => {
+        write!(f, "Recipe `{recipe}` flag `{option}` does not take a value")?;
+      }
       FormatCheckFoundDiff => {
         write!(f, "Formatted justfile differs from original.")?;
       }
@@ -360,17 +488,31 @@ impl<'src> ColorDisplay for Error<'src> {
           _ => write!(f, "Recipe `{recipe}` could not be run because of an IO error while launching the shell: {io_error}"),
         }?;
       }
+      LanguageServerIo { io_error } => {
+        write!(f, "I/O error in language server: {io_error}")?;
+      }
       Load { io_error, path } => {
         let path = path.display();
         write!(f, "Failed to read justfile at `{path}`: {io_error}")?;
       }
+      MissingImportFile { .. } => write!(f, "Could not find source file for import.")?,
+      MissingModuleFile { module } => write!(f, "Could not find source file for module `{module}`.")?,
+      MissingOption { recipe, option } => {
+        write!(f, "Recipe `{recipe}` requires option `{option}`")?;
+      }
       NoChoosableRecipes => write!(f, "Justfile contains no choosable recipes.")?,
       NoRecipes => write!(f, "Justfile contains no recipes.")?,
       NotConfirmed { recipe } => {
         write!(f, "Recipe `{recipe}` was not confirmed")?;
       }
+      OptionMissingValue { recipe, option } => {
+        write!(f, "Recipe `{recipe}` option `{option}` requires a value")?;
+      }
       RegexCompile { source } => write!(f, "{source}")?,
       Search { search_error } => Display::fmt(search_error, f)?,
+      Script { recipe, command, io_error } => {
+        write!(f, "Recipe `{recipe}` with script interpreter `{command}` execution error: {io_error}")?;
+      }
       Shebang { recipe, command, argument, io_error} => {
         if let Some(argument) = argument {
           write!(f, "Recipe `{recipe}` with shebang `#!{command} {argument}` execution error: {io_error}")?;
@@ -385,6 +527,23 @@ impl<'src> ColorDisplay for Error<'src> {
           write!(f, "Recipe `{recipe}` was terminated by signal {signal}")?;
         }
       }
+      SplatShell { shell } => {
+        if let Some(shell) = shell {
+          write!(f, "Splat interpolation `{{{{ *… }}}}` is not supported with `{shell}`, only with POSIX shells and PowerShell")?;
+        } else {
+          write!(f, "Splat interpolation `{{{{ *… }}}}` is not supported in shebang lines")?;
+        }
+      }
+      Sources { recipe, path, io_error } => {
+        write!(f, "I/O error checking whether recipe `{recipe}` is up to date at `{}`: {io_error}", path.display())?;
+      }
+      SourcesGlob { recipe, glob, pattern_error } => {
+        write!(f, "Recipe `{recipe}` glob `{glob}` could not be expanded: {pattern_error}")?;
+      }
+      TimingsTrace { path, io_error } => {
+        let path = path.display();
+        write!(f, "Failed to write timings trace to `{path}`: {io_error}")?;
+      }
       TmpdirIo { recipe, io_error } => {
         write!(f, "Recipe `{recipe}` could not be run because of an IO error while trying to create a temporary \
                    directory or write a file to that directory`:{io_error}")?;
@@ -396,6 +555,9 @@ impl<'src> ColorDisplay for Error<'src> {
           write!(f, "Recipe `{recipe}` failed for an unknown reason")?;
         }
       }
+      UnknownOption { recipe, option } => {
+        write!(f, "Recipe `{recipe}` does not have option `{option}`")?;
+      }
       UnknownOverrides { overrides } => {
         let count = Count("Variable", overrides.len());
         let overrides = List::and_ticked(overrides);
@@ -427,7 +589,7 @@ impl<'src> ColorDisplay for Error<'src> {
       writeln!(f)?;
       write!(f, "{}:\n    just {recipe}", color.message().paint("usage"))?;
       for param in parameters {
-        write!(f, " {}", param.color_display(color))?;
+        write!(f, " {}", param.usage().color_display(color))?;
       }
     }
 
@@ -1,30 +1,45 @@
-use super::*;
+use {
+  super::*,
+  sha2::{Digest, Sha256},
+};
 
 pub(crate) struct Evaluator<'src: 'run, 'run> {
+  /// Arguments of the user-defined function whose body is being evaluated
+  arguments: Option<BTreeMap<&'src str, Value>>,
   assignments: Option<&'run Table<'src, Assignment<'src>>>,
   config: &'run Config,
   dotenv: &'run BTreeMap<String, String>,
+  functions: &'run Table<'src, UserFunction<'src>>,
+  /// Scope containing the module's assignments, which function bodies are
+  /// evaluated in, if different from `scope`
+  module: Option<&'run Scope<'src, 'run>>,
   scope: Scope<'src, 'run>,
   settings: &'run Settings<'run>,
   search: &'run Search,
+  unexports: &'run BTreeSet<String>,
 }
 
 impl<'src, 'run> Evaluator<'src, 'run> {
+  /// Evaluate `assignments`, which may be a subset of those in `justfile`
   pub(crate) fn evaluate_assignments(
     assignments: &'run Table<'src, Assignment<'src>>,
     config: &'run Config,
     dotenv: &'run BTreeMap<String, String>,
+    justfile: &'run Justfile<'src>,
     overrides: Scope<'src, 'run>,
-    settings: &'run Settings<'run>,
     search: &'run Search,
   ) -> RunResult<'src, Scope<'src, 'run>> {
     let mut evaluator = Evaluator {
+      arguments: None,
       scope: overrides,
       assignments: Some(assignments),
       config,
       dotenv,
-      settings,
+      functions: &justfile.functions,
+      module: None,
+      settings: &justfile.settings,
       search,
+      unexports: &justfile.unexports,
     };
 
     for assignment in assignments.values() {
@@ -34,45 +49,137 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     Ok(evaluator.scope)
   }
 
-  fn evaluate_assignment(&mut self, assignment: &Assignment<'src>) -> RunResult<'src, &str> {
+  fn evaluate_assignment(&mut self, assignment: &Assignment<'src>) -> RunResult<'src, &Value> {
     let name = assignment.name.lexeme();
 
     if !self.scope.bound(name) {
-      let value = self.evaluate_expression(&assignment.value)?;
-      self.scope.bind(assignment.export, assignment.name, value);
+      // assignments may be evaluated on demand from a function body, but
+      // cannot refer to that function's parameters
+      let arguments = self.arguments.take();
+      let value = self.evaluate_value(&assignment.value);
+      self.arguments = arguments;
+      self.scope.bind(assignment.export, assignment.name, value?);
     }
 
     Ok(self.scope.value(name).unwrap())
   }
 
-  pub(crate) fn evaluate_expression(
-    &mut self,
-    expression: &Expression<'src>,
-  ) -> RunResult<'src, String> {
+  /// Evaluate `expression`, which, unlike with `evaluate_expression`, may
+  /// produce a list
+  pub(crate) fn evaluate_value(&mut self, expression: &Expression<'src>) -> RunResult<'src, Value> {
     match expression {
       Expression::Variable { name, .. } => {
         let variable = name.lexeme();
-        if let Some(value) = self.scope.value(variable) {
-          Ok(value.to_owned())
+
+        if let Some(value) = self
+          .arguments
+          .as_ref()
+          .and_then(|arguments| arguments.get(variable))
+        {
+          return Ok(value.clone());
+        }
+
+        let scope = match self.module {
+          Some(module) if self.arguments.is_some() => module,
+          _ => &self.scope,
+        };
+
+        if let Some(value) = scope.value(variable) {
+          Ok(value.clone())
         } else if let Some(assignment) = self
           .assignments
           .and_then(|assignments| assignments.get(variable))
         {
-          Ok(self.evaluate_assignment(assignment)?.to_owned())
+          Ok(self.evaluate_assignment(assignment)?.clone())
         } else {
           Err(Error::Internal {
             message: format!("attempted to evaluate undefined variable `{variable}`"),
           })
         }
       }
+      Expression::Call {
+        thunk: Thunk::UnaryValue {
+          name,
+          function,
+          arg,
+        },
+      } => {
+        let arg = self.evaluate_value(arg)?;
+        function(&self.function_context(), arg).map_err(|message| Error::FunctionCall {
+          function: *name,
+          message,
+        })
+      }
+      Expression::Call {
+        thunk:
+          Thunk::BinaryValue {
+            name,
+            function,
+            args: [a, b],
+          },
+      } => {
+        let a = self.evaluate_value(a)?;
+        let b = self.evaluate_value(b)?;
+        function(&self.function_context(), a, b).map_err(|message| Error::FunctionCall {
+          function: *name,
+          message,
+        })
+      }
+      Expression::Call {
+        thunk: Thunk::User { name, args },
+      } => {
+        let Some(function) = self.functions.get(name.lexeme()) else {
+          return Err(Error::Internal {
+            message: format!("attempted to call undefined function `{name}`"),
+          });
+        };
+
+        let mut arguments = BTreeMap::new();
+
+        for (parameter, arg) in function.parameters.iter().zip(args) {
+          arguments.insert(parameter.lexeme(), self.evaluate_value(arg)?);
+        }
+
+        let arguments = self.arguments.replace(arguments);
+        let value = self.evaluate_value(&function.body);
+        self.arguments = arguments;
+
+        value
+      }
+      Expression::Conditional {
+        condition,
+        then,
+        otherwise,
+      } => {
+        if self.evaluate_condition(condition)? {
+          self.evaluate_value(then)
+        } else {
+          self.evaluate_value(otherwise)
+        }
+      }
+      Expression::Group { contents } => self.evaluate_value(contents),
+      _ => self.evaluate_expression(expression).map(Value::String),
+    }
+  }
+
+  fn function_context(&self) -> FunctionContext<'run> {
+    FunctionContext {
+      dotenv: self.dotenv,
+      invocation_directory: &self.config.invocation_directory,
+      search: self.search,
+    }
+  }
+
+  pub(crate) fn evaluate_expression(
+    &mut self,
+    expression: &Expression<'src>,
+  ) -> RunResult<'src, String> {
+    match expression {
+      Expression::Variable { .. } => self.evaluate_value(expression).map(Value::into_string),
       Expression::Call { thunk } => {
         use Thunk::*;
 
-        let context = FunctionContext {
-          dotenv: self.dotenv,
-          invocation_directory: &self.config.invocation_directory,
-          search: self.search,
-        };
+        let context = self.function_context();
 
         match thunk {
           Nullary { name, function, .. } => {
@@ -157,6 +264,21 @@ impl<'src, 'run> Evaluator<'src, 'run> {
             function: *name,
             message,
           }),
+          Cached {
+            name,
+            contents,
+            token,
+            files,
+          } => {
+            let mut evaluated = Vec::new();
+            for file in files {
+              evaluated.push(self.evaluate_expression(file)?);
+            }
+            self.evaluate_cached(&context, *name, contents, token, &evaluated)
+          }
+          UnaryValue { .. } | BinaryValue { .. } | User { .. } => {
+            self.evaluate_value(expression).map(Value::into_string)
+          }
         }
       }
       Expression::StringLiteral { string_literal } => Ok(string_literal.cooked.clone()),
@@ -171,22 +293,11 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         Ok(self.evaluate_expression(lhs)? + &self.evaluate_expression(rhs)?)
       }
       Expression::Conditional {
-        lhs,
-        rhs,
+        condition,
         then,
         otherwise,
-        operator,
       } => {
-        let lhs_value = self.evaluate_expression(lhs)?;
-        let rhs_value = self.evaluate_expression(rhs)?;
-        let condition = match operator {
-          ConditionalOperator::Equality => lhs_value == rhs_value,
-          ConditionalOperator::Inequality => lhs_value != rhs_value,
-          ConditionalOperator::RegexMatch => Regex::new(&rhs_value)
-            .map_err(|source| Error::RegexCompile { source })?
-            .is_match(&lhs_value),
-        };
-        if condition {
+        if self.evaluate_condition(condition)? {
           self.evaluate_expression(then)
         } else {
           self.evaluate_expression(otherwise)
@@ -201,14 +312,118 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     }
   }
 
+  fn evaluate_condition(&mut self, condition: &Condition<'src>) -> RunResult<'src, bool> {
+    match condition {
+      Condition::And { lhs, rhs } => {
+        Ok(self.evaluate_condition(lhs)? && self.evaluate_condition(rhs)?)
+      }
+      Condition::Comparison { lhs, rhs, operator } => {
+        let lhs_value = self.evaluate_expression(lhs)?;
+        let rhs_value = self.evaluate_expression(rhs)?;
+        Ok(match operator {
+          ConditionalOperator::Equality => lhs_value == rhs_value,
+          ConditionalOperator::Inequality => lhs_value != rhs_value,
+          ConditionalOperator::RegexMatch => Regex::new(&rhs_value)
+            .map_err(|source| Error::RegexCompile { source })?
+            .is_match(&lhs_value),
+          ConditionalOperator::RegexMismatch => !Regex::new(&rhs_value)
+            .map_err(|source| Error::RegexCompile { source })?
+            .is_match(&lhs_value),
+        })
+      }
+      Condition::Group { contents } => self.evaluate_condition(contents),
+      Condition::In { lhs, list } => {
+        let lhs_value = self.evaluate_expression(lhs)?;
+        for expression in list {
+          if self.evaluate_expression(expression)? == lhs_value {
+            return Ok(true);
+          }
+        }
+        Ok(false)
+      }
+      Condition::Not { condition } => Ok(!self.evaluate_condition(condition)?),
+      Condition::Or { lhs, rhs } => {
+        Ok(self.evaluate_condition(lhs)? || self.evaluate_condition(rhs)?)
+      }
+    }
+  }
+
+  /// Evaluate `cached(`contents`, files…)`. The backtick is only run if
+  /// there is no output in the cache for its contents and the contents of
+  /// `files`.
+  fn evaluate_cached(
+    &mut self,
+    context: &FunctionContext,
+    name: Name<'src>,
+    contents: &str,
+    token: &Token<'src>,
+    files: &[String],
+  ) -> RunResult<'src, String> {
+    if self.config.dry_run {
+      return Ok(format!("`{contents}`"));
+    }
+
+    let mut hasher = Sha256::new();
+
+    hasher.update(contents.as_bytes());
+
+    for file in files {
+      let digest = function::sha256_file(context, file).map_err(|message| Error::FunctionCall {
+        function: name,
+        message,
+      })?;
+
+      hasher.update([0]);
+      hasher.update(file.as_bytes());
+      hasher.update([0]);
+      hasher.update(digest.as_bytes());
+    }
+
+    let path = self
+      .search
+      .working_directory
+      .join(search::BACKTICK_CACHE_DIRECTORY)
+      .join(format!("{:x}", hasher.finalize()));
+
+    match fs::read_to_string(&path) {
+      Ok(output) => return Ok(output),
+      Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => {}
+      Err(io_error) => return Err(Error::Cache { path, io_error }),
+    }
+
+    let output = self.run_backtick(contents, token)?;
+
+    write_atomic(&path, output.as_bytes()).map_err(|io_error| Error::Cache {
+      path: path.clone(),
+      io_error,
+    })?;
+
+    Ok(output)
+  }
+
   fn run_backtick(&self, raw: &str, token: &Token<'src>) -> RunResult<'src, String> {
+    if self.settings.builtin_shell(self.config) {
+      return InterruptHandler::guard(|| {
+        BuiltinShell::output(raw, self.config.verbosity.quiet(), None, |cmd| {
+          cmd.current_dir(&self.search.working_directory);
+          cmd.export(self.settings, self.dotenv, &self.scope, self.unexports);
+        })
+        .map_err(OutputError::Shell)
+        .and_then(|output| process_output(Ok(output)))
+        .map_err(|output_error| Error::Backtick {
+          token: *token,
+          output_error,
+        })
+      });
+    }
+
     let mut cmd = self.settings.shell_command(self.config);
 
     cmd.arg(raw);
 
     cmd.current_dir(&self.search.working_directory);
 
-    cmd.export(self.settings, self.dotenv, &self.scope);
+    cmd.export(self.settings, self.dotenv, &self.scope, self.unexports);
 
     cmd.stdin(Stdio::inherit());
 
@@ -226,10 +441,13 @@ impl<'src, 'run> Evaluator<'src, 'run> {
     })
   }
 
+  /// Evaluate `line`, which is run by `shell`. `shell` is `None` for the
+  /// shebang line of a script, which is not run by a shell.
   pub(crate) fn evaluate_line(
     &mut self,
     line: &Line<'src>,
     continued: bool,
+    shell: Option<&str>,
   ) -> RunResult<'src, String> {
     let mut evaluated = String::new();
     for (i, fragment) in line.fragments.iter().enumerate() {
@@ -243,45 +461,90 @@ impl<'src, 'run> Evaluator<'src, 'run> {
             evaluated += &lexeme;
           }
         }
-        Fragment::Interpolation { expression } => {
+        Fragment::Interpolation {
+          expression,
+          splat: false,
+        } => {
           evaluated += &self.evaluate_expression(expression)?;
         }
+        Fragment::Interpolation {
+          expression,
+          splat: true,
+        } => {
+          let kind = shell
+            .and_then(ShellKind::new)
+            .ok_or_else(|| Error::SplatShell {
+              shell: shell.map(str::to_owned),
+            })?;
+          evaluated += &self.evaluate_value(expression)?.quoted(kind);
+        }
       }
     }
     Ok(evaluated)
   }
 
   pub(crate) fn evaluate_parameters(
-    config: &'run Config,
+    context: &'run RecipeContext<'src, 'run>,
     dotenv: &'run BTreeMap<String, String>,
-    parameters: &[Parameter<'src>],
+    recipe: &Recipe<'src>,
     arguments: &[&str],
-    scope: &'run Scope<'src, 'run>,
-    settings: &'run Settings,
-    search: &'run Search,
+    options: &BTreeMap<&str, String>,
   ) -> RunResult<'src, (Scope<'src, 'run>, Vec<String>)> {
     let mut evaluator = Evaluator {
+      arguments: None,
       assignments: None,
-      scope: scope.child(),
-      search,
-      settings,
+      scope: context.scope.child(),
+      search: context.search,
+      settings: context.settings,
       dotenv,
-      config,
+      config: context.config,
+      functions: context.functions,
+      module: Some(&context.scope),
+      unexports: context.unexports,
     };
 
-    let mut scope = scope.child();
+    let mut scope = context.scope.child();
 
     let mut positional = Vec::new();
 
     let mut rest = arguments;
-    for parameter in parameters {
-      let value = if rest.is_empty() {
-        if let Some(ref default) = parameter.default {
+    for parameter in &recipe.parameters {
+      let value = if parameter.is_option() {
+        let value = if let Some(value) = options.get(parameter.name.lexeme()) {
+          parameter.check(recipe.name(), value)?;
+          value.clone()
+        } else if let Some(ref default) = parameter.default {
           let value = evaluator.evaluate_expression(default)?;
-          positional.push(value.clone());
+          parameter.check(recipe.name(), &value)?;
           value
-        } else if parameter.kind == ParameterKind::Star {
+        } else if parameter.value.is_some() {
           String::new()
+        } else {
+          return Err(Error::MissingOption {
+            recipe: recipe.name(),
+            option: parameter.option_names().join("|"),
+          });
+        };
+        positional.push(value.clone());
+        Value::String(value)
+      } else if rest.is_empty() {
+        if let Some(ref default) = parameter.default {
+          let value = evaluator.evaluate_value(default)?;
+          if parameter.kind.is_variadic() {
+            let value = value.into_list();
+            for value in &value {
+              parameter.check(recipe.name(), value)?;
+              positional.push(value.clone());
+            }
+            Value::List(value)
+          } else {
+            let value = value.into_string();
+            parameter.check(recipe.name(), &value)?;
+            positional.push(value.clone());
+            Value::String(value)
+          }
+        } else if parameter.kind == ParameterKind::Star {
+          Value::List(Vec::new())
         } else {
           return Err(Error::Internal {
             message: "missing parameter without default".to_owned(),
@@ -289,16 +552,18 @@ impl<'src, 'run> Evaluator<'src, 'run> {
         }
       } else if parameter.kind.is_variadic() {
         for value in rest {
+          parameter.check(recipe.name(), value)?;
           positional.push((*value).to_owned());
         }
-        let value = rest.to_vec().join(" ");
+        let value = rest.iter().copied().map(str::to_owned).collect();
         rest = &[];
-        value
+        Value::List(value)
       } else {
         let value = rest[0].to_owned();
+        parameter.check(recipe.name(), &value)?;
         positional.push(value.clone());
         rest = &rest[1..];
-        value
+        Value::String(value)
       };
       scope.bind(parameter.export, parameter.name, value);
     }
@@ -307,19 +572,22 @@ impl<'src, 'run> Evaluator<'src, 'run> {
   }
 
   pub(crate) fn recipe_evaluator(
-    config: &'run Config,
+    context: &'run RecipeContext<'src, 'run>,
     dotenv: &'run BTreeMap<String, String>,
     scope: &'run Scope<'src, 'run>,
-    settings: &'run Settings,
     search: &'run Search,
   ) -> Evaluator<'src, 'run> {
     Evaluator {
+      arguments: None,
       assignments: None,
       scope: Scope::child(scope),
       search,
-      settings,
+      settings: context.settings,
       dotenv,
-      config,
+      config: context.config,
+      functions: context.functions,
+      module: Some(&context.scope),
+      unexports: context.unexports,
     }
   }
 }
@@ -0,0 +1,73 @@
+use {
+  super::*,
+  std::time::{Duration, SystemTime},
+};
+
+/// An event written to `--events-file`, as a line of JSON, when running
+/// recipes with `--output-format json-events`
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub(crate) enum Event<'a> {
+  CommandFinish {
+    recipe: &'a str,
+    line_number: Option<usize>,
+    exit_code: Option<i32>,
+    signal: Option<i32>,
+    #[serde(serialize_with = "Event::milliseconds")]
+    duration_ms: Duration,
+  },
+  CommandStart {
+    recipe: &'a str,
+    line_number: Option<usize>,
+    command: &'a str,
+  },
+  RecipeFinish {
+    recipe: &'a str,
+    arguments: &'a [String],
+    success: bool,
+    exit_code: Option<i32>,
+    signal: Option<i32>,
+    #[serde(serialize_with = "Event::milliseconds")]
+    duration_ms: Duration,
+  },
+  RecipeSkip {
+    recipe: &'a str,
+    arguments: &'a [String],
+    reason: &'a str,
+  },
+  RecipeStart {
+    recipe: &'a str,
+    arguments: &'a [String],
+  },
+}
+
+impl Event<'_> {
+  pub(crate) fn emit(&self, log: &EventLog) {
+    if !log.enabled() {
+      return;
+    }
+
+    let mut event = serde_json::to_value(self).unwrap();
+
+    event["timestamp"] = SystemTime::now()
+      .duration_since(SystemTime::UNIX_EPOCH)
+      .unwrap_or_default()
+      .as_secs_f64()
+      .into();
+
+    log.write(&event);
+  }
+
+  /// Exit code and signal of a command or recipe which failed with `error`
+  pub(crate) fn status(error: &Error) -> (Option<i32>, Option<i32>) {
+    match error {
+      Error::Code { code, .. } => (Some(*code), None),
+      Error::Signal { signal, .. } => (None, Some(*signal)),
+      _ => (None, None),
+    }
+  }
+
+  fn milliseconds<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+  }
+}
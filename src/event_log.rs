@@ -0,0 +1,42 @@
+use super::*;
+
+/// Destination of the events emitted with `--output-format json-events`.
+/// Events are written to `--events-file`, rather than stderr, so that they
+/// are not interleaved with the output of recipes.
+pub(crate) struct EventLog {
+  file: Option<Mutex<fs::File>>,
+}
+
+impl EventLog {
+  pub(crate) fn new(config: &Config) -> RunResult<'static, Self> {
+    let file = match (&config.output_format, &config.events_file) {
+      (OutputFormat::JsonEvents, Some(path)) => {
+        let path = config.invocation_directory.join(path);
+        let file = fs::File::create(&path).map_err(|io_error| Error::EventsFile {
+          path: path.clone(),
+          io_error,
+        })?;
+        Some(Mutex::new(file))
+      }
+      _ => None,
+    };
+
+    Ok(Self { file })
+  }
+
+  pub(crate) fn enabled(&self) -> bool {
+    self.file.is_some()
+  }
+
+  /// Write `event` as a single line. Write errors are ignored, so that
+  /// recipes are not interrupted by a failure to record them.
+  pub(crate) fn write(&self, event: &serde_json::Value) {
+    if let Some(file) = &self.file {
+      file
+        .lock()
+        .unwrap()
+        .write_all(format!("{event}\n").as_bytes())
+        .ok();
+    }
+  }
+}
@@ -20,13 +20,11 @@ pub(crate) enum Expression<'src> {
     lhs: Box<Expression<'src>>,
     rhs: Box<Expression<'src>>,
   },
-  /// `if lhs == rhs { then } else { otherwise }`
+  /// `if condition { then } else { otherwise }`
   Conditional {
-    lhs: Box<Expression<'src>>,
-    rhs: Box<Expression<'src>>,
+    condition: Condition<'src>,
     then: Box<Expression<'src>>,
     otherwise: Box<Expression<'src>>,
-    operator: ConditionalOperator,
   },
   /// `(contents)`
   Group { contents: Box<Expression<'src>> },
@@ -45,6 +43,21 @@ impl<'src> Expression<'src> {
   pub(crate) fn variables<'expression>(&'expression self) -> Variables<'expression, 'src> {
     Variables::new(self)
   }
+
+  /// Calls to user-defined functions in this expression, with their arguments
+  pub(crate) fn calls<'expression>(
+    &'expression self,
+  ) -> impl Iterator<Item = (Name<'src>, &'expression [Expression<'src>])> {
+    let mut expressions = Variables::new(self);
+    iter::from_fn(move || loop {
+      if let Expression::Call {
+        thunk: Thunk::User { name, args },
+      } = expressions.next_expression()?
+      {
+        return Some((*name, args.as_slice()));
+      }
+    })
+  }
 }
 
 impl<'src> Display for Expression<'src> {
@@ -58,15 +71,16 @@ impl<'src> Display for Expression<'src> {
       } => write!(f, "{lhs} / {rhs}"),
       Expression::Concatenation { lhs, rhs } => write!(f, "{lhs} + {rhs}"),
       Expression::Conditional {
-        lhs,
-        rhs,
+        condition,
         then,
         otherwise,
-        operator,
-      } => write!(
-        f,
-        "if {lhs} {operator} {rhs} {{ {then} }} else {{ {otherwise} }}"
-      ),
+      } => {
+        if let Expression::Conditional { .. } = otherwise.as_ref() {
+          write!(f, "if {condition} {{ {then} }} else {otherwise}")
+        } else {
+          write!(f, "if {condition} {{ {then} }} else {{ {otherwise} }}")
+        }
+      }
       Expression::StringLiteral { string_literal } => write!(f, "{string_literal}"),
       Expression::Variable { name } => write!(f, "{}", name.lexeme()),
       Expression::Call { thunk } => write!(f, "{thunk}"),
@@ -103,17 +117,19 @@ impl<'src> Serialize for Expression<'src> {
         seq.end()
       }
       Self::Conditional {
-        lhs,
-        rhs,
+        condition,
         then,
         otherwise,
-        operator,
       } => {
         let mut seq = serializer.serialize_seq(None)?;
         seq.serialize_element("if")?;
-        seq.serialize_element(&operator.to_string())?;
-        seq.serialize_element(lhs)?;
-        seq.serialize_element(rhs)?;
+        if let Condition::Comparison { lhs, rhs, operator } = condition {
+          seq.serialize_element(&operator.to_string())?;
+          seq.serialize_element(lhs)?;
+          seq.serialize_element(rhs)?;
+        } else {
+          seq.serialize_element(condition)?;
+        }
         seq.serialize_element(then)?;
         seq.serialize_element(otherwise)?;
         seq.end()
@@ -5,8 +5,12 @@ use super::*;
 pub(crate) enum Fragment<'src> {
   /// …raw text…
   Text { token: Token<'src> },
-  /// …an interpolation containing `expression`.
-  Interpolation { expression: Expression<'src> },
+  /// …an interpolation containing `expression`, which, if `splat` is true,
+  /// as in `{{ *expression }}`, expands to shell-quoted words.
+  Interpolation {
+    expression: Expression<'src>,
+    splat: bool,
+  },
 }
 
 impl<'src> Serialize for Fragment<'src> {
@@ -16,8 +20,11 @@ impl<'src> Serialize for Fragment<'src> {
   {
     match self {
       Self::Text { token } => serializer.serialize_str(token.lexeme()),
-      Self::Interpolation { expression } => {
+      Self::Interpolation { expression, splat } => {
         let mut seq = serializer.serialize_seq(None)?;
+        if *splat {
+          seq.serialize_element("splat")?;
+        }
         seq.serialize_element(expression)?;
         seq.end()
       }
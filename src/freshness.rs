@@ -0,0 +1,282 @@
+use {
+  super::*,
+  sha2::{Digest, Sha256},
+  std::time::SystemTime,
+};
+
+/// Whether a recipe with `[sources]` or `[outputs]` attributes needs to run.
+///
+/// A recipe is fresh if all of its outputs exist and are newer than all of
+/// its sources, or if the digest of its sources matches the digest recorded
+/// in the cache the last time it ran successfully with the same arguments.
+pub(crate) enum Freshness {
+  Fresh {
+    reason: String,
+  },
+  Stale {
+    reason: String,
+    cache: Option<(PathBuf, String)>,
+  },
+}
+
+impl Freshness {
+  /// Check `recipe`, whose `[sources]` and `[outputs]` globs are relative to
+  /// `directory`. Returns `None` if the recipe has neither attribute.
+  pub(crate) fn check<'src>(
+    recipe: &Recipe<'src>,
+    directory: &Path,
+    arguments: &[String],
+  ) -> RunResult<'src, Option<Self>> {
+    let source_globs = recipe.sources();
+    let output_globs = recipe.outputs();
+
+    if source_globs.is_empty() && output_globs.is_empty() {
+      return Ok(None);
+    }
+
+    let sources = Self::expand(recipe, directory, &source_globs)?;
+
+    let cache = if source_globs.is_empty() {
+      None
+    } else {
+      Some((
+        Self::cache_path(recipe, directory, arguments),
+        Self::digest(recipe, directory, &sources)?,
+      ))
+    };
+
+    let mut oldest_output = None;
+
+    for glob in &output_globs {
+      let outputs = Self::expand(recipe, directory, &[glob])?;
+
+      if outputs.is_empty() {
+        return Ok(Some(Self::Stale {
+          reason: format!("output `{glob}` does not exist"),
+          cache,
+        }));
+      }
+
+      for output in outputs {
+        let modified = Self::modified(recipe, &output)?;
+        if oldest_output
+          .as_ref()
+          .map_or(true, |(oldest, _)| modified < *oldest)
+        {
+          oldest_output = Some((modified, output));
+        }
+      }
+    }
+
+    let mut newest_source = None;
+
+    for source in sources {
+      let modified = Self::modified(recipe, &source)?;
+      if newest_source
+        .as_ref()
+        .map_or(true, |(newest, _)| modified > *newest)
+      {
+        newest_source = Some((modified, source));
+      }
+    }
+
+    let mut reason = None;
+
+    if let Some((oldest, output)) = &oldest_output {
+      match &newest_source {
+        Some((newest, source)) if newest > oldest => {
+          reason = Some(format!(
+            "source `{}` is newer than output `{}`",
+            Self::relative(directory, source),
+            Self::relative(directory, output),
+          ));
+        }
+        Some(_) => {
+          return Ok(Some(Self::Fresh {
+            reason: "outputs are newer than sources".into(),
+          }));
+        }
+        None => {
+          return Ok(Some(Self::Fresh {
+            reason: "outputs exist".into(),
+          }));
+        }
+      }
+    }
+
+    let Some((path, digest)) = &cache else {
+      return Ok(Some(Self::Stale {
+        reason: reason.unwrap_or_default(),
+        cache,
+      }));
+    };
+
+    let reason = match fs::read_to_string(path) {
+      Ok(cached) if cached.trim() == digest => {
+        return Ok(Some(Self::Fresh {
+          reason: "sources unchanged since last run".into(),
+        }));
+      }
+      Ok(_) => reason.unwrap_or_else(|| "sources changed since last run".into()),
+      Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => {
+        reason.unwrap_or_else(|| "no record of previous run".into())
+      }
+      Err(io_error) => {
+        return Err(Error::Sources {
+          recipe: recipe.name(),
+          path: path.clone(),
+          io_error,
+        })
+      }
+    };
+
+    Ok(Some(Self::Stale { reason, cache }))
+  }
+
+  /// Whether `recipe` should be skipped. With `--verbose`, explains why or
+  /// why not.
+  pub(crate) fn skip(&self, config: &Config, recipe: &Recipe) -> bool {
+    let (skip, message) = match self {
+      Self::Fresh { reason } if config.force => (
+        false,
+        format!(
+          "Running recipe `{}` with `--force`: {reason}",
+          recipe.name()
+        ),
+      ),
+      Self::Fresh { reason } => (
+        true,
+        format!("Skipping recipe `{}`: {reason}", recipe.name()),
+      ),
+      Self::Stale { reason, .. } => (
+        false,
+        format!("Recipe `{}` is out of date: {reason}", recipe.name()),
+      ),
+    };
+
+    if config.verbosity.loquacious() {
+      let color = config.color.stderr().banner();
+      eprintln!("{}===> {message}{}", color.prefix(), color.suffix());
+    }
+
+    skip
+  }
+
+  pub(crate) fn reason(&self) -> &str {
+    match self {
+      Self::Fresh { reason } | Self::Stale { reason, .. } => reason,
+    }
+  }
+
+  /// Record the digest of a stale recipe's sources, after it has run
+  /// successfully
+  pub(crate) fn record<'src>(&self, recipe: &Recipe<'src>) -> RunResult<'src, ()> {
+    let Self::Stale {
+      cache: Some((path, digest)),
+      ..
+    } = self
+    else {
+      return Ok(());
+    };
+
+    write_atomic(path, format!("{digest}\n").as_bytes()).map_err(|io_error| Error::Sources {
+      recipe: recipe.name(),
+      path: path.clone(),
+      io_error,
+    })
+  }
+
+  fn expand<'src>(
+    recipe: &Recipe<'src>,
+    directory: &Path,
+    globs: &[&str],
+  ) -> RunResult<'src, BTreeSet<PathBuf>> {
+    let mut paths = BTreeSet::new();
+
+    let prefix = glob::Pattern::escape(&directory.to_string_lossy());
+
+    for glob in globs {
+      let pattern = Path::new(&prefix).join(glob);
+
+      let entries =
+        glob::glob(&pattern.to_string_lossy()).map_err(|pattern_error| Error::SourcesGlob {
+          recipe: recipe.name(),
+          glob: (*glob).to_owned(),
+          pattern_error,
+        })?;
+
+      for entry in entries {
+        let path = entry.map_err(|error| Error::Sources {
+          recipe: recipe.name(),
+          path: error.path().into(),
+          io_error: error.into(),
+        })?;
+
+        if path.is_file() {
+          paths.insert(path);
+        }
+      }
+    }
+
+    Ok(paths)
+  }
+
+  fn digest<'src>(
+    recipe: &Recipe<'src>,
+    directory: &Path,
+    sources: &BTreeSet<PathBuf>,
+  ) -> RunResult<'src, String> {
+    let mut hasher = Sha256::new();
+
+    for source in sources {
+      let contents = fs::read(source).map_err(|io_error| Error::Sources {
+        recipe: recipe.name(),
+        path: source.clone(),
+        io_error,
+      })?;
+
+      hasher.update(Self::relative(directory, source).as_bytes());
+      hasher.update([0]);
+      hasher.update((contents.len() as u64).to_le_bytes());
+      hasher.update(contents);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+  }
+
+  fn cache_path(recipe: &Recipe, directory: &Path, arguments: &[String]) -> PathBuf {
+    let mut hasher = Sha256::new();
+
+    hasher.update(recipe.namepath.as_bytes());
+
+    for argument in arguments {
+      hasher.update([0]);
+      hasher.update(argument.as_bytes());
+    }
+
+    directory
+      .join(".just")
+      .join("cache")
+      .join("sources")
+      .join(format!("{:x}", hasher.finalize()))
+  }
+
+  fn modified<'src>(recipe: &Recipe<'src>, path: &Path) -> RunResult<'src, SystemTime> {
+    path
+      .metadata()
+      .and_then(|metadata| metadata.modified())
+      .map_err(|io_error| Error::Sources {
+        recipe: recipe.name(),
+        path: path.into(),
+        io_error,
+      })
+  }
+
+  fn relative(directory: &Path, path: &Path) -> String {
+    path
+      .strip_prefix(directory)
+      .unwrap_or(path)
+      .display()
+      .to_string()
+  }
+}
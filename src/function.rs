@@ -8,75 +8,95 @@ use {
   Function::*,
 };
 
+#[derive(Clone, Copy)]
 pub(crate) enum Function {
   Nullary(fn(&FunctionContext) -> Result<String, String>),
   Unary(fn(&FunctionContext, &str) -> Result<String, String>),
   UnaryOpt(fn(&FunctionContext, &str, Option<&str>) -> Result<String, String>),
+  UnaryValue(fn(&FunctionContext, Value) -> Result<Value, String>),
   Binary(fn(&FunctionContext, &str, &str) -> Result<String, String>),
   BinaryPlus(fn(&FunctionContext, &str, &str, &[String]) -> Result<String, String>),
+  BinaryValue(fn(&FunctionContext, Value, Value) -> Result<Value, String>),
   Ternary(fn(&FunctionContext, &str, &str, &str) -> Result<String, String>),
 }
 
 pub(crate) fn get(name: &str) -> Option<Function> {
-  let function = match name {
-    "absolute_path" => Unary(absolute_path),
-    "arch" => Nullary(arch),
-    "capitalize" => Unary(capitalize),
-    "clean" => Unary(clean),
-    "env" => UnaryOpt(env),
-    "env_var" => Unary(env_var),
-    "env_var_or_default" => Binary(env_var_or_default),
-    "error" => Unary(error),
-    "extension" => Unary(extension),
-    "file_name" => Unary(file_name),
-    "file_stem" => Unary(file_stem),
-    "invocation_directory" => Nullary(invocation_directory),
-    "invocation_directory_native" => Nullary(invocation_directory_native),
-    "join" => BinaryPlus(join),
-    "just_executable" => Nullary(just_executable),
-    "justfile" => Nullary(justfile),
-    "justfile_directory" => Nullary(justfile_directory),
-    "kebabcase" => Unary(kebabcase),
-    "lowercamelcase" => Unary(lowercamelcase),
-    "lowercase" => Unary(lowercase),
-    "num_cpus" => Nullary(num_cpus),
-    "os" => Nullary(os),
-    "os_family" => Nullary(os_family),
-    "parent_directory" => Unary(parent_directory),
-    "path_exists" => Unary(path_exists),
-    "quote" => Unary(quote),
-    "replace" => Ternary(replace),
-    "replace_regex" => Ternary(replace_regex),
-    "semver_matches" => Binary(semver_matches),
-    "sha256" => Unary(sha256),
-    "sha256_file" => Unary(sha256_file),
-    "shoutykebabcase" => Unary(shoutykebabcase),
-    "shoutysnakecase" => Unary(shoutysnakecase),
-    "snakecase" => Unary(snakecase),
-    "titlecase" => Unary(titlecase),
-    "trim" => Unary(trim),
-    "trim_end" => Unary(trim_end),
-    "trim_end_match" => Binary(trim_end_match),
-    "trim_end_matches" => Binary(trim_end_matches),
-    "trim_start" => Unary(trim_start),
-    "trim_start_match" => Binary(trim_start_match),
-    "trim_start_matches" => Binary(trim_start_matches),
-    "uppercamelcase" => Unary(uppercamelcase),
-    "uppercase" => Unary(uppercase),
-    "uuid" => Nullary(uuid),
-    "without_extension" => Unary(without_extension),
-    _ => return None,
-  };
-  Some(function)
-}
+  FUNCTIONS
+    .iter()
+    .find(|(function, _)| *function == name)
+    .map(|(_, function)| *function)
+}
+
+/// Names of all built-in functions, in alphabetical order
+pub(crate) fn names() -> impl Iterator<Item = &'static str> {
+  FUNCTIONS.iter().map(|(name, _)| *name)
+}
+
+const FUNCTIONS: &[(&str, Function)] = &[
+  ("absolute_path", Unary(absolute_path)),
+  ("arch", Nullary(arch)),
+  ("capitalize", Unary(capitalize)),
+  ("clean", Unary(clean)),
+  ("env", UnaryOpt(env)),
+  ("env_var", Unary(env_var)),
+  ("env_var_or_default", Binary(env_var_or_default)),
+  ("error", Unary(error)),
+  ("extension", Unary(extension)),
+  ("file_name", Unary(file_name)),
+  ("file_stem", Unary(file_stem)),
+  ("filter", BinaryValue(filter)),
+  ("first", UnaryValue(first)),
+  ("invocation_directory", Nullary(invocation_directory)),
+  (
+    "invocation_directory_native",
+    Nullary(invocation_directory_native),
+  ),
+  ("join", BinaryPlus(join)),
+  ("join_with", BinaryValue(join_with)),
+  ("just_executable", Nullary(just_executable)),
+  ("justfile", Nullary(justfile)),
+  ("justfile_directory", Nullary(justfile_directory)),
+  ("kebabcase", Unary(kebabcase)),
+  ("len", UnaryValue(len)),
+  ("lowercamelcase", Unary(lowercamelcase)),
+  ("lowercase", Unary(lowercase)),
+  ("map", BinaryValue(map)),
+  ("num_cpus", Nullary(num_cpus)),
+  ("os", Nullary(os)),
+  ("os_family", Nullary(os_family)),
+  ("parent_directory", Unary(parent_directory)),
+  ("path_exists", Unary(path_exists)),
+  ("quote", Unary(quote)),
+  ("replace", Ternary(replace)),
+  ("replace_regex", Ternary(replace_regex)),
+  ("semver_matches", Binary(semver_matches)),
+  ("sha256", Unary(sha256)),
+  ("sha256_file", Unary(sha256_file)),
+  ("shoutykebabcase", Unary(shoutykebabcase)),
+  ("shoutysnakecase", Unary(shoutysnakecase)),
+  ("snakecase", Unary(snakecase)),
+  ("split", BinaryValue(split)),
+  ("titlecase", Unary(titlecase)),
+  ("trim", Unary(trim)),
+  ("trim_end", Unary(trim_end)),
+  ("trim_end_match", Binary(trim_end_match)),
+  ("trim_end_matches", Binary(trim_end_matches)),
+  ("trim_start", Unary(trim_start)),
+  ("trim_start_match", Binary(trim_start_match)),
+  ("trim_start_matches", Binary(trim_start_matches)),
+  ("uppercamelcase", Unary(uppercamelcase)),
+  ("uppercase", Unary(uppercase)),
+  ("uuid", Nullary(uuid)),
+  ("without_extension", Unary(without_extension)),
+];
 
 impl Function {
   pub(crate) fn argc(&self) -> Range<usize> {
     match *self {
       Nullary(_) => 0..0,
-      Unary(_) => 1..1,
+      Unary(_) | UnaryValue(_) => 1..1,
       UnaryOpt(_) => 1..2,
-      Binary(_) => 2..2,
+      Binary(_) | BinaryValue(_) => 2..2,
       BinaryPlus(_) => 2..usize::MAX,
       Ternary(_) => 3..3,
     }
@@ -182,6 +202,26 @@ fn file_stem(_context: &FunctionContext, path: &str) -> Result<String, String> {
     .ok_or_else(|| format!("Could not extract file stem from `{path}`"))
 }
 
+fn filter(_context: &FunctionContext, list: Value, regex: Value) -> Result<Value, String> {
+  let regex = Regex::new(&regex.into_string()).map_err(|err| err.to_string())?;
+  Ok(Value::List(
+    list
+      .into_list()
+      .into_iter()
+      .filter(|element| regex.is_match(element))
+      .collect(),
+  ))
+}
+
+fn first(_context: &FunctionContext, list: Value) -> Result<Value, String> {
+  list
+    .into_list()
+    .into_iter()
+    .next()
+    .map(Value::String)
+    .ok_or_else(|| "List is empty".to_owned())
+}
+
 fn invocation_directory(context: &FunctionContext) -> Result<String, String> {
   Platform::convert_native_path(
     &context.search.working_directory,
@@ -216,6 +256,12 @@ fn join(
   Ok(result.to_string())
 }
 
+fn join_with(_context: &FunctionContext, list: Value, separator: Value) -> Result<Value, String> {
+  Ok(Value::String(
+    list.into_list().join(&separator.into_string()),
+  ))
+}
+
 fn just_executable(_context: &FunctionContext) -> Result<String, String> {
   let exe_path =
     env::current_exe().map_err(|e| format!("Error getting current executable: {e}"))?;
@@ -265,6 +311,10 @@ fn kebabcase(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.to_kebab_case())
 }
 
+fn len(_context: &FunctionContext, list: Value) -> Result<Value, String> {
+  Ok(Value::String(list.into_list().len().to_string()))
+}
+
 fn lowercamelcase(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.to_lower_camel_case())
 }
@@ -273,6 +323,27 @@ fn lowercase(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.to_lowercase())
 }
 
+fn map(context: &FunctionContext, list: Value, function: Value) -> Result<Value, String> {
+  let name = function.into_string();
+
+  let function = match get(&name) {
+    Some(Unary(function)) => function,
+    Some(_) => {
+      return Err(format!(
+        "Function `{name}` cannot be used with `map` because it does not take one argument"
+      ))
+    }
+    None => return Err(format!("Unknown function `{name}`")),
+  };
+
+  list
+    .into_list()
+    .iter()
+    .map(|element| function(context, element))
+    .collect::<Result<Vec<String>, String>>()
+    .map(Value::List)
+}
+
 fn num_cpus(_context: &FunctionContext) -> Result<String, String> {
   let num = num_cpus::get();
   Ok(num.to_string())
@@ -334,7 +405,7 @@ fn sha256(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(format!("{hash:x}"))
 }
 
-fn sha256_file(context: &FunctionContext, path: &str) -> Result<String, String> {
+pub(crate) fn sha256_file(context: &FunctionContext, path: &str) -> Result<String, String> {
   use sha2::{Digest, Sha256};
   let justpath = context.search.working_directory.join(path);
   let mut hasher = Sha256::new();
@@ -358,6 +429,21 @@ fn snakecase(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.to_snake_case())
 }
 
+fn split(_context: &FunctionContext, s: Value, separator: Value) -> Result<Value, String> {
+  let separator = separator.into_string();
+
+  if separator.is_empty() {
+    return Err("Separator may not be empty".to_owned());
+  }
+
+  Ok(Value::List(
+    s.into_string()
+      .split(&separator)
+      .map(str::to_owned)
+      .collect(),
+  ))
+}
+
 fn titlecase(_context: &FunctionContext, s: &str) -> Result<String, String> {
   Ok(s.to_title_case())
 }
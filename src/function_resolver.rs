@@ -0,0 +1,185 @@
+use {super::*, CompileErrorKind::*};
+
+pub(crate) struct FunctionResolver<'src: 'run, 'run> {
+  assignments: &'run Table<'src, Assignment<'src>>,
+  functions: &'run Table<'src, UserFunction<'src>>,
+  resolved: BTreeSet<&'src str>,
+  stack: Vec<&'src str>,
+}
+
+impl<'src: 'run, 'run> FunctionResolver<'src, 'run> {
+  /// Check that calls to user-defined functions in `expressions`, and in the
+  /// bodies of `functions`, name a defined function and have the right number
+  /// of arguments, that functions are not recursive, and that function bodies
+  /// only refer to parameters and assignments.
+  pub(crate) fn resolve_functions(
+    functions: &Table<'src, UserFunction<'src>>,
+    assignments: &Table<'src, Assignment<'src>>,
+    expressions: &[&Expression<'src>],
+  ) -> CompileResult<'src, ()> {
+    let mut resolver = FunctionResolver {
+      resolved: BTreeSet::new(),
+      stack: Vec::new(),
+      assignments,
+      functions,
+    };
+
+    for expression in expressions {
+      resolver.resolve_calls(expression)?;
+    }
+
+    for function in functions.values() {
+      resolver.resolve_function(function)?;
+    }
+
+    Ok(())
+  }
+
+  fn resolve_calls(&mut self, expression: &Expression<'src>) -> CompileResult<'src, ()> {
+    for (name, arguments) in expression.calls() {
+      let Some(function) = self.functions.get(name.lexeme()) else {
+        return Err(name.error(UnknownFunction {
+          function: name.lexeme(),
+        }));
+      };
+
+      let parameters = function.parameters.len();
+
+      if arguments.len() != parameters {
+        return Err(name.error(FunctionArgumentCountMismatch {
+          function: name.lexeme(),
+          found: arguments.len(),
+          expected: parameters..parameters,
+        }));
+      }
+
+      if self.stack.contains(&name.lexeme()) {
+        self.stack.push(name.lexeme());
+        return Err(
+          name.error(CircularFunctionDependency {
+            function: name.lexeme(),
+            circle: self
+              .stack
+              .iter()
+              .skip_while(|function| **function != name.lexeme())
+              .copied()
+              .collect(),
+          }),
+        );
+      }
+
+      self.resolve_function(function)?;
+    }
+
+    Ok(())
+  }
+
+  fn resolve_function(&mut self, function: &UserFunction<'src>) -> CompileResult<'src, ()> {
+    let name = function.name.lexeme();
+
+    if self.resolved.contains(name) {
+      return Ok(());
+    }
+
+    for variable in function.globals() {
+      if !self.assignments.contains_key(variable.lexeme()) {
+        return Err(variable.error(UndefinedVariable {
+          variable: variable.lexeme(),
+        }));
+      }
+    }
+
+    self.stack.push(name);
+    self.resolve_calls(&function.body)?;
+    self.stack.pop();
+
+    self.resolved.insert(name);
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  analysis_error! {
+    name:   unknown_function,
+    input:  "a := foo()",
+    offset: 5,
+    line:   0,
+    column: 5,
+    width:  3,
+    kind:   UnknownFunction{function: "foo"},
+  }
+
+  analysis_error! {
+    name:   unknown_function_in_interpolation,
+    input:  "a:\n echo {{bar()}}",
+    offset: 11,
+    line:   1,
+    column: 8,
+    width:  3,
+    kind:   UnknownFunction{function: "bar"},
+  }
+
+  analysis_error! {
+    name:   unknown_function_in_default,
+    input:  "a f=baz():",
+    offset: 4,
+    line:   0,
+    column: 4,
+    width:  3,
+    kind:   UnknownFunction{function: "baz"},
+  }
+
+  analysis_error! {
+    name:   unknown_function_in_function,
+    input:  "fn f() := g()",
+    offset: 10,
+    line:   0,
+    column: 10,
+    width:  1,
+    kind:   UnknownFunction{function: "g"},
+  }
+
+  analysis_error! {
+    name:   user_function_argument_count_mismatch,
+    input:  "fn f(a, b) := a + b\nx := f('a')",
+    offset: 25,
+    line:   1,
+    column: 5,
+    width:  1,
+    kind:   FunctionArgumentCountMismatch{function: "f", found: 1, expected: 2..2},
+  }
+
+  analysis_error! {
+    name:   self_function_dependency,
+    input:  "fn f(a) := f(a)",
+    offset: 11,
+    line:   0,
+    column: 11,
+    width:  1,
+    kind:   CircularFunctionDependency{function: "f", circle: vec!["f", "f"]},
+  }
+
+  analysis_error! {
+    name:   circular_function_dependency,
+    input:  "fn f() := g()\nfn g() := f()",
+    offset: 24,
+    line:   1,
+    column: 10,
+    width:  1,
+    kind:   CircularFunctionDependency{function: "f", circle: vec!["f", "g", "f"]},
+  }
+
+  analysis_error! {
+    name:   undefined_variable_in_function,
+    input:  "fn f(a) := a + b",
+    offset: 15,
+    line:   0,
+    column: 15,
+    width:  1,
+    kind:   UndefinedVariable{variable: "b"},
+  }
+}
@@ -1,5 +1,20 @@
 use super::*;
 
 pub fn compile(text: &str) {
-  let _ = compiler::Compiler::compile(text);
+  let root = PathBuf::from("justfile");
+
+  let Ok(tokens) = Lexer::lex(&root, text) else {
+    return;
+  };
+
+  let Ok(ast) = Parser::parse(&tokens) else {
+    return;
+  };
+
+  let mut asts = HashMap::new();
+  asts.insert(root.clone(), ast);
+  let mut paths = HashMap::new();
+  paths.insert(root.clone(), root.clone());
+
+  let _ = Analyzer::analyze(&paths, &asts, &root, None, "");
 }
@@ -4,7 +4,7 @@ pub(crate) struct InterruptGuard;
 
 impl InterruptGuard {
   pub(crate) fn new() -> Self {
-    InterruptHandler::instance().block();
+    InterruptHandler::block(InterruptHandler::instance());
     Self
   }
 }
@@ -53,8 +53,24 @@ impl InterruptHandler {
     process::exit(130);
   }
 
-  pub(crate) fn block(&mut self) {
-    self.blocks += 1;
+  /// Block interrupts while a child process runs. If an interrupt has
+  /// already been received while other children are running, this thread is
+  /// parked instead of starting a new child, and the process exits once the
+  /// last running child finishes.
+  pub(crate) fn block(mut handler: MutexGuard<Self>) {
+    if handler.interrupted {
+      if handler.blocks == 0 {
+        Self::exit();
+      }
+
+      drop(handler);
+
+      loop {
+        thread::park();
+      }
+    }
+
+    handler.blocks += 1;
   }
 
   pub(crate) fn unblock(&mut self) {
@@ -74,7 +90,7 @@ impl InterruptHandler {
 
     self.blocks -= 1;
 
-    if self.interrupted {
+    if self.interrupted && self.blocks == 0 {
       Self::exit();
     }
   }
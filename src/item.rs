@@ -6,8 +6,22 @@ pub(crate) enum Item<'src> {
   Alias(Alias<'src, Name<'src>>),
   Assignment(Assignment<'src>),
   Comment(&'src str),
+  Function(UserFunction<'src>),
+  Import {
+    absolute: Option<PathBuf>,
+    path: Token<'src>,
+    relative: StringLiteral<'src>,
+  },
+  Module {
+    absolute: Option<PathBuf>,
+    name: Name<'src>,
+    path: Option<StringLiteral<'src>>,
+  },
   Recipe(UnresolvedRecipe<'src>),
   Set(Set<'src>),
+  Unexport {
+    name: Name<'src>,
+  },
 }
 
 impl<'src> Display for Item<'src> {
@@ -16,8 +30,20 @@ impl<'src> Display for Item<'src> {
       Item::Alias(alias) => write!(f, "{alias}"),
       Item::Assignment(assignment) => write!(f, "{assignment}"),
       Item::Comment(comment) => write!(f, "{comment}"),
+      Item::Function(function) => write!(f, "{function}"),
+      Item::Import { relative, .. } => write!(f, "import {relative}"),
+      Item::Module { name, path, .. } => {
+        write!(f, "mod {name}")?;
+
+        if let Some(path) = path {
+          write!(f, " {path}")?;
+        }
+
+        Ok(())
+      }
       Item::Recipe(recipe) => write!(f, "{}", recipe.color_display(Color::never())),
       Item::Set(set) => write!(f, "{set}"),
+      Item::Unexport { name } => write!(f, "unexport {name}"),
     }
   }
 }
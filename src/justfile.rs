@@ -1,13 +1,31 @@
 use {super::*, serde::Serialize};
 
+/// A recipe from the command line, with the module containing it, and its
+/// positional arguments and options
+type Invocation<'src, 'run> = (
+  &'run Justfile<'src>,
+  &'run Recipe<'src>,
+  Vec<&'run str>,
+  BTreeMap<&'run str, String>,
+);
+
 #[derive(Debug, PartialEq, Serialize)]
 pub(crate) struct Justfile<'src> {
   pub(crate) aliases: Table<'src, Alias<'src>>,
   pub(crate) assignments: Table<'src, Assignment<'src>>,
   #[serde(serialize_with = "keyed::serialize_option")]
-  pub(crate) first: Option<Rc<Recipe<'src>>>,
-  pub(crate) recipes: Table<'src, Rc<Recipe<'src>>>,
+  pub(crate) first: Option<Arc<Recipe<'src>>>,
+  pub(crate) functions: Table<'src, UserFunction<'src>>,
+  #[serde(skip)]
+  pub(crate) loaded: Vec<PathBuf>,
+  pub(crate) modules: Table<'src, Justfile<'src>>,
+  #[serde(skip)]
+  pub(crate) name: Option<Name<'src>>,
+  pub(crate) recipes: Table<'src, Arc<Recipe<'src>>>,
   pub(crate) settings: Settings<'src>,
+  #[serde(skip)]
+  pub(crate) source: PathBuf,
+  pub(crate) unexports: BTreeSet<String>,
   pub(crate) warnings: Vec<Warning>,
 }
 
@@ -84,13 +102,22 @@ impl<'src> Justfile<'src> {
       });
     }
 
-    let dotenv = if config.load_dotenv {
+    let sources = if config.load_dotenv {
       load_dotenv(config, &self.settings, &search.working_directory)?
     } else {
       BTreeMap::new()
     };
 
-    let scope = {
+    let dotenv = sources
+      .iter()
+      .map(|(key, (value, _path))| (key.clone(), value.clone()))
+      .collect::<BTreeMap<String, String>>();
+
+    // Assignments filtered by `set lazy`, which must outlive the scopes that
+    // they are evaluated in
+    let assignments = Arena::new();
+
+    let overrides = {
       let mut scope = Scope::new();
       let mut unknown_overrides = Vec::new();
 
@@ -108,14 +135,7 @@ impl<'src> Justfile<'src> {
         });
       }
 
-      Evaluator::evaluate_assignments(
-        &self.assignments,
-        config,
-        &dotenv,
-        scope,
-        &self.settings,
-        search,
-      )?
+      scope
     };
 
     match &config.subcommand {
@@ -134,9 +154,18 @@ impl<'src> Justfile<'src> {
 
         command.current_dir(&search.working_directory);
 
+        let scope = self.evaluate_assignments(
+          &assignments,
+          config,
+          &dotenv,
+          overrides,
+          search,
+          Vec::new(),
+        )?;
+
         let scope = scope.child();
 
-        command.export(&self.settings, &dotenv, &scope);
+        command.export(&self.settings, &dotenv, &scope, &self.unexports);
 
         let status = InterruptHandler::guard(|| command.status()).map_err(|io_error| {
           Error::CommandInvoke {
@@ -157,6 +186,19 @@ impl<'src> Justfile<'src> {
         return Ok(());
       }
       Subcommand::Evaluate { variable, .. } => {
+        let names = match variable {
+          Some(variable) => self
+            .assignments
+            .get(variable)
+            .map(|assignment| assignment.name.lexeme())
+            .into_iter()
+            .collect(),
+          None => self.assignments.keys().copied().collect(),
+        };
+
+        let scope =
+          self.evaluate_assignments(&assignments, config, &dotenv, overrides, search, names)?;
+
         if let Some(variable) = variable {
           if let Some(value) = scope.value(variable) {
             print!("{value}");
@@ -167,18 +209,43 @@ impl<'src> Justfile<'src> {
             });
           }
         } else {
-          let mut width = 0;
+          // Exported variables are prefixed with `export`, and unexported
+          // variables are listed last, so that the output reflects the
+          // environment that recipes run in
+          let names = scope
+            .bindings()
+            .map(|binding| {
+              if self.settings.export || binding.export {
+                format!("export {}", binding.name)
+              } else {
+                binding.name.lexeme().to_owned()
+              }
+            })
+            .collect::<Vec<String>>();
+
+          let width = names.iter().map(String::len).max().unwrap_or_default();
+
+          for (name, binding) in names.iter().zip(scope.bindings()) {
+            println!("{name:width$} := \"{}\"", binding.value);
+          }
+
+          for function in self.functions.values() {
+            println!("{function}");
+          }
 
-          for name in scope.names() {
-            width = cmp::max(name.len(), width);
+          for name in &self.unexports {
+            println!("unexport {name}");
           }
 
-          for binding in scope.bindings() {
+          // Variables loaded from dotenv files are followed by a comment
+          // naming the file they came from
+          for (key, (value, path)) in &sources {
             println!(
-              "{0:1$} := \"{2}\"",
-              binding.name.lexeme(),
-              width,
-              binding.value
+              "{key}={value:?} # {}",
+              path
+                .strip_prefix(&search.working_directory)
+                .unwrap_or(path)
+                .display()
             );
           }
         }
@@ -210,24 +277,33 @@ impl<'src> Justfile<'src> {
     let mut rest = arguments;
 
     while let Some((argument, mut tail)) = rest.split_first() {
-      if let Some(recipe) = self.get_recipe(argument) {
-        if recipe.parameters.is_empty() {
-          grouped.push((recipe, &[][..]));
-        } else {
-          let argument_range = recipe.argument_range();
-          let argument_count = cmp::min(tail.len(), recipe.max_arguments());
-          if !argument_range.range_contains(&argument_count) {
-            return Err(Error::ArgumentCountMismatch {
-              recipe: recipe.name(),
-              parameters: recipe.parameters.clone(),
-              found: tail.len(),
-              min: recipe.min_arguments(),
-              max: recipe.max_arguments(),
-            });
+      if let Some((module, recipe)) = self.resolve_recipe(argument, &mut tail) {
+        let mut positional = Vec::new();
+        let mut options = BTreeMap::new();
+
+        while let Some((next, remaining)) = tail.split_first() {
+          if let Some((parameter, value, remaining)) = recipe.parse_option(next, remaining)? {
+            options.insert(parameter.name.lexeme(), value);
+            tail = remaining;
+          } else if positional.len() < recipe.max_arguments() {
+            positional.push(*next);
+            tail = remaining;
+          } else {
+            break;
           }
-          grouped.push((recipe, &tail[0..argument_count]));
-          tail = &tail[argument_count..];
         }
+
+        if !recipe.argument_range().range_contains(&positional.len()) {
+          return Err(Error::ArgumentCountMismatch {
+            recipe: recipe.name(),
+            parameters: recipe.parameters.clone(),
+            found: positional.len(),
+            min: recipe.min_arguments(),
+            max: recipe.max_arguments(),
+          });
+        }
+
+        grouped.push((module, recipe, positional, options));
       } else {
         missing.push((*argument).to_owned());
       }
@@ -246,19 +322,290 @@ impl<'src> Justfile<'src> {
       });
     }
 
-    let context = RecipeContext {
-      settings: &self.settings,
+    for (_module, recipe, arguments, options) in &grouped {
+      recipe.check_arguments(arguments, options)?;
+    }
+
+    let jobs = Semaphore::new(config.jobs.unwrap_or(usize::MAX));
+
+    let timings = Timings::new(config);
+
+    let events = EventLog::new(config)?;
+
+    let scope = self.evaluate_assignments(
+      &assignments,
       config,
-      scope,
+      &dotenv,
+      overrides,
       search,
+      self.recipe_variables(&grouped),
+    )?;
+
+    // Submodules are evaluated in their own scope, with their own settings,
+    // and run in the directory containing their source file
+    let searches = Arena::new();
+
+    let mut contexts = vec![(
+      self,
+      RecipeContext {
+        settings: &self.settings,
+        config,
+        events: &events,
+        functions: &self.functions,
+        jobs: &jobs,
+        scope,
+        search,
+        timings: &timings,
+        unexports: &self.unexports,
+      },
+    )];
+
+    let ran = Ran::default();
+
+    let result = grouped
+      .iter()
+      .try_for_each(|&(module, recipe, ref arguments, ref options)| {
+        let index = if let Some(index) = contexts
+          .iter()
+          .position(|(justfile, _context)| ptr::eq(*justfile, module))
+        {
+          index
+        } else {
+          let search: &Search = searches.alloc(Search {
+            justfile: module.source.clone(),
+            working_directory: module.source.parent().unwrap().into(),
+          });
+
+          let scope = module.evaluate_assignments(
+            &assignments,
+            config,
+            &dotenv,
+            Scope::new(),
+            search,
+            module.recipe_variables(&grouped),
+          )?;
+
+          contexts.push((
+            module,
+            RecipeContext {
+              settings: &module.settings,
+              config,
+              events: &events,
+              functions: &module.functions,
+              jobs: &jobs,
+              scope,
+              search,
+              timings: &timings,
+              unexports: &module.unexports,
+            },
+          ));
+
+          contexts.len() - 1
+        };
+
+        let context = &contexts[index].1;
+
+        let output = Self::run_recipe(
+          context,
+          recipe,
+          &arguments
+            .iter()
+            .copied()
+            .map(str::to_owned)
+            .collect::<Vec<String>>(),
+          options,
+          &dotenv,
+          context.search,
+          &ran,
+        )?;
+
+        // Captured output of recipes run from the command line is printed
+        if recipe.capture() {
+          print!("{output}");
+        }
+
+        RunResult::Ok(())
+      });
+
+    // Timings are reported even if a recipe failed, since the slowest parts
+    // of a failed run are often still of interest
+    let report = timings.report(config);
+
+    result?;
+
+    report
+  }
+
+  /// Evaluate assignments in a scope containing `overrides`. With `set lazy`,
+  /// only exported assignments and those transitively referenced by `names`
+  /// are evaluated.
+  fn evaluate_assignments<'run>(
+    &'run self,
+    arena: &'run Arena<Table<'src, Assignment<'src>>>,
+    config: &'run Config,
+    dotenv: &'run BTreeMap<String, String>,
+    overrides: Scope<'src, 'run>,
+    search: &'run Search,
+    names: Vec<&'src str>,
+  ) -> RunResult<'src, Scope<'src, 'run>> {
+    let assignments = if self.settings.lazy {
+      arena.alloc(self.referenced_assignments(names))
+    } else {
+      &self.assignments
     };
 
-    let mut ran = BTreeSet::new();
-    for (recipe, arguments) in grouped {
-      Self::run_recipe(&context, recipe, arguments, &dotenv, search, &mut ran)?;
+    Evaluator::evaluate_assignments(assignments, config, dotenv, self, overrides, search)
+  }
+
+  /// Exported assignments, and assignments transitively referenced by `names`
+  fn referenced_assignments(&self, mut names: Vec<&'src str>) -> Table<'src, Assignment<'src>> {
+    names.extend(
+      self
+        .assignments
+        .values()
+        .filter(|assignment| self.settings.export || assignment.export)
+        .map(|assignment| assignment.name.lexeme()),
+    );
+
+    let mut referenced = Table::new();
+
+    while let Some(name) = names.pop() {
+      if referenced.contains_key(name) {
+        continue;
+      }
+
+      if let Some(assignment) = self.assignments.get(name) {
+        names.extend(self.expression_variables(&assignment.value));
+        referenced.insert(assignment.clone());
+      }
     }
 
-    Ok(())
+    referenced
+  }
+
+  /// Variables referenced by `expression`, and by the bodies of the
+  /// user-defined functions that it calls
+  fn expression_variables(&self, expression: &Expression<'src>) -> Vec<&'src str> {
+    let mut variables = expression
+      .variables()
+      .map(|variable| variable.lexeme())
+      .collect::<Vec<&str>>();
+
+    let mut calls = expression
+      .calls()
+      .map(|(name, _arguments)| name.lexeme())
+      .collect::<Vec<&str>>();
+
+    let mut visited = BTreeSet::new();
+
+    while let Some(name) = calls.pop() {
+      if !visited.insert(name) {
+        continue;
+      }
+
+      if let Some(function) = self.functions.get(name) {
+        variables.extend(function.globals().map(|variable| variable.lexeme()));
+        calls.extend(
+          function
+            .body
+            .calls()
+            .map(|(name, _arguments)| name.lexeme()),
+        );
+      }
+    }
+
+    variables
+  }
+
+  /// Variables referenced by those recipes in `grouped` which belong to this
+  /// module, or by their dependencies
+  fn recipe_variables(&self, grouped: &[Invocation<'src, '_>]) -> Vec<&'src str> {
+    let mut recipes = grouped
+      .iter()
+      .filter(|(module, ..)| ptr::eq(*module, self))
+      .map(|(_module, recipe, ..)| *recipe)
+      .collect::<Vec<&Recipe>>();
+
+    let mut visited = BTreeSet::new();
+    let mut variables = Vec::new();
+
+    while let Some(recipe) = recipes.pop() {
+      if !visited.insert(recipe.name()) {
+        continue;
+      }
+
+      let expressions = recipe
+        .parameters
+        .iter()
+        .filter_map(|parameter| parameter.default.as_ref())
+        .chain(
+          recipe
+            .dependencies
+            .iter()
+            .flat_map(|dependency| &dependency.arguments),
+        )
+        .chain(
+          recipe
+            .body
+            .iter()
+            .flat_map(|line| &line.fragments)
+            .filter_map(|fragment| match fragment {
+              Fragment::Interpolation { expression, .. } => Some(expression),
+              Fragment::Text { .. } => None,
+            }),
+        )
+        .chain(recipe.confirm_prompt());
+
+      for expression in expressions {
+        variables.extend(self.expression_variables(expression));
+      }
+
+      recipes.extend(
+        recipe
+          .dependencies
+          .iter()
+          .map(|dependency| dependency.recipe.as_ref()),
+      );
+    }
+
+    variables
+  }
+
+  /// Resolve `path` to a recipe and the module that contains it. Recipes in
+  /// submodules may be named with a `::`-separated path, as in `foo::build`,
+  /// or with further arguments, as in `foo build`, in which case the
+  /// arguments used are consumed from `rest`. Naming a module alone resolves
+  /// to its default recipe.
+  fn resolve_recipe<'run>(
+    &'run self,
+    path: &str,
+    rest: &mut &[&str],
+  ) -> Option<(&'run Justfile<'src>, &'run Recipe<'src>)> {
+    let components = path.split("::").collect::<Vec<&str>>();
+
+    let (last, modules) = components.split_last()?;
+
+    let mut module = self;
+
+    for name in modules {
+      module = module.modules.get(name)?;
+    }
+
+    if let Some(recipe) = module.get_recipe(last) {
+      return Some((module, recipe));
+    }
+
+    let module = module.modules.get(last)?;
+
+    if let Some((next, tail)) = rest.split_first() {
+      let mut lookahead = tail;
+      if let Some(resolved) = module.resolve_recipe(next, &mut lookahead) {
+        *rest = lookahead;
+        return Some(resolved);
+      }
+    }
+
+    module.first.as_deref().map(|recipe| (module, recipe))
   }
 
   pub(crate) fn get_alias(&self, name: &str) -> Option<&Alias<'src>> {
@@ -269,89 +616,257 @@ impl<'src> Justfile<'src> {
     self
       .recipes
       .get(name)
-      .map(Rc::as_ref)
+      .map(Arc::as_ref)
       .or_else(|| self.aliases.get(name).map(|alias| alias.target.as_ref()))
   }
 
   fn run_recipe(
     context: &RecipeContext<'src, '_>,
     recipe: &Recipe<'src>,
-    arguments: &[&str],
+    arguments: &[String],
+    options: &BTreeMap<&str, String>,
     dotenv: &BTreeMap<String, String>,
     search: &Search,
-    ran: &mut BTreeSet<Vec<String>>,
-  ) -> RunResult<'src, ()> {
-    let mut invocation = vec![recipe.name().to_owned()];
-    for argument in arguments {
-      invocation.push((*argument).to_string());
-    }
+    ran: &Ran,
+  ) -> RunResult<'src, String> {
+    let mut invocation = vec![recipe.namepath.clone()];
+    invocation.extend(arguments.iter().cloned());
+    invocation.extend(
+      options
+        .iter()
+        .map(|(name, value)| format!("--{name}={value}")),
+    );
+
+    let mutex = ran.mutex(invocation);
+
+    let mut ran_invocation = mutex.lock().unwrap();
 
-    if ran.contains(&invocation) {
-      return Ok(());
+    match &*ran_invocation {
+      Some(Outcome::Succeeded(output)) => {
+        Event::RecipeSkip {
+          recipe: &recipe.namepath,
+          arguments,
+          reason: "already ran",
+        }
+        .emit(context.events);
+        return Ok(output.clone());
+      }
+      Some(Outcome::Failed) => {
+        return Err(Error::DependencyFailed {
+          recipe: recipe.namepath.clone(),
+        })
+      }
+      None => {}
     }
 
-    if !context.config.yes && !recipe.confirm()? {
+    let result = Self::run_invocation(context, recipe, arguments, options, dotenv, search, ran);
+
+    *ran_invocation = Some(match &result {
+      Ok(output) => Outcome::Succeeded(output.clone()),
+      Err(_) => Outcome::Failed,
+    });
+
+    result
+  }
+
+  fn run_invocation(
+    context: &RecipeContext<'src, '_>,
+    recipe: &Recipe<'src>,
+    arguments: &[String],
+    options: &BTreeMap<&str, String>,
+    dotenv: &BTreeMap<String, String>,
+    search: &Search,
+    ran: &Ran,
+  ) -> RunResult<'src, String> {
+    let arguments = arguments.iter().map(String::as_str).collect::<Vec<&str>>();
+
+    let (outer, positional) =
+      Evaluator::evaluate_parameters(context, dotenv, recipe, &arguments, options)?;
+
+    if !context.config.yes
+      && !recipe.confirm(&mut Evaluator::recipe_evaluator(
+        context, dotenv, &outer, search,
+      ))?
+    {
       return Err(Error::NotConfirmed {
         recipe: recipe.name(),
       });
     }
 
-    let (outer, positional) = Evaluator::evaluate_parameters(
-      context.config,
-      dotenv,
-      &recipe.parameters,
-      arguments,
-      &context.scope,
-      context.settings,
-      search,
+    let scope = outer.child();
+
+    let mut evaluator = Evaluator::recipe_evaluator(context, dotenv, &scope, search);
+
+    let priors = Self::evaluate_dependencies(
+      &mut evaluator,
+      recipe.dependencies.iter().take(recipe.priors),
     )?;
 
-    let scope = outer.child();
+    let outputs = Self::run_dependencies(context, recipe, &priors, dotenv, search, ran)?;
 
-    let mut evaluator =
-      Evaluator::recipe_evaluator(context.config, dotenv, &scope, context.settings, search);
+    let mut captured = scope.child();
 
-    for Dependency { recipe, arguments } in recipe.dependencies.iter().take(recipe.priors) {
-      let arguments = arguments
-        .iter()
-        .map(|argument| evaluator.evaluate_expression(argument))
-        .collect::<RunResult<Vec<String>>>()?;
-
-      Self::run_recipe(
-        context,
-        recipe,
-        &arguments.iter().map(String::as_ref).collect::<Vec<&str>>(),
-        dotenv,
-        search,
-        ran,
-      )?;
+    for ((dependency, _arguments), output) in priors.iter().zip(outputs) {
+      if dependency.capture() {
+        captured.bind(false, dependency.name, output.trim().to_owned());
+      }
     }
 
-    recipe.run(context, dotenv, scope.child(), search, &positional)?;
+    let mut output = String::new();
+
+    let freshness = Freshness::check(recipe, &search.working_directory, &positional)?;
 
+    if let Some(freshness) = freshness
+      .as_ref()
+      .filter(|freshness| freshness.skip(context.config, recipe))
     {
-      let mut ran = BTreeSet::new();
+      Event::RecipeSkip {
+        recipe: &recipe.namepath,
+        arguments: &positional,
+        reason: freshness.reason(),
+      }
+      .emit(context.events);
+    } else {
+      {
+        let _job = context.jobs.acquire();
+        output = recipe.run(context, dotenv, captured.child(), search, &positional)?;
+      }
 
-      for Dependency { recipe, arguments } in recipe.dependencies.iter().skip(recipe.priors) {
-        let mut evaluated = Vec::new();
+      if let Some(freshness) = &freshness {
+        if !context.config.dry_run {
+          freshness.record(recipe)?;
+        }
+      }
+    }
+
+    let mut evaluator = Evaluator::recipe_evaluator(context, dotenv, &captured, search);
+
+    let subsequents = Self::evaluate_dependencies(
+      &mut evaluator,
+      recipe.dependencies.iter().skip(recipe.priors),
+    )?;
 
-        for argument in arguments {
-          evaluated.push(evaluator.evaluate_expression(argument)?);
+    Self::run_dependencies(
+      context,
+      recipe,
+      &subsequents,
+      dotenv,
+      search,
+      &Ran::default(),
+    )?;
+
+    Ok(output)
+  }
+
+  fn evaluate_dependencies<'a>(
+    evaluator: &mut Evaluator<'src, '_>,
+    dependencies: impl Iterator<Item = &'a Dependency<'src>>,
+  ) -> RunResult<'src, Vec<(&'a Recipe<'src>, Vec<String>)>>
+  where
+    'src: 'a,
+  {
+    let mut evaluated = Vec::new();
+
+    for Dependency { recipe, arguments } in dependencies {
+      // Arguments passed to a variadic parameter are spliced in, so that each
+      // element of a list becomes a separate argument
+      let variadic = recipe
+        .positional_parameters()
+        .position(|parameter| parameter.kind.is_variadic());
+
+      let mut values = Vec::new();
+
+      for (i, argument) in arguments.iter().enumerate() {
+        if variadic.map_or(false, |variadic| i >= variadic) {
+          values.extend(evaluator.evaluate_value(argument)?.into_list());
+        } else {
+          values.push(evaluator.evaluate_expression(argument)?);
         }
+      }
 
-        Self::run_recipe(
-          context,
-          recipe,
-          &evaluated.iter().map(String::as_ref).collect::<Vec<&str>>(),
-          dotenv,
-          search,
-          &mut ran,
-        )?;
+      if values.len() < recipe.min_arguments() {
+        return Err(Error::ArgumentCountMismatch {
+          recipe: recipe.name(),
+          parameters: recipe.parameters.clone(),
+          found: values.len(),
+          min: recipe.min_arguments(),
+          max: recipe.max_arguments(),
+        });
       }
+
+      evaluated.push((recipe.as_ref(), values));
     }
 
-    ran.insert(invocation);
-    Ok(())
+    Ok(evaluated)
+  }
+
+  /// Run `dependencies` of `recipe`, in parallel if `recipe` has the
+  /// `[parallel]` attribute or more than one job was requested with `--jobs`.
+  /// When run in parallel, all dependencies are allowed to finish and the
+  /// first error, if any, is returned.
+  fn run_dependencies(
+    context: &RecipeContext<'src, '_>,
+    recipe: &Recipe<'src>,
+    dependencies: &[(&Recipe<'src>, Vec<String>)],
+    dotenv: &BTreeMap<String, String>,
+    search: &Search,
+    ran: &Ran,
+  ) -> RunResult<'src, Vec<String>> {
+    let parallel = recipe.parallel() || context.config.jobs.map_or(false, |jobs| jobs > 1);
+
+    if !parallel || dependencies.len() < 2 {
+      return dependencies
+        .iter()
+        .map(|(recipe, arguments)| {
+          Self::run_recipe(
+            context,
+            recipe,
+            arguments,
+            &BTreeMap::new(),
+            dotenv,
+            search,
+            ran,
+          )
+        })
+        .collect();
+    }
+
+    thread::scope(|thread_scope| {
+      let handles = dependencies
+        .iter()
+        .map(|(recipe, arguments)| {
+          thread_scope.spawn(move || {
+            Self::run_recipe(
+              context,
+              recipe,
+              arguments,
+              &BTreeMap::new(),
+              dotenv,
+              search,
+              ran,
+            )
+          })
+        })
+        .collect::<Vec<_>>();
+
+      let mut result = Ok(Vec::new());
+
+      for handle in handles {
+        let joined = handle
+          .join()
+          .unwrap_or_else(|_| Err(Error::internal("dependency thread panicked")));
+
+        match (&mut result, joined) {
+          (Ok(outputs), Ok(output)) => outputs.push(output),
+          // a dependency which failed in another thread is superseded by
+          // the error of the thread which ran it
+          (Ok(_) | Err(Error::DependencyFailed { .. }), Err(error)) => result = Err(error),
+          (Err(_), _) => {}
+        }
+      }
+
+      result
+    })
   }
 
   pub(crate) fn public_recipes(&self, source_order: bool) -> Vec<&Recipe<'src, Dependency>> {
@@ -363,11 +878,40 @@ impl<'src> Justfile<'src> {
       .collect::<Vec<&Recipe<Dependency>>>();
 
     if source_order {
-      recipes.sort_by_key(|recipe| recipe.name.offset);
+      recipes.sort_by_key(|recipe| {
+        (
+          self.loaded.iter().position(|path| path == recipe.name.path),
+          recipe.name.offset,
+        )
+      });
     }
 
     recipes
   }
+
+  pub(crate) fn public_groups(&self, source_order: bool) -> Vec<String> {
+    let mut groups = Vec::new();
+
+    for recipe in self.public_recipes(source_order) {
+      for group in recipe.groups() {
+        if !groups.contains(&group) {
+          groups.push(group);
+        }
+      }
+    }
+
+    if !source_order {
+      groups.sort();
+    }
+
+    groups
+  }
+}
+
+impl<'src> Keyed<'src> for Justfile<'src> {
+  fn key(&self) -> &'src str {
+    self.name.map(|name| name.lexeme()).unwrap_or_default()
+  }
 }
 
 impl<'src> ColorDisplay for Justfile<'src> {
@@ -0,0 +1,267 @@
+use {super::*, std::fmt::Write as _};
+
+/// Lines longer than this are wrapped, if they are recipe headers or
+/// assignments
+const MAX_WIDTH: usize = 80;
+
+/// Indentation of lines continued with `\`
+const CONTINUATION_INDENT: &str = "  ";
+
+/// Formats a justfile for `--fmt`.
+///
+/// Unlike `Ast`'s `Display` implementation, which is used by `--dump`, the
+/// formatter preserves comments on the same line as items, and blank lines
+/// between items, with runs of blank lines collapsed into a single blank line.
+/// Recipes are always separated from other items by a blank line. Recipe
+/// headers and assignments longer than `MAX_WIDTH` are wrapped with `\`.
+pub(crate) struct JustfileFormatter<'src, 'ast> {
+  ast: &'ast Ast<'src>,
+  lines: Vec<&'src str>,
+}
+
+impl<'src, 'ast> JustfileFormatter<'src, 'ast> {
+  pub(crate) fn format(src: &'src str, ast: &'ast Ast<'src>) -> String {
+    Self {
+      ast,
+      lines: src.lines().collect(),
+    }
+    .formatted()
+  }
+
+  fn formatted(&self) -> String {
+    let mut formatted = String::new();
+
+    let mut previous: Option<(&Item, &Layout)> = None;
+
+    for (item, layout) in self.ast.items.iter().zip(&self.ast.layout) {
+      if let Some((previous_item, previous_layout)) = previous {
+        // Comments on the same line as the end of the previous item are not
+        // always included in its layout, so are appended to it here
+        if let Item::Comment(comment) = item {
+          if layout.first_line == previous_layout.last_line {
+            formatted.push(' ');
+            formatted.push_str(comment);
+            continue;
+          }
+        }
+
+        formatted.push('\n');
+
+        if matches!(previous_item, Item::Recipe(_))
+          || matches!(item, Item::Recipe(_))
+          || self.blank_line_between(previous_layout, layout)
+        {
+          formatted.push('\n');
+        }
+      }
+
+      Self::item(&mut formatted, item, layout);
+
+      previous = Some((item, layout));
+    }
+
+    if previous.is_some() {
+      formatted.push('\n');
+    }
+
+    formatted
+  }
+
+  fn blank_line_between(&self, previous: &Layout, next: &Layout) -> bool {
+    (previous.last_line + 1..next.first_line).any(|i| {
+      self
+        .lines
+        .get(i)
+        .map_or(false, |line| line.trim().is_empty())
+    })
+  }
+
+  fn item(formatted: &mut String, item: &Item, layout: &Layout) {
+    let words = match item {
+      Item::Alias(alias) => {
+        Self::attributes(formatted, &alias.attributes, layout);
+        vec![alias.to_string()]
+      }
+      Item::Assignment(assignment) => {
+        let mut words = vec![format!(
+          "{}{} :=",
+          if assignment.export { "export " } else { "" },
+          assignment.name,
+        )];
+        Self::operands(&mut words, None, &assignment.value);
+        words
+      }
+      Item::Comment(comment) => {
+        formatted.push_str(comment);
+        return;
+      }
+      Item::Function(function) => {
+        let parameters = function
+          .parameters
+          .iter()
+          .map(Name::lexeme)
+          .collect::<Vec<&str>>()
+          .join(", ");
+        let mut words = vec![format!(
+          "{} {}({parameters}) :=",
+          Keyword::Fn,
+          function.name
+        )];
+        Self::operands(&mut words, None, &function.body);
+        words
+      }
+      Item::Import { .. } | Item::Module { .. } | Item::Set(_) | Item::Unexport { .. } => {
+        vec![item.to_string()]
+      }
+      Item::Recipe(recipe) => {
+        Self::recipe(formatted, recipe, layout);
+        return;
+      }
+    };
+
+    Self::wrap(formatted, &words);
+    Self::comments(
+      formatted,
+      layout,
+      layout.attribute_lines.last().map_or(0, |line| line + 1),
+    );
+  }
+
+  fn recipe(formatted: &mut String, recipe: &UnresolvedRecipe, layout: &Layout) {
+    if let Some(doc) = recipe.doc {
+      writeln!(formatted, "# {doc}").unwrap();
+    }
+
+    Self::attributes(formatted, &recipe.attributes, layout);
+
+    let mut words = vec![format!(
+      "{}{}",
+      if recipe.quiet { "@" } else { "" },
+      recipe.name
+    )];
+
+    for parameter in &recipe.parameters {
+      words.push(parameter.color_display(Color::never()).to_string());
+    }
+
+    words.last_mut().unwrap().push(':');
+
+    for (i, dependency) in recipe.dependencies.iter().enumerate() {
+      if i == recipe.priors {
+        words.push("&&".into());
+      }
+      words.push(dependency.to_string());
+    }
+
+    Self::wrap(formatted, &words);
+    Self::comments(formatted, layout, recipe.name.line);
+
+    for line in &recipe.body {
+      formatted.push('\n');
+
+      for (i, fragment) in line.fragments.iter().enumerate() {
+        if i == 0 {
+          formatted.push_str("    ");
+        }
+
+        match fragment {
+          Fragment::Text { token } => formatted.push_str(token.lexeme()),
+          Fragment::Interpolation {
+            expression,
+            splat: false,
+          } => {
+            write!(formatted, "{{{{ {expression} }}}}").unwrap();
+          }
+          Fragment::Interpolation {
+            expression,
+            splat: true,
+          } => {
+            write!(formatted, "{{{{ *{expression} }}}}").unwrap();
+          }
+        }
+      }
+    }
+  }
+
+  /// Push the operands of a chain of `+` and `/` operators onto `words`,
+  /// each prefixed by its operator, so that long assignments may be wrapped
+  /// before operators
+  fn operands(words: &mut Vec<String>, operator: Option<&str>, expression: &Expression) {
+    let prefixed = |operand: &dyn Display| match operator {
+      Some(operator) => format!("{operator} {operand}"),
+      None => operand.to_string(),
+    };
+
+    match expression {
+      Expression::Concatenation { lhs, rhs } => {
+        words.push(prefixed(lhs));
+        Self::operands(words, Some("+"), rhs);
+      }
+      Expression::Join {
+        lhs: Some(lhs),
+        rhs,
+      } => {
+        words.push(prefixed(lhs));
+        Self::operands(words, Some("/"), rhs);
+      }
+      _ => words.push(prefixed(expression)),
+    }
+  }
+
+  /// Join `words` with spaces, continuing the line with `\` before any word
+  /// which would make it longer than `MAX_WIDTH`. Words containing newlines,
+  /// like indented strings, are never wrapped around.
+  fn wrap(formatted: &mut String, words: &[String]) {
+    let wrap = !words.iter().any(|word| word.contains('\n'));
+
+    let mut width = 0;
+
+    for (i, word) in words.iter().enumerate() {
+      let word_width = UnicodeWidthStr::width(word.as_str());
+
+      if i > 0 {
+        if wrap && width + 1 + word_width + 2 > MAX_WIDTH {
+          formatted.push_str(" \\\n");
+          formatted.push_str(CONTINUATION_INDENT);
+          width = CONTINUATION_INDENT.len();
+        } else {
+          formatted.push(' ');
+          width += 1;
+        }
+      }
+
+      formatted.push_str(word);
+      width += word_width;
+    }
+  }
+
+  /// Write `attributes`, one per line. Comments on the line of an attribute
+  /// are written after the last attribute from that line.
+  fn attributes(formatted: &mut String, attributes: &BTreeSet<Attribute>, layout: &Layout) {
+    let lines = &layout.attribute_lines;
+
+    for (i, attribute) in attributes.iter().enumerate() {
+      write!(formatted, "[{attribute}]").unwrap();
+
+      if let Some(line) = lines.get(i) {
+        if !lines[i + 1..].contains(line) {
+          for (_line, comment) in layout.comments.iter().filter(|(l, _)| l == line) {
+            formatted.push(' ');
+            formatted.push_str(comment);
+          }
+        }
+      }
+
+      formatted.push('\n');
+    }
+  }
+
+  /// Write comments on or after line `first`, which are at the end of the
+  /// item, or of its header if it is a recipe
+  fn comments(formatted: &mut String, layout: &Layout, first: usize) {
+    for (_line, comment) in layout.comments.iter().filter(|(line, _)| *line >= first) {
+      formatted.push(' ');
+      formatted.push_str(comment);
+    }
+  }
+}
@@ -5,25 +5,55 @@ use super::*;
 pub(crate) enum Keyword {
   Alias,
   AllowDuplicateRecipes,
+  Cached,
   DotenvFilename,
+  DotenvFiles,
   DotenvLoad,
+  DotenvOverride,
   DotenvPath,
   Else,
   Export,
   Fallback,
   False,
+  Fn,
   If,
   IgnoreComments,
+  Import,
+  In,
+  Lazy,
+  Mod,
   PositionalArguments,
   Set,
   Shell,
   True,
+  Unexport,
   WindowsPowershell,
   WindowsShell,
+  WorkingDirectory,
   Tempdir,
 }
 
 impl Keyword {
+  /// Keywords which name settings, as in `set NAME := VALUE`
+  pub(crate) const SETTINGS: &'static [Keyword] = &[
+    Keyword::AllowDuplicateRecipes,
+    Keyword::DotenvFilename,
+    Keyword::DotenvFiles,
+    Keyword::DotenvLoad,
+    Keyword::DotenvOverride,
+    Keyword::DotenvPath,
+    Keyword::Export,
+    Keyword::Fallback,
+    Keyword::IgnoreComments,
+    Keyword::Lazy,
+    Keyword::PositionalArguments,
+    Keyword::Shell,
+    Keyword::Tempdir,
+    Keyword::WindowsPowershell,
+    Keyword::WindowsShell,
+    Keyword::WorkingDirectory,
+  ];
+
   pub(crate) fn from_lexeme(lexeme: &str) -> Option<Keyword> {
     lexeme.parse().ok()
   }
@@ -0,0 +1,43 @@
+use {super::*, TokenKind::*};
+
+/// The source lines spanned by a top-level item, the comments on those lines,
+/// and the lines of the item's attributes. The formatter uses these to
+/// preserve comments and blank lines, which are otherwise discarded by the
+/// parser.
+#[derive(Debug, Clone)]
+pub(crate) struct Layout<'src> {
+  /// Lines of the item's attributes, in the order of its attribute set
+  pub(crate) attribute_lines: Vec<usize>,
+  /// Comments, and the lines they appear on
+  pub(crate) comments: Vec<(usize, &'src str)>,
+  pub(crate) first_line: usize,
+  pub(crate) last_line: usize,
+}
+
+impl<'src> Layout<'src> {
+  /// Layout of an item starting with `first` and made up of `tokens`, with
+  /// attributes on `attribute_lines`
+  pub(crate) fn new(
+    first: Token<'src>,
+    tokens: &[Token<'src>],
+    attribute_lines: Vec<usize>,
+  ) -> Self {
+    let last_line = tokens
+      .iter()
+      .filter(|token| !matches!(token.kind, Dedent | Eof | Eol | Indent | Whitespace))
+      .map(|token| token.line + token.lexeme().matches('\n').count())
+      .max()
+      .unwrap_or(first.line);
+
+    Self {
+      attribute_lines,
+      comments: tokens
+        .iter()
+        .filter(|token| token.kind == Comment)
+        .map(|token| (token.line, token.lexeme().trim_end()))
+        .collect(),
+      first_line: first.line,
+      last_line,
+    }
+  }
+}
@@ -11,6 +11,8 @@ use {super::*, CompileErrorKind::*, TokenKind::*};
 pub(crate) struct Lexer<'src> {
   /// Source text
   src: &'src str,
+  /// Path to source file, relative to the root justfile's directory
+  path: &'src Path,
   /// Char iterator
   chars: Chars<'src>,
   /// Tokens
@@ -34,13 +36,18 @@ pub(crate) struct Lexer<'src> {
 }
 
 impl<'src> Lexer<'src> {
-  /// Lex `text`
-  pub(crate) fn lex(src: &'src str) -> CompileResult<Vec<Token<'src>>> {
-    Lexer::new(src).tokenize()
+  /// Lex `src`, read from `path`
+  pub(crate) fn lex(path: &'src Path, src: &'src str) -> CompileResult<'src, Vec<Token<'src>>> {
+    Lexer::new(path, src).tokenize()
   }
 
-  /// Create a new Lexer to lex `text`
-  fn new(src: &'src str) -> Lexer<'src> {
+  #[cfg(test)]
+  pub(crate) fn test_lex(src: &'src str) -> CompileResult<'src, Vec<Token<'src>>> {
+    Lexer::new("justfile".as_ref(), src).tokenize()
+  }
+
+  /// Create a new Lexer to lex `src`
+  fn new(path: &'src Path, src: &'src str) -> Lexer<'src> {
     let mut chars = src.chars();
     let next = chars.next();
 
@@ -62,6 +69,7 @@ impl<'src> Lexer<'src> {
       chars,
       next,
       src,
+      path,
     }
   }
 
@@ -187,6 +195,7 @@ impl<'src> Lexer<'src> {
       column: self.token_start.column,
       line: self.token_start.line,
       src: self.src,
+      path: self.path,
       length: self.token_end.offset - self.token_start.offset,
       kind,
     });
@@ -200,6 +209,7 @@ impl<'src> Lexer<'src> {
     // Use `self.token_end` as the location of the error
     let token = Token {
       src: self.src,
+      path: self.path,
       offset: self.token_end.offset,
       line: self.token_end.line,
       column: self.token_end.column,
@@ -236,6 +246,7 @@ impl<'src> Lexer<'src> {
     let token = Token {
       kind: Unspecified,
       src: self.src,
+      path: self.path,
       offset: self.token_start.offset,
       line: self.token_start.line,
       column: self.token_start.column,
@@ -470,7 +481,7 @@ impl<'src> Lexer<'src> {
   fn lex_normal(&mut self, start: char) -> CompileResult<'src, ()> {
     match start {
       ' ' | '\t' => self.lex_whitespace(),
-      '!' => self.lex_digraph('!', '=', BangEquals),
+      '!' => self.lex_choices('!', &[('=', BangEquals), ('~', BangTilde)], Bang),
       '#' => self.lex_comment(),
       '$' => self.lex_single(Dollar),
       '&' => self.lex_digraph('&', '&', AmpersandAmpersand),
@@ -490,6 +501,7 @@ impl<'src> Lexer<'src> {
       ']' => self.lex_delimiter(BracketR),
       '`' | '"' | '\'' => self.lex_string(),
       '{' => self.lex_delimiter(BraceL),
+      '|' => self.lex_choices('|', &[('|', BarBar)], Bar),
       '}' => self.lex_delimiter(BraceR),
       _ if Self::is_identifier_start(start) => self.lex_identifier(),
       _ => {
@@ -893,7 +905,7 @@ mod tests {
       text.to_owned()
     };
 
-    let have = Lexer::lex(&text).unwrap();
+    let have = Lexer::test_lex(&text).unwrap();
 
     let have_kinds = have
       .iter()
@@ -942,7 +954,11 @@ mod tests {
       AmpersandAmpersand => "&&",
       Asterisk => "*",
       At => "@",
+      Bang => "!",
       BangEquals => "!=",
+      BangTilde => "!~",
+      Bar => "|",
+      BarBar => "||",
       BraceL => "{",
       BraceR => "}",
       BracketL => "[",
@@ -1000,13 +1016,14 @@ mod tests {
     length: usize,
     kind: CompileErrorKind,
   ) {
-    match Lexer::lex(src) {
+    match Lexer::test_lex(src) {
       Ok(_) => panic!("Lexing succeeded but expected"),
       Err(have) => {
         let want = CompileError {
           token: Token {
             kind: have.token.kind,
             src,
+            path: "justfile".as_ref(),
             offset,
             line,
             column,
@@ -1091,12 +1108,30 @@ mod tests {
     tokens: (EqualsEquals),
   }
 
+  test! {
+    name:   bang,
+    text:   "!",
+    tokens: (Bang),
+  }
+
   test! {
     name:   bang_equals,
     text:   "!=",
     tokens: (BangEquals),
   }
 
+  test! {
+    name:   bang_tilde,
+    text:   "!~",
+    tokens: (BangTilde),
+  }
+
+  test! {
+    name:   bar_bar,
+    text:   "||",
+    tokens: (BarBar),
+  }
+
   test! {
     name:   brace_l,
     text:   "{",
@@ -2220,16 +2255,6 @@ mod tests {
     kind:   UnterminatedInterpolation,
   }
 
-  error! {
-    name:   unexpected_character_after_bang,
-    input:  "!{",
-    offset: 1,
-    line:   0,
-    column: 1,
-    width:  1,
-    kind:   UnexpectedCharacter { expected: '=' },
-  }
-
   error! {
     name:   mismatched_closing_brace,
     input:  "(]",
@@ -2244,30 +2269,6 @@ mod tests {
     },
   }
 
-  error! {
-    name:   bang_eof,
-    input:  "!",
-    offset: 1,
-    line:   0,
-    column: 1,
-    width:  0,
-    kind:   UnexpectedEndOfToken {
-      expected: '=',
-    },
-  }
-
-  error! {
-    name:   bang_unexpected,
-    input:  "!%",
-    offset: 1,
-    line:   0,
-    column: 1,
-    width:  1,
-    kind:   UnexpectedCharacter {
-      expected: '=',
-    },
-  }
-
   error! {
     name:   ampersand_eof,
     input:  "&",
@@ -2293,7 +2294,9 @@ mod tests {
 
   #[test]
   fn presume_error() {
-    let compile_error = Lexer::new("!").presume('-').unwrap_err();
+    let compile_error = Lexer::new("justfile".as_ref(), "!")
+      .presume('-')
+      .unwrap_err();
     assert_matches!(
       compile_error.token,
       Token {
@@ -2303,6 +2306,7 @@ mod tests {
         length: 0,
         src: "!",
         kind: Unspecified,
+        ..
       }
     );
     assert_matches!(&*compile_error.kind,
@@ -2316,7 +2320,7 @@ mod tests {
         .to_string(),
       "error: Internal error, this may indicate a bug in just: \
       Lexer presumed character `-`\nconsider filing an issue: \
-      https://github.com/casey/just/issues/new\n  |\n1 | !\n  | ^"
+      https://github.com/casey/just/issues/new\n ——▶ justfile:1:1\n  |\n1 | !\n  | ^"
     );
   }
 }
@@ -10,31 +10,106 @@
 
 pub(crate) use {
   crate::{
-    alias::Alias, analyzer::Analyzer, assignment::Assignment,
-    assignment_resolver::AssignmentResolver, ast::Ast, attribute::Attribute, binding::Binding,
-    color::Color, color_display::ColorDisplay, command_ext::CommandExt,
-    compile_error::CompileError, compile_error_kind::CompileErrorKind, compiler::Compiler,
-    conditional_operator::ConditionalOperator, config::Config, config_error::ConfigError,
-    count::Count, delimiter::Delimiter, dependency::Dependency, dump_format::DumpFormat,
-    enclosure::Enclosure, error::Error, evaluator::Evaluator, expression::Expression,
-    fragment::Fragment, function::Function, function_context::FunctionContext,
-    interrupt_guard::InterruptGuard, interrupt_handler::InterruptHandler, item::Item,
-    justfile::Justfile, keyed::Keyed, keyword::Keyword, lexer::Lexer, line::Line, list::List,
-    load_dotenv::load_dotenv, loader::Loader, name::Name, ordinal::Ordinal, output::output,
-    output_error::OutputError, parameter::Parameter, parameter_kind::ParameterKind, parser::Parser,
-    platform::Platform, platform_interface::PlatformInterface, position::Position,
-    positional::Positional, range_ext::RangeExt, recipe::Recipe, recipe_context::RecipeContext,
-    recipe_resolver::RecipeResolver, scope::Scope, search::Search, search_config::SearchConfig,
-    search_error::SearchError, set::Set, setting::Setting, settings::Settings, shebang::Shebang,
-    shell::Shell, show_whitespace::ShowWhitespace, string_kind::StringKind,
-    string_literal::StringLiteral, subcommand::Subcommand, suggestion::Suggestion, table::Table,
-    thunk::Thunk, token::Token, token_kind::TokenKind, unresolved_dependency::UnresolvedDependency,
-    unresolved_recipe::UnresolvedRecipe, use_color::UseColor, variables::Variables,
-    verbosity::Verbosity, warning::Warning,
+    alias::Alias,
+    analyzer::Analyzer,
+    assignment::Assignment,
+    assignment_resolver::AssignmentResolver,
+    ast::Ast,
+    attribute::{Attribute, AttributeDiscriminant},
+    binding::Binding,
+    builtin_shell::BuiltinShell,
+    color::Color,
+    color_display::ColorDisplay,
+    command_ext::CommandExt,
+    compilation::Compilation,
+    compile_error::CompileError,
+    compile_error_kind::CompileErrorKind,
+    compiler::Compiler,
+    condition::Condition,
+    conditional_operator::ConditionalOperator,
+    config::Config,
+    config_error::ConfigError,
+    count::Count,
+    delimiter::Delimiter,
+    dependency::Dependency,
+    dump_format::DumpFormat,
+    enclosure::Enclosure,
+    error::Error,
+    evaluator::Evaluator,
+    event::Event,
+    event_log::EventLog,
+    expression::Expression,
+    fragment::Fragment,
+    freshness::Freshness,
+    function::Function,
+    function_context::FunctionContext,
+    function_resolver::FunctionResolver,
+    interrupt_guard::InterruptGuard,
+    interrupt_handler::InterruptHandler,
+    item::Item,
+    justfile::Justfile,
+    justfile_formatter::JustfileFormatter,
+    keyed::Keyed,
+    keyword::Keyword,
+    layout::Layout,
+    lexer::Lexer,
+    line::Line,
+    list::List,
+    load_dotenv::load_dotenv,
+    loader::Loader,
+    lsp::Lsp,
+    name::Name,
+    ordinal::Ordinal,
+    output::{output, process_output},
+    output_error::OutputError,
+    output_format::OutputFormat,
+    parameter::Parameter,
+    parameter_kind::ParameterKind,
+    parser::Parser,
+    pattern::Pattern,
+    platform::Platform,
+    platform_interface::PlatformInterface,
+    position::Position,
+    positional::Positional,
+    ran::{Outcome, Ran},
+    range_ext::RangeExt,
+    recipe::Recipe,
+    recipe_context::RecipeContext,
+    recipe_resolver::RecipeResolver,
+    scope::Scope,
+    search::Search,
+    search_config::SearchConfig,
+    search_error::SearchError,
+    semaphore::Semaphore,
+    set::Set,
+    setting::Setting,
+    settings::Settings,
+    shebang::Shebang,
+    shell::Shell,
+    shell_kind::ShellKind,
+    show_whitespace::ShowWhitespace,
+    string_kind::StringKind,
+    string_literal::StringLiteral,
+    subcommand::Subcommand,
+    suggestion::Suggestion,
+    table::Table,
+    thunk::Thunk,
+    timings::Timings,
+    token::Token,
+    token_kind::TokenKind,
+    unresolved_dependency::UnresolvedDependency,
+    unresolved_recipe::UnresolvedRecipe,
+    use_color::UseColor,
+    user_function::UserFunction,
+    value::Value,
+    variables::Variables,
+    verbosity::Verbosity,
+    warning::Warning,
+    write_atomic::write_atomic,
   },
   std::{
     cmp,
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     env,
     ffi::{OsStr, OsString},
     fmt::{self, Debug, Display, Formatter},
@@ -45,10 +120,10 @@ pub(crate) use {
     ops::{Index, Range, RangeInclusive},
     path::{self, Path, PathBuf},
     process::{self, Command, ExitStatus, Stdio},
-    rc::Rc,
+    ptr,
     str::{self, Chars},
-    sync::{Mutex, MutexGuard},
-    vec,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread, vec,
   },
   {
     camino::Utf8Path,
@@ -63,7 +138,7 @@ pub(crate) use {
       Serialize, Serializer,
     },
     snafu::{ResultExt, Snafu},
-    strum::{Display, EnumString, IntoStaticStr},
+    strum::{Display, EnumDiscriminants, EnumString, IntoStaticStr},
     typed_arena::Arena,
     unicode_width::{UnicodeWidthChar, UnicodeWidthStr},
   },
@@ -110,13 +185,16 @@ mod assignment_resolver;
 mod ast;
 mod attribute;
 mod binding;
+mod builtin_shell;
 mod color;
 mod color_display;
 mod command_ext;
+mod compilation;
 mod compile_error;
 mod compile_error_kind;
 mod compiler;
 mod completions;
+mod condition;
 mod conditional_operator;
 mod config;
 mod config_error;
@@ -127,32 +205,42 @@ mod dump_format;
 mod enclosure;
 mod error;
 mod evaluator;
+mod event;
+mod event_log;
 mod expression;
 mod fragment;
+mod freshness;
 mod function;
 mod function_context;
+mod function_resolver;
 mod interrupt_guard;
 mod interrupt_handler;
 mod item;
 mod justfile;
+mod justfile_formatter;
 mod keyed;
 mod keyword;
+mod layout;
 mod lexer;
 mod line;
 mod list;
 mod load_dotenv;
 mod loader;
+mod lsp;
 mod name;
 mod ordinal;
 mod output;
 mod output_error;
+mod output_format;
 mod parameter;
 mod parameter_kind;
 mod parser;
+mod pattern;
 mod platform;
 mod platform_interface;
 mod position;
 mod positional;
+mod ran;
 mod range_ext;
 mod recipe;
 mod recipe_context;
@@ -162,11 +250,13 @@ mod scope;
 mod search;
 mod search_config;
 mod search_error;
+mod semaphore;
 mod set;
 mod setting;
 mod settings;
 mod shebang;
 mod shell;
+mod shell_kind;
 mod show_whitespace;
 mod string_kind;
 mod string_literal;
@@ -174,12 +264,16 @@ mod subcommand;
 mod suggestion;
 mod table;
 mod thunk;
+mod timings;
 mod token;
 mod token_kind;
 mod unindent;
 mod unresolved_dependency;
 mod unresolved_recipe;
 mod use_color;
+mod user_function;
+mod value;
 mod variables;
 mod verbosity;
 mod warning;
+mod write_atomic;
@@ -2,11 +2,13 @@ use super::*;
 
 const DEFAULT_DOTENV_FILENAME: &str = ".env";
 
+/// Load environment variables from dotenv files, returning each variable's
+/// value along with the path of the file it was loaded from
 pub(crate) fn load_dotenv(
   config: &Config,
   settings: &Settings,
   working_directory: &Path,
-) -> RunResult<'static, BTreeMap<String, String>> {
+) -> RunResult<'static, BTreeMap<String, (String, PathBuf)>> {
   let dotenv_filename = config
     .dotenv_filename
     .as_ref()
@@ -17,12 +19,37 @@ pub(crate) fn load_dotenv(
     .as_ref()
     .or(settings.dotenv_path.as_ref());
 
-  if !settings.dotenv_load.unwrap_or(false) && dotenv_filename.is_none() && dotenv_path.is_none() {
-    return Ok(BTreeMap::new());
+  let mut dotenv = BTreeMap::new();
+
+  if !settings.dotenv_load.unwrap_or(false)
+    && dotenv_filename.is_none()
+    && dotenv_path.is_none()
+    && settings.dotenv_files.is_none()
+  {
+    return Ok(dotenv);
   }
 
   if let Some(path) = dotenv_path {
-    return load_from_file(path);
+    load_from_file(settings, path, &mut dotenv)?;
+    return Ok(dotenv);
+  }
+
+  if let (None, Some(files)) = (dotenv_filename, &settings.dotenv_files) {
+    // Files are loaded in order, with variables in later files overriding
+    // those in earlier files. Files which don't exist are skipped.
+    for file in files {
+      let Some(file) = expand(settings, file, &dotenv) else {
+        continue;
+      };
+
+      let path = working_directory.join(file);
+
+      if path.is_file() {
+        load_from_file(settings, &path, &mut dotenv)?;
+      }
+    }
+
+    return Ok(dotenv);
   }
 
   let filename = dotenv_filename.map_or(DEFAULT_DOTENV_FILENAME, |s| s.as_str());
@@ -30,21 +57,218 @@ pub(crate) fn load_dotenv(
   for directory in working_directory.ancestors() {
     let path = directory.join(filename);
     if path.is_file() {
-      return load_from_file(&path);
+      load_from_file(settings, &path, &mut dotenv)?;
+      break;
     }
   }
 
-  Ok(BTreeMap::new())
+  Ok(dotenv)
 }
 
-fn load_from_file(path: &Path) -> RunResult<'static, BTreeMap<String, String>> {
-  let iter = dotenvy::from_path_iter(path)?;
-  let mut dotenv = BTreeMap::new();
-  for result in iter {
-    let (key, value) = result?;
-    if env::var_os(&key).is_none() {
-      dotenv.insert(key, value);
+/// Load variables from the file at `path` into `dotenv`. Variables already
+/// loaded from other files are available for `${VAR}` substitution.
+fn load_from_file(
+  settings: &Settings,
+  path: &Path,
+  dotenv: &mut BTreeMap<String, (String, PathBuf)>,
+) -> RunResult<'static, ()> {
+  let error = |dotenv_error| Error::Dotenv {
+    path: path.into(),
+    dotenv_error,
+  };
+
+  let src = fs::read_to_string(path).map_err(|io_error| error(dotenvy::Error::Io(io_error)))?;
+
+  // Parse the file on its own before substitution, so that errors refer to
+  // the file's original contents
+  for result in dotenvy::from_read_iter(src.as_bytes()) {
+    result.map_err(error)?;
+  }
+
+  let src = substitute(&src, dotenv);
+
+  for result in dotenvy::from_read_iter(src.as_bytes()) {
+    let (key, value) = result.map_err(error)?;
+    if settings.dotenv_override || env::var_os(&key).is_none() {
+      dotenv.insert(key, (value, path.into()));
     }
   }
-  Ok(dotenv)
+
+  Ok(())
+}
+
+/// Replace references in the values of dotenv file `src` to variables loaded
+/// from earlier files with their values, quoted so that they are not subject
+/// to further substitution. `dotenv` only contains variables which take
+/// precedence over the environment, so references to variables which are
+/// set in the environment, defined earlier in `src`, or not defined at all,
+/// are left for `dotenvy` to resolve.
+fn substitute(src: &str, dotenv: &BTreeMap<String, (String, PathBuf)>) -> String {
+  let mut defined = HashSet::new();
+
+  let mut output = String::new();
+
+  let mut quote = None;
+
+  for line in src.split_inclusive('\n') {
+    let mut key = None;
+
+    let value = if quote.is_some() {
+      line
+    } else {
+      let trimmed = line.trim_start();
+
+      let Some(equals) = trimmed.find('=').filter(|_| !trimmed.starts_with('#')) else {
+        output.push_str(line);
+        continue;
+      };
+
+      let name = trimmed[..equals].trim();
+
+      key = Some(
+        name
+          .strip_prefix("export")
+          .filter(|rest| rest.starts_with(char::is_whitespace))
+          .map_or(name, str::trim_start),
+      );
+
+      let value = &line[line.len() - trimmed.len() + equals + 1..];
+
+      output.push_str(&line[..line.len() - value.len()]);
+
+      value
+    };
+
+    let mut escaped = false;
+
+    let mut chars = value.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+      if escaped {
+        escaped = false;
+      } else if quote == Some('\'') {
+        if c == '\'' {
+          quote = None;
+        }
+      } else if c == '$' {
+        let rest = &value[i + 1..];
+
+        let (name, length) = if let Some(braced) = rest.strip_prefix('{') {
+          match braced.find('}') {
+            Some(end) => (&braced[..end], end + 2),
+            None => ("", 0),
+          }
+        } else {
+          let end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+          (&rest[..end], end)
+        };
+
+        if let Some((replacement, _path)) = dotenv
+          .get(name)
+          .filter(|_| !name.is_empty() && !defined.contains(name))
+        {
+          output.push_str(&quoted(replacement, quote));
+          for _ in 0..rest[..length].chars().count() {
+            chars.next();
+          }
+          continue;
+        }
+      } else if quote == Some('"') {
+        match c {
+          '"' => quote = None,
+          '\\' => escaped = true,
+          _ => {}
+        }
+      } else {
+        match c {
+          '\'' | '"' => quote = Some(c),
+          '\\' => escaped = true,
+          // unquoted whitespace ends the value, and may only be followed
+          // by a comment
+          ' ' | '\t' => {
+            output.push_str(&value[i..]);
+            break;
+          }
+          _ => {}
+        }
+      }
+
+      output.push(c);
+    }
+
+    // a variable may refer to its own value from an earlier file
+    defined.extend(key);
+  }
+
+  output
+}
+
+/// Quote `value` for inclusion in a dotenv value, inside of `quote`
+fn quoted(value: &str, quote: Option<char>) -> String {
+  let mut escaped = String::new();
+
+  for c in value.chars() {
+    match c {
+      '\\' | '"' | '$' => {
+        escaped.push('\\');
+        escaped.push(c);
+      }
+      '\n' => escaped.push_str("\\n"),
+      _ => escaped.push(c),
+    }
+  }
+
+  if quote == Some('"') {
+    escaped
+  } else {
+    format!("\"{escaped}\"")
+  }
+}
+
+/// Expand `$VAR` and `${VAR}` in `file`, returning `None` if a variable is not
+/// defined
+fn expand(
+  settings: &Settings,
+  file: &str,
+  dotenv: &BTreeMap<String, (String, PathBuf)>,
+) -> Option<String> {
+  let lookup = |name: &str| {
+    let loaded = dotenv.get(name).map(|(value, _path)| value.clone());
+    if settings.dotenv_override {
+      loaded.or_else(|| env::var(name).ok())
+    } else {
+      env::var(name).ok().or(loaded)
+    }
+  };
+
+  let mut expanded = String::new();
+
+  let mut rest = file;
+
+  while let Some(i) = rest.find('$') {
+    expanded.push_str(&rest[..i]);
+
+    rest = &rest[i + 1..];
+
+    let name = if let Some(braced) = rest.strip_prefix('{') {
+      let end = braced.find('}')?;
+      rest = &braced[end + 1..];
+      &braced[..end]
+    } else {
+      let end = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+      let name = &rest[..end];
+      rest = &rest[end..];
+      name
+    };
+
+    expanded.push_str(&lookup(name)?);
+  }
+
+  expanded.push_str(rest);
+
+  Some(expanded)
 }
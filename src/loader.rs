@@ -26,24 +26,51 @@ impl<'a> Iterator for LinesWithEndings<'a> {
 }
 
 pub(crate) struct Loader {
-  arena: Arena<String>,
+  overlays: HashMap<PathBuf, String>,
+  paths: Arena<PathBuf>,
+  srcs: Arena<String>,
   unstable: bool,
 }
 
 impl Loader {
   pub(crate) fn new(unstable: bool) -> Self {
     Loader {
-      arena: Arena::new(),
+      overlays: HashMap::new(),
+      paths: Arena::new(),
+      srcs: Arena::new(),
       unstable,
     }
   }
 
-  pub(crate) fn load<'src>(&'src self, path: &Path) -> RunResult<&'src str> {
+  /// Use `src` as the contents of the file at `path`, instead of reading it
+  /// from disk, as when compiling unsaved changes in an editor
+  pub(crate) fn overlay(mut self, path: PathBuf, src: String) -> Self {
+    self.overlays.insert(path, src);
+    self
+  }
+
+  /// Load the source file at `path`, returning it along with its path
+  /// relative to the directory containing the `root` justfile
+  pub(crate) fn load<'src>(
+    &'src self,
+    root: &Path,
+    path: &Path,
+  ) -> RunResult<'src, (&'src Path, &'src str)> {
     let src = self.load_recursive(path, HashSet::new())?;
-    Ok(self.arena.alloc(src))
+
+    let relative = root
+      .parent()
+      .and_then(|directory| path.strip_prefix(directory).ok())
+      .unwrap_or(path);
+
+    Ok((self.paths.alloc(relative.into()), self.srcs.alloc(src)))
   }
 
-  fn load_file<'a>(path: &Path) -> RunResult<'a, String> {
+  fn load_file<'a>(&self, path: &Path) -> RunResult<'a, String> {
+    if let Some(src) = self.overlays.get(path) {
+      return Ok(src.clone());
+    }
+
     fs::read_to_string(path).map_err(|io_error| Error::Load {
       path: path.to_owned(),
       io_error,
@@ -51,7 +78,7 @@ impl Loader {
   }
 
   fn load_recursive(&self, file: &Path, seen: HashSet<PathBuf>) -> RunResult<String> {
-    let src = Self::load_file(file)?;
+    let src = self.load_file(file)?;
 
     let mut output = String::new();
 
@@ -174,7 +201,7 @@ some_recipe: recipe_b
     let loader = Loader::new(true);
 
     let justfile_a_path = tmp.path().join("justfile");
-    let loader_output = loader.load(&justfile_a_path).unwrap();
+    let (_path, loader_output) = loader.load(&justfile_a_path, &justfile_a_path).unwrap();
 
     assert_eq!(loader_output, full_concatenated_output);
   }
@@ -206,7 +233,7 @@ recipe_b:
     let loader = Loader::new(true);
 
     let justfile_a_path = tmp.path().join("justfile");
-    let loader_output = loader.load(&justfile_a_path).unwrap_err();
+    let loader_output = loader.load(&justfile_a_path, &justfile_a_path).unwrap_err();
 
     assert_matches!(loader_output, Error::CircularInclude { current, include }
         if current == tmp.path().join("subdir").join("justfile_b").lexiclean() &&
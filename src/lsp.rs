@@ -0,0 +1,809 @@
+use {
+  super::*,
+  serde_json::{json, Value},
+  std::{
+    fmt::Write as _,
+    io::{BufRead, Write},
+  },
+};
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const PARSE_ERROR: i64 = -32700;
+
+/// A language server for justfiles, which speaks the Language Server
+/// Protocol over `input` and `output`. Diagnostics, definitions, and hovers
+/// are produced by the same lexer, parser, and analyzer as the command line,
+/// and formatting is the same as `--fmt`.
+pub(crate) struct Lsp<R, W> {
+  documents: BTreeMap<String, Document>,
+  input: R,
+  output: W,
+  unstable: bool,
+}
+
+impl<R: BufRead, W: Write> Lsp<R, W> {
+  pub(crate) fn new(input: R, output: W, unstable: bool) -> Self {
+    Self {
+      documents: BTreeMap::new(),
+      input,
+      output,
+      unstable,
+    }
+  }
+
+  pub(crate) fn run(mut self) -> Result<(), Error<'static>> {
+    while let Some(content) = self.receive()? {
+      let message = match serde_json::from_str::<Value>(&content) {
+        Ok(message) => message,
+        Err(error) => {
+          self.send(&json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": { "code": PARSE_ERROR, "message": error.to_string() },
+          }))?;
+          continue;
+        }
+      };
+
+      let Some(method) = message["method"].as_str() else {
+        continue;
+      };
+
+      if method == "exit" {
+        break;
+      }
+
+      let result = self.handle(method, &message["params"])?;
+
+      if let Some(id) = message.get("id") {
+        let response = match result {
+          Some(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+          None => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+              "code": METHOD_NOT_FOUND,
+              "message": format!("Method `{method}` not found"),
+            },
+          }),
+        };
+
+        self.send(&response)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Handle a request or notification, returning the result of a request,
+  /// or `None` if the method is not supported
+  fn handle(&mut self, method: &str, params: &Value) -> Result<Option<Value>, Error<'static>> {
+    let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+    let result = match method {
+      "initialize" => json!({
+        "capabilities": {
+          "completionProvider": {},
+          "definitionProvider": true,
+          "documentFormattingProvider": true,
+          "hoverProvider": true,
+          "textDocumentSync": 1,
+        },
+        "serverInfo": {
+          "name": env!("CARGO_PKG_NAME"),
+          "version": env!("CARGO_PKG_VERSION"),
+        },
+      }),
+      "shutdown" => Value::Null,
+      "textDocument/didOpen" => {
+        if let (Some(path), Some(text)) =
+          (Document::path(uri), params["textDocument"]["text"].as_str())
+        {
+          self.documents.insert(
+            uri.into(),
+            Document {
+              path,
+              text: text.into(),
+              unstable: self.unstable,
+            },
+          );
+          self.publish_diagnostics(uri)?;
+        }
+        return Ok(None);
+      }
+      "textDocument/didChange" => {
+        let text = params["contentChanges"]
+          .as_array()
+          .and_then(|changes| changes.last())
+          .and_then(|change| change["text"].as_str());
+
+        if let (Some(document), Some(text)) = (self.documents.get_mut(uri), text) {
+          document.text = text.into();
+          self.publish_diagnostics(uri)?;
+        }
+        return Ok(None);
+      }
+      "textDocument/didClose" => {
+        self.documents.remove(uri);
+        self.publish_diagnostics(uri)?;
+        return Ok(None);
+      }
+      "textDocument/completion" => self.documents.get(uri).map_or(Value::Null, |document| {
+        document.completion(Self::position(params))
+      }),
+      "textDocument/definition" => self.documents.get(uri).map_or(Value::Null, |document| {
+        document.definition(Self::position(params))
+      }),
+      "textDocument/formatting" => self
+        .documents
+        .get(uri)
+        .map_or(Value::Null, Document::formatting),
+      "textDocument/hover" => self.documents.get(uri).map_or(Value::Null, |document| {
+        document.hover(Self::position(params))
+      }),
+      _ => return Ok(None),
+    };
+
+    Ok(Some(result))
+  }
+
+  fn position(params: &Value) -> (usize, usize) {
+    let position = &params["position"];
+    (
+      Self::index(&position["line"]),
+      Self::index(&position["character"]),
+    )
+  }
+
+  fn index(value: &Value) -> usize {
+    value
+      .as_u64()
+      .and_then(|index| index.try_into().ok())
+      .unwrap_or_default()
+  }
+
+  fn publish_diagnostics(&mut self, uri: &str) -> Result<(), Error<'static>> {
+    let diagnostics = self
+      .documents
+      .get(uri)
+      .map(Document::diagnostics)
+      .unwrap_or_default();
+
+    self.send(&json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/publishDiagnostics",
+      "params": { "uri": uri, "diagnostics": diagnostics },
+    }))
+  }
+
+  /// Receive the content of the next message, or `None` at end of input
+  fn receive(&mut self) -> Result<Option<String>, Error<'static>> {
+    let mut content_length = None;
+
+    loop {
+      let mut header = String::new();
+
+      if self
+        .input
+        .read_line(&mut header)
+        .map_err(|io_error| Error::LanguageServerIo { io_error })?
+        == 0
+      {
+        return Ok(None);
+      }
+
+      let header = header.trim_end();
+
+      if header.is_empty() {
+        if content_length.is_some() {
+          break;
+        }
+        continue;
+      }
+
+      if let Some((name, value)) = header.split_once(':') {
+        if name.eq_ignore_ascii_case("content-length") {
+          content_length = value.trim().parse::<usize>().ok();
+        }
+      }
+    }
+
+    let mut content = vec![0; content_length.unwrap()];
+
+    self
+      .input
+      .read_exact(&mut content)
+      .map_err(|io_error| Error::LanguageServerIo { io_error })?;
+
+    Ok(Some(String::from_utf8_lossy(&content).into_owned()))
+  }
+
+  fn send(&mut self, message: &Value) -> Result<(), Error<'static>> {
+    let content = message.to_string();
+
+    write!(
+      self.output,
+      "Content-Length: {}\r\n\r\n{content}",
+      content.len()
+    )
+    .and_then(|()| self.output.flush())
+    .map_err(|io_error| Error::LanguageServerIo { io_error })
+  }
+}
+
+/// A justfile open in the editor, which may have unsaved changes
+#[derive(Clone)]
+struct Document {
+  path: PathBuf,
+  text: String,
+  unstable: bool,
+}
+
+/// A name in a justfile which refers to a recipe or variable
+#[derive(Clone, Copy)]
+enum Symbol<'src> {
+  Recipe(&'src str),
+  Variable {
+    name: &'src str,
+    recipe: Option<&'src str>,
+  },
+}
+
+impl Document {
+  fn path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?.as_bytes();
+
+    let mut decoded = Vec::new();
+
+    let mut i = 0;
+    while i < encoded.len() {
+      if encoded[i] == b'%' {
+        let byte = encoded
+          .get(i + 1..i + 3)
+          .and_then(|hex| std::str::from_utf8(hex).ok())
+          .and_then(|hex| u8::from_str_radix(hex, 16).ok())?;
+        decoded.push(byte);
+        i += 3;
+      } else {
+        decoded.push(encoded[i]);
+        i += 1;
+      }
+    }
+
+    let path = String::from_utf8(decoded).ok()?;
+
+    // Windows paths look like `/C:/justfile`
+    let path = match path.as_bytes() {
+      [b'/', drive, b':', ..] if cfg!(windows) && drive.is_ascii_alphabetic() => &path[1..],
+      _ => &path,
+    };
+
+    Some(path.into())
+  }
+
+  fn uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+
+    let path = path.to_string_lossy().replace('\\', "/");
+
+    if !path.starts_with('/') {
+      uri.push('/');
+    }
+
+    for byte in path.bytes() {
+      if byte.is_ascii_alphanumeric() || b"-._~/:".contains(&byte) {
+        uri.push(byte.into());
+      } else {
+        write!(uri, "%{byte:02X}").unwrap();
+      }
+    }
+
+    uri
+  }
+
+  /// Compile the document, and call `f` with its syntax tree and, if it
+  /// compiles, the justfile. If it doesn't compile, the syntax tree is
+  /// produced by parsing the document alone.
+  fn analyze<T>(&self, f: impl FnOnce(&Ast, Option<&Justfile>) -> T) -> Option<T> {
+    let loader = Loader::new(self.unstable).overlay(self.path.clone(), self.text.clone());
+
+    if let Ok(compilation) = Compiler::compile(&loader, &self.path) {
+      return Some(f(compilation.root_ast(), Some(&compilation.justfile)));
+    }
+
+    let tokens = Lexer::lex(&self.path, &self.text).ok()?;
+    let ast = Parser::parse(&tokens).ok()?;
+
+    Some(f(&ast, None))
+  }
+
+  fn diagnostics(&self) -> Vec<Value> {
+    let loader = Loader::new(self.unstable).overlay(self.path.clone(), self.text.clone());
+
+    let Err(error) = Compiler::compile(&loader, &self.path) else {
+      return Vec::new();
+    };
+
+    let (mut message, token) = if let Error::Compile { compile_error } = &error {
+      (compile_error.to_string(), Some(compile_error.context()))
+    } else {
+      let token = error.context();
+
+      let mut message = error.color_display(Color::never()).to_string();
+
+      if let Some(token) = token {
+        let context = format!("\n{}", token.color_display(Color::never()));
+        if let Some(stripped) = message.strip_suffix(&context) {
+          message = stripped.into();
+        }
+      }
+
+      if let Some(stripped) = message.strip_prefix("error: ") {
+        message = stripped.into();
+      }
+
+      (message, token)
+    };
+
+    let range = match token {
+      Some(token) if self.contains(token) => Self::range(token),
+      Some(token) => {
+        message = format!(
+          "{}:{}:{}: {message}",
+          token.path.display(),
+          token.line.ordinal(),
+          token.column.ordinal()
+        );
+        json!({
+          "start": { "line": 0, "character": 0 },
+          "end": { "line": 0, "character": 0 },
+        })
+      }
+      None => json!({
+        "start": { "line": 0, "character": 0 },
+        "end": { "line": 0, "character": 0 },
+      }),
+    };
+
+    vec![json!({
+      "range": range,
+      "severity": 1,
+      "source": env!("CARGO_PKG_NAME"),
+      "message": message,
+    })]
+  }
+
+  fn completion(&self, (line, character): (usize, usize)) -> Value {
+    let offset = Self::offset(&self.text, line, character);
+
+    let line_start = self.text[..offset].rfind('\n').map_or(0, |i| i + 1);
+
+    let before = &self.text[line_start..offset];
+
+    let mut words = before.split_whitespace();
+
+    if words.next() == Some(Keyword::Set.lexeme()) && words.nth(1).is_none() {
+      return Keyword::SETTINGS
+        .iter()
+        .map(|setting| json!({ "label": setting.lexeme(), "kind": 10, "detail": "setting" }))
+        .collect();
+    }
+
+    let mut items = function::names()
+      .chain(iter::once(Keyword::Cached.lexeme()))
+      .map(|name| json!({ "label": name, "kind": 3, "detail": "function" }))
+      .collect::<Vec<Value>>();
+
+    let indented = before.starts_with(|c: char| c.is_whitespace());
+
+    let symbols = |ast: &Ast| {
+      let mut items = Vec::new();
+
+      // Parameters are only in scope in the header and body of the recipe
+      // that the cursor is in
+      let enclosing = ast
+        .items
+        .iter()
+        .filter_map(|item| match item {
+          Item::Recipe(recipe) if recipe.name.offset <= offset => Some(recipe),
+          _ => None,
+        })
+        .next_back()
+        .filter(|recipe| indented || recipe.name.offset >= line_start);
+
+      if let Some(recipe) = enclosing {
+        for parameter in &recipe.parameters {
+          items.push(json!({
+            "label": parameter.name.lexeme(),
+            "kind": 6,
+            "detail": "parameter",
+          }));
+        }
+      }
+
+      for item in &ast.items {
+        match item {
+          Item::Assignment(assignment) => items.push(json!({
+            "label": assignment.name.lexeme(),
+            "kind": 6,
+            "detail": "variable",
+          })),
+          Item::Recipe(recipe) => items.push(json!({
+            "label": recipe.name.lexeme(),
+            "kind": 2,
+            "detail": "recipe",
+            "documentation": recipe.doc,
+          })),
+          _ => {}
+        }
+      }
+
+      items
+    };
+
+    // The line being edited is often incomplete, so if the document doesn't
+    // parse, try again with that line blanked out
+    let symbols = self.analyze(|ast, _justfile| symbols(ast)).or_else(|| {
+      let line_end = self.text[offset..]
+        .find('\n')
+        .map_or(self.text.len(), |i| offset + i);
+
+      let document = Self {
+        text: format!(
+          "{}{}{}",
+          &self.text[..line_start],
+          " ".repeat(line_end - line_start),
+          &self.text[line_end..],
+        ),
+        ..self.clone()
+      };
+
+      document.analyze(|ast, _justfile| symbols(ast))
+    });
+
+    items.extend(symbols.into_iter().flatten());
+
+    Value::Array(items)
+  }
+
+  fn definition(&self, (line, character): (usize, usize)) -> Value {
+    let offset = Self::offset(&self.text, line, character);
+
+    let directory = self.path.parent().unwrap_or(&self.path);
+
+    self
+      .analyze(|ast, justfile| {
+        let (_token, symbol) = Self::symbol_at(ast, offset)?;
+
+        let token = match symbol {
+          Symbol::Recipe(name) => ast
+            .items
+            .iter()
+            .find_map(|item| match item {
+              Item::Alias(alias) if alias.name.lexeme() == name => Some(alias.name.token()),
+              Item::Recipe(recipe) if recipe.name.lexeme() == name => Some(recipe.name.token()),
+              _ => None,
+            })
+            .or_else(|| {
+              let justfile = justfile?;
+              justfile
+                .aliases
+                .get(name)
+                .map(|alias| alias.name.token())
+                .or_else(|| justfile.recipes.get(name).map(|recipe| recipe.name.token()))
+            }),
+          Symbol::Variable { name, recipe } => Self::parameter(ast, recipe, name)
+            .map(|parameter| parameter.name.token())
+            .or_else(|| {
+              ast.items.iter().find_map(|item| match item {
+                Item::Assignment(assignment) if assignment.name.lexeme() == name => {
+                  Some(assignment.name.token())
+                }
+                _ => None,
+              })
+            })
+            .or_else(|| {
+              justfile?
+                .assignments
+                .get(name)
+                .map(|assignment| assignment.name.token())
+            }),
+        }?;
+
+        Some(json!({
+          "uri": Self::uri(&directory.join(token.path)),
+          "range": Self::range(token),
+        }))
+      })
+      .flatten()
+      .unwrap_or(Value::Null)
+  }
+
+  fn formatting(&self) -> Value {
+    let Ok(tokens) = Lexer::lex(&self.path, &self.text) else {
+      return Value::Null;
+    };
+
+    let Ok(ast) = Parser::parse(&tokens) else {
+      return Value::Null;
+    };
+
+    let formatted = JustfileFormatter::format(&self.text, &ast);
+
+    if formatted == self.text {
+      return json!([]);
+    }
+
+    json!([{
+      "range": {
+        "start": { "line": 0, "character": 0 },
+        "end": Self::position(&self.text, self.text.len()),
+      },
+      "newText": formatted,
+    }])
+  }
+
+  fn hover(&self, (line, character): (usize, usize)) -> Value {
+    let offset = Self::offset(&self.text, line, character);
+
+    self
+      .analyze(|ast, justfile| {
+        let (token, symbol) = Self::symbol_at(ast, offset)?;
+
+        let contents = match symbol {
+          Symbol::Recipe(name) => {
+            let recipe = ast.items.iter().find_map(|item| match item {
+              Item::Recipe(recipe) if recipe.name.lexeme() == name => {
+                Some(recipe.color_display(Color::never()).to_string())
+              }
+              _ => None,
+            });
+
+            let alias = ast.items.iter().find_map(|item| match item {
+              Item::Alias(alias) if alias.name.lexeme() == name => Some(alias),
+              _ => None,
+            });
+
+            if let Some(alias) = alias {
+              let target = ast.items.iter().find_map(|item| match item {
+                Item::Recipe(recipe) if recipe.name.lexeme() == alias.target.lexeme() => {
+                  Some(recipe.color_display(Color::never()).to_string())
+                }
+                _ => None,
+              });
+              match target {
+                Some(target) => format!("{alias}\n{target}"),
+                None => alias.to_string(),
+              }
+            } else if let Some(recipe) = recipe {
+              recipe
+            } else {
+              let justfile = justfile?;
+              if let Some(alias) = justfile.get_alias(name) {
+                format!("{alias}\n{}", alias.target.color_display(Color::never()))
+              } else {
+                justfile
+                  .get_recipe(name)?
+                  .color_display(Color::never())
+                  .to_string()
+              }
+            }
+          }
+          Symbol::Variable { name, recipe } => {
+            if let Some(parameter) = Self::parameter(ast, recipe, name) {
+              format!(
+                "```just\n{}\n```\n\nParameter of recipe `{}`",
+                parameter.color_display(Color::never()),
+                recipe.unwrap_or_default(),
+              )
+            } else {
+              ast
+                .items
+                .iter()
+                .find_map(|item| match item {
+                  Item::Assignment(assignment) if assignment.name.lexeme() == name => {
+                    Some(assignment.to_string())
+                  }
+                  _ => None,
+                })
+                .or_else(|| justfile?.assignments.get(name).map(ToString::to_string))?
+            }
+          }
+        };
+
+        let value = if contents.starts_with("```") {
+          contents
+        } else {
+          format!("```just\n{contents}\n```")
+        };
+
+        Some(json!({
+          "contents": { "kind": "markdown", "value": value },
+          "range": Self::range(token),
+        }))
+      })
+      .flatten()
+      .unwrap_or(Value::Null)
+  }
+
+  /// Whether `token` is from this document
+  fn contains(&self, token: Token) -> bool {
+    self
+      .path
+      .parent()
+      .map_or(false, |directory| directory.join(token.path) == self.path)
+  }
+
+  fn parameter<'a, 'src>(
+    ast: &'a Ast<'src>,
+    recipe: Option<&str>,
+    name: &str,
+  ) -> Option<&'a Parameter<'src>> {
+    let recipe = recipe?;
+
+    ast.items.iter().find_map(|item| match item {
+      Item::Recipe(unresolved) if unresolved.name.lexeme() == recipe => unresolved
+        .parameters
+        .iter()
+        .find(|parameter| parameter.name.lexeme() == name),
+      _ => None,
+    })
+  }
+
+  /// Find the recipe or variable name at `offset`
+  fn symbol_at<'src>(ast: &Ast<'src>, offset: usize) -> Option<(Token<'src>, Symbol<'src>)> {
+    let mut symbols = Vec::new();
+
+    for item in &ast.items {
+      match item {
+        Item::Alias(alias) => {
+          symbols.push((alias.name.token(), Symbol::Recipe(alias.name.lexeme())));
+          symbols.push((alias.target.token(), Symbol::Recipe(alias.target.lexeme())));
+        }
+        Item::Assignment(assignment) => {
+          symbols.push((
+            assignment.name.token(),
+            Symbol::Variable {
+              name: assignment.name.lexeme(),
+              recipe: None,
+            },
+          ));
+          for variable in assignment.value.variables() {
+            symbols.push((
+              variable,
+              Symbol::Variable {
+                name: variable.lexeme(),
+                recipe: None,
+              },
+            ));
+          }
+        }
+        Item::Recipe(recipe) => {
+          let scope = Some(recipe.name.lexeme());
+
+          let variable = |token: Token<'src>| {
+            (
+              token,
+              Symbol::Variable {
+                name: token.lexeme(),
+                recipe: scope,
+              },
+            )
+          };
+
+          symbols.push((recipe.name.token(), Symbol::Recipe(recipe.name.lexeme())));
+
+          for parameter in &recipe.parameters {
+            symbols.push(variable(parameter.name.token()));
+            if let Some(default) = &parameter.default {
+              symbols.extend(default.variables().map(variable));
+            }
+          }
+
+          for dependency in &recipe.dependencies {
+            symbols.push((
+              dependency.recipe.token(),
+              Symbol::Recipe(dependency.recipe.lexeme()),
+            ));
+            for argument in &dependency.arguments {
+              symbols.extend(argument.variables().map(variable));
+            }
+          }
+
+          for line in &recipe.body {
+            for fragment in &line.fragments {
+              if let Fragment::Interpolation { expression, .. } = fragment {
+                symbols.extend(expression.variables().map(variable));
+              }
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+
+    symbols
+      .into_iter()
+      .find(|(token, _symbol)| token.offset <= offset && offset <= token.offset + token.length)
+  }
+
+  /// Convert a zero-based line and UTF-16 character position to a byte
+  /// offset into `src`
+  fn offset(src: &str, line: usize, character: usize) -> usize {
+    let mut offset = 0;
+
+    for _ in 0..line {
+      match src[offset..].find('\n') {
+        Some(i) => offset += i + 1,
+        None => return src.len(),
+      }
+    }
+
+    let mut units = 0;
+
+    for c in src[offset..].chars() {
+      if c == '\n' || units >= character {
+        break;
+      }
+      units += c.len_utf16();
+      offset += c.len_utf8();
+    }
+
+    offset
+  }
+
+  /// Convert a byte offset into `src` to an LSP position
+  fn position(src: &str, offset: usize) -> Value {
+    let before = &src[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    json!({
+      "line": before.matches('\n').count(),
+      "character": before[line_start..].encode_utf16().count(),
+    })
+  }
+
+  fn range(token: Token) -> Value {
+    json!({
+      "start": Self::position(token.src, token.offset),
+      "end": Self::position(token.src, token.offset + token.length),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn offset() {
+    let src = "foo\nbär baz\n";
+    assert_eq!(Document::offset(src, 0, 0), 0);
+    assert_eq!(Document::offset(src, 0, 2), 2);
+    assert_eq!(Document::offset(src, 0, 10), 3);
+    assert_eq!(Document::offset(src, 1, 2), 7);
+    assert_eq!(Document::offset(src, 1, 4), 9);
+    assert_eq!(Document::offset(src, 5, 0), src.len());
+  }
+
+  #[test]
+  fn position() {
+    let src = "foo\nbär baz\n";
+    assert_eq!(
+      Document::position(src, 9),
+      json!({ "line": 1, "character": 4 })
+    );
+    assert_eq!(
+      Document::position(src, src.len()),
+      json!({ "line": 2, "character": 0 })
+    );
+  }
+
+  #[test]
+  #[cfg(unix)]
+  fn uri_round_trip() {
+    let path = Path::new("/tmp/my project/justfile");
+    let uri = Document::uri(path);
+    assert_eq!(uri, "file:///tmp/my%20project/justfile");
+    assert_eq!(Document::path(&uri).unwrap(), path);
+  }
+}
@@ -21,8 +21,20 @@ impl<'src> Node<'src> for Item<'src> {
       Item::Alias(alias) => alias.tree(),
       Item::Assignment(assignment) => assignment.tree(),
       Item::Comment(comment) => comment.tree(),
+      Item::Function(function) => function.tree(),
+      Item::Import { relative, .. } => Tree::atom("import").push(Tree::string(&relative.cooked)),
+      Item::Module { name, path, .. } => {
+        let mut tree = Tree::atom("mod").push(name.lexeme());
+
+        if let Some(path) = path {
+          tree.push_mut(Tree::string(&path.cooked));
+        }
+
+        tree
+      }
       Item::Recipe(recipe) => recipe.tree(),
       Item::Set(set) => set.tree(),
+      Item::Unexport { name } => Tree::atom(Keyword::Unexport.lexeme()).push(name.lexeme()),
     }
   }
 }
@@ -35,6 +47,21 @@ impl<'src> Node<'src> for Alias<'src, Name<'src>> {
   }
 }
 
+impl<'src> Node<'src> for UserFunction<'src> {
+  fn tree(&self) -> Tree<'src> {
+    Tree::atom(Keyword::Fn.lexeme())
+      .push(self.name.lexeme())
+      .push(Tree::List(
+        self
+          .parameters
+          .iter()
+          .map(|parameter| Tree::atom(parameter.lexeme()))
+          .collect(),
+      ))
+      .push(self.body.tree())
+  }
+}
+
 impl<'src> Node<'src> for Assignment<'src> {
   fn tree(&self) -> Tree<'src> {
     if self.export {
@@ -54,16 +81,18 @@ impl<'src> Node<'src> for Expression<'src> {
     match self {
       Expression::Concatenation { lhs, rhs } => Tree::atom("+").push(lhs.tree()).push(rhs.tree()),
       Expression::Conditional {
-        lhs,
-        rhs,
+        condition,
         then,
         otherwise,
-        operator,
       } => {
         let mut tree = Tree::atom(Keyword::If.lexeme());
-        tree.push_mut(lhs.tree());
-        tree.push_mut(operator.to_string());
-        tree.push_mut(rhs.tree());
+        if let Condition::Comparison { lhs, rhs, operator } = condition {
+          tree.push_mut(lhs.tree());
+          tree.push_mut(operator.to_string());
+          tree.push_mut(rhs.tree());
+        } else {
+          tree.push_mut(condition.tree());
+        }
         tree.push_mut(then.tree());
         tree.push_mut(otherwise.tree());
         tree
@@ -75,7 +104,7 @@ impl<'src> Node<'src> for Expression<'src> {
 
         match thunk {
           Nullary { name, .. } => tree.push_mut(name.lexeme()),
-          Unary { name, arg, .. } => {
+          Unary { name, arg, .. } | UnaryValue { name, arg, .. } => {
             tree.push_mut(name.lexeme());
             tree.push_mut(arg.tree());
           }
@@ -90,6 +119,9 @@ impl<'src> Node<'src> for Expression<'src> {
           }
           Binary {
             name, args: [a, b], ..
+          }
+          | BinaryValue {
+            name, args: [a, b], ..
           } => {
             tree.push_mut(name.lexeme());
             tree.push_mut(a.tree());
@@ -117,6 +149,24 @@ impl<'src> Node<'src> for Expression<'src> {
             tree.push_mut(b.tree());
             tree.push_mut(c.tree());
           }
+          Cached {
+            name,
+            contents,
+            files,
+            ..
+          } => {
+            tree.push_mut(name.lexeme());
+            tree.push_mut(Tree::atom("backtick").push(Tree::string(contents)));
+            for file in files {
+              tree.push_mut(file.tree());
+            }
+          }
+          User { name, args } => {
+            tree.push_mut(name.lexeme());
+            for arg in args {
+              tree.push_mut(arg.tree());
+            }
+          }
         }
 
         tree
@@ -136,6 +186,27 @@ impl<'src> Node<'src> for Expression<'src> {
   }
 }
 
+impl<'src> Node<'src> for Condition<'src> {
+  fn tree(&self) -> Tree<'src> {
+    match self {
+      Condition::And { lhs, rhs } => Tree::atom("&&").push(lhs.tree()).push(rhs.tree()),
+      Condition::Comparison { lhs, rhs, operator } => Tree::atom(operator.to_string())
+        .push(lhs.tree())
+        .push(rhs.tree()),
+      Condition::Group { contents } => Tree::List(vec![contents.tree()]),
+      Condition::In { lhs, list } => {
+        let mut tree = Tree::atom(Keyword::In.lexeme()).push(lhs.tree());
+        for expression in list {
+          tree.push_mut(expression.tree());
+        }
+        tree
+      }
+      Condition::Not { condition } => Tree::atom("!").push(condition.tree()),
+      Condition::Or { lhs, rhs } => Tree::atom("||").push(lhs.tree()).push(rhs.tree()),
+    }
+  }
+}
+
 impl<'src> Node<'src> for UnresolvedRecipe<'src> {
   fn tree(&self) -> Tree<'src> {
     let mut t = Tree::atom("recipe");
@@ -204,6 +275,16 @@ impl<'src> Node<'src> for Parameter<'src> {
   fn tree(&self) -> Tree<'src> {
     let mut children = vec![Tree::atom(self.name.lexeme())];
 
+    if !self.choices.is_empty() {
+      let mut choices = Tree::atom("choices");
+
+      for choice in &self.choices {
+        choices.push_mut(Tree::string(&choice.cooked));
+      }
+
+      children.push(choices);
+    }
+
     if let Some(default) = &self.default {
       children.push(default.tree());
     }
@@ -222,7 +303,14 @@ impl<'src> Node<'src> for Fragment<'src> {
   fn tree(&self) -> Tree<'src> {
     match self {
       Fragment::Text { token } => Tree::string(token.lexeme()),
-      Fragment::Interpolation { expression } => Tree::List(vec![expression.tree()]),
+      Fragment::Interpolation {
+        expression,
+        splat: false,
+      } => Tree::List(vec![expression.tree()]),
+      Fragment::Interpolation {
+        expression,
+        splat: true,
+      } => Tree::List(vec![Tree::atom("*"), expression.tree()]),
     }
   }
 }
@@ -235,11 +323,13 @@ impl<'src> Node<'src> for Set<'src> {
     match &self.value {
       Setting::AllowDuplicateRecipes(value)
       | Setting::DotenvLoad(value)
+      | Setting::DotenvOverride(value)
       | Setting::Export(value)
       | Setting::Fallback(value)
       | Setting::PositionalArguments(value)
       | Setting::WindowsPowerShell(value)
-      | Setting::IgnoreComments(value) => {
+      | Setting::IgnoreComments(value)
+      | Setting::Lazy(value) => {
         set.push_mut(value.to_string());
       }
       Setting::Shell(Shell { command, arguments })
@@ -249,7 +339,15 @@ impl<'src> Node<'src> for Set<'src> {
           set.push_mut(Tree::string(&argument.cooked));
         }
       }
-      Setting::DotenvFilename(value) | Setting::DotenvPath(value) | Setting::Tempdir(value) => {
+      Setting::DotenvFiles(files) => {
+        for file in files {
+          set.push_mut(Tree::string(file));
+        }
+      }
+      Setting::DotenvFilename(value)
+      | Setting::DotenvPath(value)
+      | Setting::Tempdir(value)
+      | Setting::WorkingDirectory(value) => {
         set.push_mut(Tree::string(value));
       }
     }
@@ -2,7 +2,12 @@ use super::*;
 
 /// Run a command and return the data it wrote to stdout as a string
 pub(crate) fn output(mut command: Command) -> Result<String, OutputError> {
-  match command.output() {
+  process_output(command.output())
+}
+
+/// Return the data a process wrote to stdout as a string, if it succeeded
+pub(crate) fn process_output(output: io::Result<process::Output>) -> Result<String, OutputError> {
+  match output {
     Ok(output) => {
       if let Some(code) = output.status.code() {
         if code != 0 {
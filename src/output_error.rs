@@ -6,6 +6,8 @@ pub(crate) enum OutputError {
   Code(i32),
   /// IO error
   Io(io::Error),
+  /// Command line could not be run by the builtin shell
+  Shell(String),
   /// Terminated by signal
   Signal(i32),
   /// Unknown failure
@@ -19,6 +21,7 @@ impl Display for OutputError {
     match *self {
       Self::Code(code) => write!(f, "Process exited with status code {code}"),
       Self::Io(ref io_error) => write!(f, "Error executing process: {io_error}"),
+      Self::Shell(ref message) => write!(f, "Builtin shell error: {message}"),
       Self::Signal(signal) => write!(f, "Process terminated by signal {signal}"),
       Self::Unknown => write!(f, "Process experienced an unknown failure"),
       Self::Utf8(ref err) => write!(f, "Could not convert process stdout to UTF-8: {err}"),
@@ -0,0 +1,5 @@
+#[derive(Debug, PartialEq)]
+pub(crate) enum OutputFormat {
+  JsonEvents,
+  Text,
+}
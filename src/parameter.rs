@@ -3,14 +3,25 @@ use super::*;
 /// A single function parameter
 #[derive(PartialEq, Debug, Clone, Serialize)]
 pub(crate) struct Parameter<'src> {
+  /// Values which arguments are restricted to, if any
+  pub(crate) choices: Vec<StringLiteral<'src>>,
   /// An optional default expression
   pub(crate) default: Option<Expression<'src>>,
   /// Export parameter as environment variable
   pub(crate) export: bool,
   /// The kind of parameter
   pub(crate) kind: ParameterKind,
+  /// Long option name, from an `[arg]` attribute
+  pub(crate) long: Option<String>,
   /// The parameter name
   pub(crate) name: Name<'src>,
+  /// Pattern which arguments must match, from an `[arg]` attribute
+  pub(crate) pattern: Option<Pattern<'src>>,
+  /// Short option name, from an `[arg]` attribute
+  pub(crate) short: Option<char>,
+  /// Value the parameter takes when passed as a flag, from an `[arg]`
+  /// attribute
+  pub(crate) value: Option<String>,
 }
 
 impl<'src> ColorDisplay for Parameter<'src> {
@@ -22,9 +33,112 @@ impl<'src> ColorDisplay for Parameter<'src> {
       write!(f, "$")?;
     }
     write!(f, "{}", color.parameter().paint(self.name.lexeme()))?;
+    if !self.choices.is_empty() {
+      write!(f, ": (")?;
+      for (i, choice) in self.choices.iter().enumerate() {
+        if i > 0 {
+          write!(f, " | ")?;
+        }
+        write!(f, "{}", color.string().paint(&choice.to_string()))?;
+      }
+      write!(f, ")")?;
+    }
     if let Some(ref default) = self.default {
       write!(f, "={}", color.string().paint(&default.to_string()))?;
     }
     Ok(())
   }
 }
+
+impl<'src> Parameter<'src> {
+  /// Whether the parameter is passed as a named option, rather than
+  /// positionally
+  pub(crate) fn is_option(&self) -> bool {
+    self.long.is_some() || self.short.is_some()
+  }
+
+  /// Option names, short first, as they are written on the command line
+  pub(crate) fn option_names(&self) -> Vec<String> {
+    self
+      .short
+      .iter()
+      .map(|short| format!("-{short}"))
+      .chain(self.long.iter().map(|long| format!("--{long}")))
+      .collect()
+  }
+
+  /// Display the parameter as it is passed on the command line, for `--list`
+  /// and usage messages
+  pub(crate) fn usage(&self) -> Usage<'_, 'src> {
+    Usage(self)
+  }
+
+  /// Check that `argument` is one of the allowed choices, and matches the
+  /// parameter's pattern
+  pub(crate) fn check(&self, recipe: &'src str, argument: &str) -> RunResult<'src, ()> {
+    if !self.choices.is_empty() && !self.choices.iter().any(|choice| choice.cooked == argument) {
+      return Err(Error::ArgumentNotChoice {
+        recipe,
+        parameter: self.name.lexeme(),
+        argument: argument.into(),
+        choices: self
+          .choices
+          .iter()
+          .map(|choice| choice.cooked.clone())
+          .collect(),
+      });
+    }
+
+    if let Some(pattern) = &self.pattern {
+      if !pattern.is_match(argument) {
+        return Err(Error::ArgumentPatternMismatch {
+          recipe,
+          parameter: self.name.lexeme(),
+          argument: argument.into(),
+          pattern: pattern.original().into(),
+          token: Box::new(pattern.token()),
+        });
+      }
+    }
+
+    Ok(())
+  }
+}
+
+pub(crate) struct Usage<'a, 'src>(&'a Parameter<'src>);
+
+impl ColorDisplay for Usage<'_, '_> {
+  fn fmt(&self, f: &mut Formatter, color: Color) -> Result<(), fmt::Error> {
+    let parameter = self.0;
+
+    if !parameter.is_option() {
+      return ColorDisplay::fmt(parameter, f, color);
+    }
+
+    let optional = parameter.value.is_some() || parameter.default.is_some();
+
+    if optional {
+      write!(f, "[")?;
+    }
+
+    write!(
+      f,
+      "{}",
+      color.parameter().paint(&parameter.option_names().join("|"))
+    )?;
+
+    if parameter.value.is_none() {
+      if let Some(default) = &parameter.default {
+        write!(f, "={}", color.string().paint(&default.to_string()))?;
+      } else {
+        write!(f, "=<{}>", color.parameter().paint(parameter.name.lexeme()))?;
+      }
+    }
+
+    if optional {
+      write!(f, "]")?;
+    }
+
+    Ok(())
+  }
+}
@@ -289,12 +289,14 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
   fn parse_ast(mut self) -> CompileResult<'src, Ast<'src>> {
     fn pop_doc_comment<'src>(
       items: &mut Vec<Item<'src>>,
+      layout: &mut Vec<Layout<'src>>,
       eol_since_last_comment: bool,
     ) -> Option<&'src str> {
       if !eol_since_last_comment {
         if let Some(Item::Comment(contents)) = items.last() {
           let doc = Some(contents[1..].trim_start());
           items.pop();
+          layout.pop();
           return doc;
         }
       }
@@ -304,6 +306,8 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
     let mut items = Vec::new();
 
+    let mut layout = Vec::new();
+
     let mut eol_since_last_comment = false;
 
     self.accept(ByteOrderMark)?;
@@ -311,6 +315,10 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     loop {
       let next = self.next()?;
 
+      let start = self.next;
+
+      let mut attribute_lines = Vec::new();
+
       if let Some(comment) = self.accept(Comment)? {
         items.push(Item::Comment(comment.lexeme().trim_end()));
         self.expect_eol()?;
@@ -328,6 +336,39 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
             self.presume_keyword(Keyword::Export)?;
             items.push(Item::Assignment(self.parse_assignment(true)?));
           }
+          Some(Keyword::Fn) if self.next_are(&[Identifier, Identifier, ParenL]) => {
+            items.push(Item::Function(self.parse_function()?));
+          }
+          Some(Keyword::Import) if self.next_are(&[Identifier, StringToken]) => {
+            self.presume_keyword(Keyword::Import)?;
+            let (path, relative) = self.parse_string_literal_token()?;
+            self.expect_eol()?;
+            items.push(Item::Import {
+              absolute: None,
+              path,
+              relative,
+            });
+          }
+          Some(Keyword::Mod)
+            if self.next_are(&[Identifier, Identifier, Comment])
+              || self.next_are(&[Identifier, Identifier, Eof])
+              || self.next_are(&[Identifier, Identifier, Eol])
+              || self.next_are(&[Identifier, Identifier, StringToken]) =>
+          {
+            self.presume_keyword(Keyword::Mod)?;
+            let name = self.parse_name()?;
+            let path = if self.next_is(StringToken) {
+              Some(self.parse_string_literal()?)
+            } else {
+              None
+            };
+            self.expect_eol()?;
+            items.push(Item::Module {
+              absolute: None,
+              name,
+              path,
+            });
+          }
           Some(Keyword::Set)
             if self.next_are(&[Identifier, Identifier, ColonEquals])
               || self.next_are(&[Identifier, Identifier, Comment, Eof])
@@ -337,11 +378,21 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
           {
             items.push(Item::Set(self.parse_set()?));
           }
+          Some(Keyword::Unexport)
+            if self.next_are(&[Identifier, Identifier, Comment])
+              || self.next_are(&[Identifier, Identifier, Eof])
+              || self.next_are(&[Identifier, Identifier, Eol]) =>
+          {
+            self.presume_keyword(Keyword::Unexport)?;
+            let name = self.parse_name()?;
+            self.expect_eol()?;
+            items.push(Item::Unexport { name });
+          }
           _ => {
             if self.next_are(&[Identifier, ColonEquals]) {
               items.push(Item::Assignment(self.parse_assignment(false)?));
             } else {
-              let doc = pop_doc_comment(&mut items, eol_since_last_comment);
+              let doc = pop_doc_comment(&mut items, &mut layout, eol_since_last_comment);
               items.push(Item::Recipe(self.parse_recipe(
                 doc,
                 false,
@@ -351,13 +402,15 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
           }
         }
       } else if self.accepted(At)? {
-        let doc = pop_doc_comment(&mut items, eol_since_last_comment);
+        let doc = pop_doc_comment(&mut items, &mut layout, eol_since_last_comment);
         items.push(Item::Recipe(self.parse_recipe(
           doc,
           true,
           BTreeSet::new(),
         )?));
-      } else if let Some(attributes) = self.parse_attributes()? {
+      } else if let Some(lines) = self.parse_attributes()? {
+        attribute_lines = lines.values().copied().collect();
+        let attributes = lines.into_keys().collect::<BTreeSet<Attribute>>();
         let next_keyword = Keyword::from_lexeme(self.next()?.lexeme());
         match next_keyword {
           Some(Keyword::Alias) if self.next_are(&[Identifier, Identifier, ColonEquals]) => {
@@ -365,19 +418,28 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
           }
           _ => {
             let quiet = self.accepted(At)?;
-            let doc = pop_doc_comment(&mut items, eol_since_last_comment);
+            let doc = pop_doc_comment(&mut items, &mut layout, eol_since_last_comment);
             items.push(Item::Recipe(self.parse_recipe(doc, quiet, attributes)?));
           }
         }
       } else {
         return Err(self.unexpected_token()?);
       }
+
+      if items.len() > layout.len() {
+        layout.push(Layout::new(
+          next,
+          &self.tokens[start..self.next],
+          attribute_lines,
+        ));
+      }
     }
 
     if self.next == self.tokens.len() {
       Ok(Ast {
         warnings: Vec::new(),
         items,
+        layout,
       })
     } else {
       Err(self.internal_error(format!(
@@ -390,7 +452,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
   /// Parse an alias, e.g `alias name := target`
   fn parse_alias(
     &mut self,
-    attributes: BTreeSet<Attribute>,
+    attributes: BTreeSet<Attribute<'src>>,
   ) -> CompileResult<'src, Alias<'src, Name<'src>>> {
     self.presume_keyword(Keyword::Alias)?;
     let name = self.parse_name()?;
@@ -417,8 +479,37 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     })
   }
 
-  /// Parse an expression, e.g. `1 + 2`
-  fn parse_expression(&mut self) -> CompileResult<'src, Expression<'src>> {
+  /// Parse a user-defined function, as in `fn name(a, b) := body`
+  fn parse_function(&mut self) -> CompileResult<'src, UserFunction<'src>> {
+    self.presume_keyword(Keyword::Fn)?;
+    let name = self.parse_name()?;
+    self.presume(ParenL)?;
+
+    let mut parameters = Vec::new();
+
+    while !self.next_is(ParenR) {
+      parameters.push(self.parse_name()?);
+
+      if !self.accepted(Comma)? {
+        break;
+      }
+    }
+
+    self.expect(ParenR)?;
+    self.expect(ColonEquals)?;
+    let body = self.parse_expression()?;
+    self.expect_eol()?;
+
+    Ok(UserFunction {
+      body,
+      name,
+      parameters,
+    })
+  }
+
+  /// Increment the recursion depth, returning an error if the maximum
+  /// recursion depth has been reached
+  fn increment_depth(&mut self) -> CompileResult<'src, ()> {
     if self.depth == if cfg!(windows) { 48 } else { 256 } {
       let token = self.next()?;
       return Err(CompileError::new(
@@ -429,6 +520,13 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
     self.depth += 1;
 
+    Ok(())
+  }
+
+  /// Parse an expression, e.g. `1 + 2`
+  fn parse_expression(&mut self) -> CompileResult<'src, Expression<'src>> {
+    self.increment_depth()?;
+
     let expression = if self.accepted_keyword(Keyword::If)? {
       self.parse_conditional()?
     } else if self.accepted(Slash)? {
@@ -458,18 +556,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
   /// Parse a conditional, e.g. `if a == b { "foo" } else { "bar" }`
   fn parse_conditional(&mut self) -> CompileResult<'src, Expression<'src>> {
-    let lhs = self.parse_expression()?;
-
-    let operator = if self.accepted(BangEquals)? {
-      ConditionalOperator::Inequality
-    } else if self.accepted(EqualsTilde)? {
-      ConditionalOperator::RegexMatch
-    } else {
-      self.expect(EqualsEquals)?;
-      ConditionalOperator::Equality
-    };
-
-    let rhs = self.parse_expression()?;
+    let condition = self.parse_condition()?;
 
     self.expect(BraceL)?;
 
@@ -489,14 +576,132 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     };
 
     Ok(Expression::Conditional {
-      lhs: Box::new(lhs),
-      rhs: Box::new(rhs),
+      condition,
       then: Box::new(then),
       otherwise: Box::new(otherwise),
-      operator,
     })
   }
 
+  /// Parse a condition, e.g. `a == b || c != d`
+  fn parse_condition(&mut self) -> CompileResult<'src, Condition<'src>> {
+    let mut condition = self.parse_conjunction()?;
+
+    while self.accepted(BarBar)? {
+      condition = Condition::Or {
+        lhs: Box::new(condition),
+        rhs: Box::new(self.parse_conjunction()?),
+      };
+    }
+
+    Ok(condition)
+  }
+
+  /// Parse a conjunction, e.g. `a == b && c != d`
+  fn parse_conjunction(&mut self) -> CompileResult<'src, Condition<'src>> {
+    let mut condition = self.parse_negation()?;
+
+    while self.accepted(AmpersandAmpersand)? {
+      condition = Condition::And {
+        lhs: Box::new(condition),
+        rhs: Box::new(self.parse_negation()?),
+      };
+    }
+
+    Ok(condition)
+  }
+
+  /// Parse a possibly negated comparison or parenthesized condition, e.g.
+  /// `!(a == b)`
+  fn parse_negation(&mut self) -> CompileResult<'src, Condition<'src>> {
+    self.increment_depth()?;
+
+    let condition = if self.accepted(Bang)? {
+      Condition::Not {
+        condition: Box::new(self.parse_negation()?),
+      }
+    } else if self.next_is_condition_group() {
+      self.presume(ParenL)?;
+      let contents = Box::new(self.parse_condition()?);
+      self.expect(ParenR)?;
+      Condition::Group { contents }
+    } else {
+      self.parse_comparison()?
+    };
+
+    self.depth -= 1;
+
+    Ok(condition)
+  }
+
+  /// Check if the next tokens are a parenthesized condition, e.g. `(a == b)`,
+  /// as opposed to a comparison with a parenthesized left-hand side, e.g.
+  /// `(a + b) == c`
+  fn next_is_condition_group(&mut self) -> bool {
+    if !self.next_is(ParenL) {
+      return false;
+    }
+
+    let mut rest = self.rest();
+
+    let mut depth = 0;
+    for token in rest.by_ref() {
+      match token.kind {
+        ParenL => depth += 1,
+        ParenR if depth == 1 => break,
+        ParenR => depth -= 1,
+        _ => {}
+      }
+    }
+
+    match rest.next() {
+      Some(token) => {
+        !(matches!(
+          token.kind,
+          BangEquals | BangTilde | EqualsEquals | EqualsTilde | Plus | Slash
+        ) || token.kind == Identifier && token.lexeme() == Keyword::In.lexeme())
+      }
+      None => true,
+    }
+  }
+
+  /// Parse a comparison, e.g. `a == b` or `a in ["b", "c"]`
+  fn parse_comparison(&mut self) -> CompileResult<'src, Condition<'src>> {
+    let lhs = Box::new(self.parse_expression()?);
+
+    if self.accepted_keyword(Keyword::In)? {
+      self.expect(BracketL)?;
+
+      let mut list = Vec::new();
+
+      while !self.next_is(BracketR) {
+        list.push(self.parse_expression()?);
+
+        if !self.accepted(Comma)? {
+          break;
+        }
+      }
+
+      self.expect(BracketR)?;
+
+      return Ok(Condition::In { lhs, list });
+    }
+
+    let operator = if self.accepted(BangEquals)? {
+      ConditionalOperator::Inequality
+    } else if self.accepted(BangTilde)? {
+      ConditionalOperator::RegexMismatch
+    } else if self.accepted(EqualsTilde)? {
+      ConditionalOperator::RegexMatch
+    } else {
+      self.expect(EqualsEquals)?;
+      ConditionalOperator::Equality
+    };
+
+    let rhs = Box::new(self.parse_expression()?);
+
+    Ok(Condition::Comparison { lhs, rhs, operator })
+  }
+
   /// Parse a value, e.g. `(bar)`
   fn parse_value(&mut self) -> CompileResult<'src, Expression<'src>> {
     if self.next_is(StringToken) {
@@ -520,6 +725,8 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       }
 
       Ok(Expression::Backtick { contents, token })
+    } else if self.next_are(&[Identifier, ParenL]) && Keyword::Cached == self.next()?.lexeme() {
+      self.parse_cached()
     } else if self.next_is(Identifier) {
       let name = self.parse_name()?;
 
@@ -541,8 +748,36 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     }
   }
 
+  /// Parse a call to `cached`, whose first argument must be a backtick
+  fn parse_cached(&mut self) -> CompileResult<'src, Expression<'src>> {
+    let name = self.parse_name()?;
+
+    let mut arguments = self.parse_sequence()?.into_iter();
+
+    let Some(Expression::Backtick { contents, token }) = arguments.next() else {
+      return Err(name.error(CompileErrorKind::CachedWithoutBacktick));
+    };
+
+    Ok(Expression::Call {
+      thunk: Thunk::Cached {
+        name,
+        contents,
+        token,
+        files: arguments.collect(),
+      },
+    })
+  }
+
   /// Parse a string literal, e.g. `"FOO"`
   fn parse_string_literal(&mut self) -> CompileResult<'src, StringLiteral<'src>> {
+    let (_token, string_literal) = self.parse_string_literal_token()?;
+    Ok(string_literal)
+  }
+
+  /// Parse a string literal, e.g. `"FOO"`, returning the string token as well
+  fn parse_string_literal_token(
+    &mut self,
+  ) -> CompileResult<'src, (Token<'src>, StringLiteral<'src>)> {
     let token = self.expect(StringToken)?;
 
     let kind = StringKind::from_string_or_backtick(token)?;
@@ -587,7 +822,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       unindented
     };
 
-    Ok(StringLiteral { kind, raw, cooked })
+    Ok((token, StringLiteral { cooked, kind, raw }))
   }
 
   /// Parse a name from an identifier token
@@ -619,7 +854,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     &mut self,
     doc: Option<&'src str>,
     quiet: bool,
-    attributes: BTreeSet<Attribute>,
+    attributes: BTreeSet<Attribute<'src>>,
   ) -> CompileResult<'src, UnresolvedRecipe<'src>> {
     let name = self.parse_name()?;
 
@@ -651,6 +886,67 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       None
     };
 
+    let mut parameters = positional
+      .into_iter()
+      .chain(variadic)
+      .collect::<Vec<Parameter>>();
+
+    let mut configured = BTreeSet::new();
+    let mut options = BTreeSet::new();
+
+    for attribute in &attributes {
+      if let Attribute::Arg {
+        long,
+        name: argument,
+        pattern,
+        short,
+        token,
+        value,
+      } = attribute
+      {
+        let parameter = parameters
+          .iter_mut()
+          .find(|parameter| parameter.name.lexeme() == argument.cooked)
+          .ok_or_else(|| {
+            token.error(CompileErrorKind::UndefinedArgAttribute {
+              argument: argument.raw,
+            })
+          })?;
+
+        if !configured.insert(parameter.name.lexeme()) {
+          return Err(token.error(CompileErrorKind::DuplicateArgAttribute {
+            parameter: parameter.name.lexeme(),
+          }));
+        }
+
+        parameter.pattern = pattern
+          .as_ref()
+          .map(|pattern| Pattern::new(**token, pattern.clone()))
+          .transpose()?;
+
+        parameter.long = long.as_ref().map(|long| long.cooked.clone());
+        parameter.short = short.as_ref().and_then(|short| short.cooked.chars().next());
+        parameter.value = value.as_ref().map(|value| value.cooked.clone());
+
+        if parameter.is_option() && parameter.kind.is_variadic() {
+          return Err(token.error(CompileErrorKind::VariadicOption {
+            parameter: parameter.name.lexeme(),
+          }));
+        }
+
+        for option in parameter
+          .long
+          .iter()
+          .map(|long| format!("--{long}"))
+          .chain(parameter.short.iter().map(|short| format!("-{short}")))
+        {
+          if !options.insert(option.clone()) {
+            return Err(token.error(CompileErrorKind::DuplicateOption { option }));
+          }
+        }
+      }
+    }
+
     self.expect(Colon)?;
 
     let mut dependencies = Vec::new();
@@ -680,7 +976,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     let body = self.parse_body()?;
 
     Ok(Recipe {
-      parameters: positional.into_iter().chain(variadic).collect(),
+      parameters,
       private: name.lexeme().starts_with('_'),
       shebang: body.first().map_or(false, Line::is_shebang),
       attributes,
@@ -688,6 +984,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
       body,
       dependencies,
       doc,
+      namepath: name.lexeme().into(),
       name,
       quiet,
     })
@@ -699,6 +996,23 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
     let name = self.parse_name()?;
 
+    let mut choices = Vec::new();
+
+    if self.next_are(&[Colon, ParenL, StringToken]) {
+      self.expect(Colon)?;
+      self.expect(ParenL)?;
+
+      loop {
+        choices.push(self.parse_string_literal()?);
+
+        if !self.accepted(Bar)? {
+          break;
+        }
+      }
+
+      self.expect(ParenR)?;
+    }
+
     let default = if self.accepted(Equals)? {
       Some(self.parse_value()?)
     } else {
@@ -706,10 +1020,15 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     };
 
     Ok(Parameter {
+      choices,
       default,
       export,
       kind,
+      long: None,
       name,
+      pattern: None,
+      short: None,
+      value: None,
     })
   }
 
@@ -730,8 +1049,10 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
             if let Some(token) = self.accept(Text)? {
               fragments.push(Fragment::Text { token });
             } else if self.accepted(InterpolationStart)? {
+              let splat = self.accepted(Asterisk)?;
               fragments.push(Fragment::Interpolation {
                 expression: self.parse_expression()?,
+                splat,
               });
               self.expect(InterpolationEnd)?;
             } else {
@@ -791,9 +1112,11 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
         Some(Setting::AllowDuplicateRecipes(self.parse_set_bool()?))
       }
       Keyword::DotenvLoad => Some(Setting::DotenvLoad(self.parse_set_bool()?)),
+      Keyword::DotenvOverride => Some(Setting::DotenvOverride(self.parse_set_bool()?)),
       Keyword::Export => Some(Setting::Export(self.parse_set_bool()?)),
       Keyword::Fallback => Some(Setting::Fallback(self.parse_set_bool()?)),
       Keyword::IgnoreComments => Some(Setting::IgnoreComments(self.parse_set_bool()?)),
+      Keyword::Lazy => Some(Setting::Lazy(self.parse_set_bool()?)),
       Keyword::PositionalArguments => Some(Setting::PositionalArguments(self.parse_set_bool()?)),
       Keyword::WindowsPowershell => Some(Setting::WindowsPowerShell(self.parse_set_bool()?)),
       _ => None,
@@ -807,10 +1130,14 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
 
     let set_value = match keyword {
       Keyword::DotenvFilename => Some(Setting::DotenvFilename(self.parse_string_literal()?.cooked)),
+      Keyword::DotenvFiles => Some(Setting::DotenvFiles(self.parse_string_list()?)),
       Keyword::DotenvPath => Some(Setting::DotenvPath(self.parse_string_literal()?.cooked)),
       Keyword::Shell => Some(Setting::Shell(self.parse_shell()?)),
       Keyword::Tempdir => Some(Setting::Tempdir(self.parse_string_literal()?.cooked)),
       Keyword::WindowsShell => Some(Setting::WindowsShell(self.parse_shell()?)),
+      Keyword::WorkingDirectory => Some(Setting::WorkingDirectory(
+        self.parse_string_literal()?.cooked,
+      )),
       _ => None,
     };
 
@@ -846,24 +1173,80 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     Ok(Shell { arguments, command })
   }
 
-  /// Parse recipe attributes
-  fn parse_attributes(&mut self) -> CompileResult<'src, Option<BTreeSet<Attribute>>> {
+  /// Parse a list of strings, as in `["a", "b"]`
+  fn parse_string_list(&mut self) -> CompileResult<'src, Vec<String>> {
+    self.expect(BracketL)?;
+
+    let mut strings = Vec::new();
+
+    while !self.next_is(BracketR) {
+      strings.push(self.parse_string_literal()?.cooked);
+
+      if !self.accepted(Comma)? {
+        break;
+      }
+    }
+
+    self.expect(BracketR)?;
+
+    Ok(strings)
+  }
+
+  /// Parse attributes, returning them with the lines they appear on
+  fn parse_attributes(&mut self) -> CompileResult<'src, Option<BTreeMap<Attribute<'src>, usize>>> {
     let mut attributes = BTreeMap::new();
 
     while self.accepted(BracketL)? {
       loop {
         let name = self.parse_name()?;
-        let attribute = Attribute::from_name(name).ok_or_else(|| {
-          name.error(CompileErrorKind::UnknownAttribute {
-            attribute: name.lexeme(),
-          })
-        })?;
-        if let Some(line) = attributes.get(&attribute) {
+
+        let mut arguments = Vec::new();
+        let mut keyword_arguments = BTreeMap::new();
+
+        if self.accepted(ParenL)? {
+          while !self.next_is(ParenR) {
+            if self.next_are(&[Identifier, Equals]) {
+              let keyword = self.parse_name()?;
+              self.expect(Equals)?;
+              let value = self.parse_string_literal()?;
+
+              if keyword_arguments
+                .insert(keyword.lexeme(), (keyword, value))
+                .is_some()
+              {
+                return Err(keyword.error(CompileErrorKind::DuplicateAttributeKeyword {
+                  attribute: name.lexeme(),
+                  keyword: keyword.lexeme(),
+                }));
+              }
+            } else {
+              arguments.push(self.parse_expression()?);
+            }
+
+            if !self.accepted(Comma)? {
+              break;
+            }
+          }
+
+          self.expect(ParenR)?;
+        }
+
+        let attribute = Attribute::new(name, arguments, keyword_arguments)?;
+
+        let discriminant = AttributeDiscriminant::from(&attribute);
+
+        // attributes which are not repeatable are duplicates even if their
+        // arguments differ
+        if let Some((_other, line)) = attributes.iter().find(|(other, _line)| {
+          **other == attribute
+            || (!discriminant.repeatable() && AttributeDiscriminant::from(*other) == discriminant)
+        }) {
           return Err(name.error(CompileErrorKind::DuplicateAttribute {
             attribute: name.lexeme(),
             first: *line,
           }));
         }
+
         attributes.insert(attribute, name.line);
 
         if !self.accepted(Comma)? {
@@ -877,7 +1260,7 @@ impl<'tokens, 'src> Parser<'tokens, 'src> {
     if attributes.is_empty() {
       Ok(None)
     } else {
-      Ok(Some(attributes.into_keys().collect()))
+      Ok(Some(attributes))
     }
   }
 }
@@ -906,7 +1289,7 @@ mod tests {
 
   fn test(text: &str, want: Tree) {
     let unindented = unindent(text);
-    let tokens = Lexer::lex(&unindented).expect("lexing failed");
+    let tokens = Lexer::test_lex(&unindented).expect("lexing failed");
     let justfile = Parser::parse(&tokens).expect("parsing failed");
     let have = justfile.tree();
     if have != want {
@@ -943,7 +1326,7 @@ mod tests {
     length: usize,
     kind: CompileErrorKind,
   ) {
-    let tokens = Lexer::lex(src).expect("Lexing failed in parse test...");
+    let tokens = Lexer::test_lex(src).expect("Lexing failed in parse test...");
 
     match Parser::parse(&tokens) {
       Ok(_) => panic!("Parsing unexpectedly succeeded"),
@@ -952,6 +1335,7 @@ mod tests {
           token: Token {
             kind: have.token.kind,
             src,
+            path: "justfile".as_ref(),
             offset,
             line,
             column,
@@ -1050,6 +1434,36 @@ mod tests {
     tree: (justfile (assignment x "hello")),
   }
 
+  test! {
+    name: function,
+    text: "fn f(a, b) := a + b",
+    tree: (justfile (fn f (a b) (+ a b))),
+  }
+
+  test! {
+    name: function_without_parameters,
+    text: "fn f() := 'a'",
+    tree: (justfile (fn f () "a")),
+  }
+
+  test! {
+    name: function_with_trailing_comma,
+    text: "fn f(a,) := a",
+    tree: (justfile (fn f (a) a)),
+  }
+
+  test! {
+    name: function_call,
+    text: "x := f('a', y)",
+    tree: (justfile (assignment x (call f "a" y))),
+  }
+
+  test! {
+    name: recipe_named_fn,
+    text: "fn:",
+    tree: (justfile (recipe fn)),
+  }
+
   test! {
     name: assignment_equals,
     text: r#"x := "hello""#,
@@ -1158,6 +1572,18 @@ mod tests {
     tree: (justfile (recipe foo (params +(bar)))),
   }
 
+  test! {
+    name: recipe_parameter_choices,
+    text: r#"foo bar: ("a" | 'b'):"#,
+    tree: (justfile (recipe foo (params (bar (choices "a" "b"))))),
+  }
+
+  test! {
+    name: recipe_parameter_choices_default,
+    text: r#"foo bar: ("a" | "b")="a" +baz: ("c"):"#,
+    tree: (justfile (recipe foo (params (bar (choices "a" "b") "a") +(baz (choices "c"))))),
+  }
+
   test! {
     name: recipe_star_variadic,
     text: r"foo *bar:",
@@ -1242,6 +1668,12 @@ mod tests {
     tree: (justfile (recipe foo (body ("bar" ("bob") "biz")))),
   }
 
+  test! {
+    name: recipe_line_splat,
+    text: "foo *args:\n bar {{*args}}",
+    tree: (justfile (recipe foo (params *(args)) (body ("bar " (* args))))),
+  }
+
   test! {
     name: comment,
     text: "# foo",
@@ -1808,6 +2240,30 @@ mod tests {
     tree: (justfile (set export true)),
   }
 
+  test! {
+    name: import,
+    text: "import \"some/file/path.txt\"     \n",
+    tree: (justfile (import "some/file/path.txt")),
+  }
+
+  test! {
+    name: module,
+    text: "mod foo",
+    tree: (justfile (mod foo)),
+  }
+
+  test! {
+    name: module_with_path,
+    text: "mod foo \"some/file/path.txt\"     \n",
+    tree: (justfile (mod foo "some/file/path.txt")),
+  }
+
+  test! {
+    name: mod_recipe,
+    text: "mod foo:",
+    tree: (justfile (recipe mod (params (foo)))),
+  }
+
   test! {
     name: set_export_true,
     text: "set export := true",
@@ -1826,6 +2282,24 @@ mod tests {
     tree: (justfile (set dotenv_load true)),
   }
 
+  test! {
+    name: set_dotenv_files,
+    text: "set dotenv-files := ['.env', \".env.local\",]",
+    tree: (justfile (set dotenv_files ".env" ".env.local")),
+  }
+
+  test! {
+    name: set_dotenv_files_empty,
+    text: "set dotenv-files := []",
+    tree: (justfile (set dotenv_files)),
+  }
+
+  test! {
+    name: set_dotenv_override_implicit,
+    text: "set dotenv-override",
+    tree: (justfile (set dotenv_override true)),
+  }
+
   test! {
     name: set_allow_duplicate_recipes_implicit,
     text: "set allow-duplicate-recipes",
@@ -1862,6 +2336,30 @@ mod tests {
     tree: (justfile (set positional_arguments false)),
   }
 
+  test! {
+    name: unexport,
+    text: "unexport FOO",
+    tree: (justfile (unexport FOO)),
+  }
+
+  test! {
+    name: unexport_with_comment,
+    text: "unexport FOO # bar",
+    tree: (justfile (unexport FOO)),
+  }
+
+  test! {
+    name: unexport_recipe,
+    text: "unexport:",
+    tree: (justfile (recipe unexport)),
+  }
+
+  test! {
+    name: set_working_directory,
+    text: "set working-directory := 'foo'",
+    tree: (justfile (set working_directory "foo")),
+  }
+
   test! {
     name: set_shell_no_arguments,
     text: "set shell := ['tclsh']",
@@ -1958,6 +2456,36 @@ mod tests {
     tree: (justfile (assignment a (if b == c d (if b == c d e)))),
   }
 
+  test! {
+    name: conditional_and_or,
+    text: "a := if b == c || d != e && f == g { h } else { i }",
+    tree: (justfile (assignment a (if (|| (== b c) (&& (!= d e) (== f g))) h i))),
+  }
+
+  test! {
+    name: conditional_not,
+    text: "a := if !b == c { d } else { e }",
+    tree: (justfile (assignment a (if (! (== b c)) d e))),
+  }
+
+  test! {
+    name: conditional_group,
+    text: "a := if (b == c || d == e) && f == g { h } else { i }",
+    tree: (justfile (assignment a (if (&& ((|| (== b c) (== d e))) (== f g)) h i))),
+  }
+
+  test! {
+    name: conditional_group_expression,
+    text: "a := if (b + c) == d { e } else { f }",
+    tree: (justfile (assignment a (if ((+ b c)) == d e f))),
+  }
+
+  test! {
+    name: conditional_in,
+    text: "a := if b in [c, d] { e } else { f }",
+    tree: (justfile (assignment a (if (in b c d) e f))),
+  }
+
   error! {
     name:   alias_syntax_multiple_rhs,
     input:  "alias foo := bar baz",
@@ -2053,6 +2581,32 @@ mod tests {
     },
   }
 
+  error! {
+    name:   function_missing_colon_equals,
+    input:  "fn f(a) a",
+    offset: 8,
+    line:   0,
+    column: 8,
+    width:  1,
+    kind: UnexpectedToken{
+      expected: vec![ColonEquals],
+      found: Identifier,
+    },
+  }
+
+  error! {
+    name:   function_parameter_not_name,
+    input:  "fn f('a') := 'a'",
+    offset: 5,
+    line:   0,
+    column: 5,
+    width:  3,
+    kind: UnexpectedToken{
+      expected: vec![Identifier, ParenR],
+      found: StringToken,
+    },
+  }
+
   error! {
     name:   unclosed_parenthesis_in_expression,
     input:  "x := foo(",
@@ -2249,6 +2803,34 @@ mod tests {
     kind:   UnknownAttribute { attribute: "unknown" },
   }
 
+  error! {
+    name:   attribute_missing_argument,
+    input:  "[group]\nsome_recipe:\n @exit 3",
+    offset: 1,
+    line:   0,
+    column: 1,
+    width:  5,
+    kind:   AttributeArgumentCountMismatch {
+      attribute: "group",
+      found: 0,
+      expected: 1..=1,
+    },
+  }
+
+  error! {
+    name:   attribute_unexpected_argument,
+    input:  "[private('foo')]\nsome_recipe:\n @exit 3",
+    offset: 1,
+    line:   0,
+    column: 1,
+    width:  7,
+    kind:   AttributeArgumentCountMismatch {
+      attribute: "private",
+      found: 1,
+      expected: 0..=0,
+    },
+  }
+
   error! {
     name:   set_unknown,
     input:  "set shall := []",
@@ -2273,36 +2855,6 @@ mod tests {
     },
   }
 
-  error! {
-    name:   unknown_function,
-    input:  "a := foo()",
-    offset: 5,
-    line:   0,
-    column: 5,
-    width:  3,
-    kind:   UnknownFunction{function: "foo"},
-  }
-
-  error! {
-    name:   unknown_function_in_interpolation,
-    input:  "a:\n echo {{bar()}}",
-    offset: 11,
-    line:   1,
-    column: 8,
-    width:  3,
-    kind:   UnknownFunction{function: "bar"},
-  }
-
-  error! {
-    name:   unknown_function_in_default,
-    input:  "a f=baz():",
-    offset: 4,
-    line:   0,
-    column: 4,
-    width:  3,
-    kind:   UnknownFunction{function: "baz"},
-  }
-
   error! {
     name: function_argument_count_nullary,
     input: "x := arch('foo')",
@@ -0,0 +1,60 @@
+use super::*;
+
+/// A regular expression that arguments to a parameter must match in full
+#[derive(Debug, Clone)]
+pub(crate) struct Pattern<'src> {
+  literal: StringLiteral<'src>,
+  regex: Regex,
+  token: Token<'src>,
+}
+
+impl<'src> Pattern<'src> {
+  /// Compile `literal`, from the `[arg]` attribute whose name is `token`
+  pub(crate) fn new(token: Token<'src>, literal: StringLiteral<'src>) -> CompileResult<'src, Self> {
+    let regex = Regex::new(&format!("^(?:{})$", literal.cooked)).map_err(|source| {
+      token.error(CompileErrorKind::ArgumentPattern {
+        pattern: literal.cooked.clone(),
+        source,
+      })
+    })?;
+
+    Ok(Self {
+      literal,
+      regex,
+      token,
+    })
+  }
+
+  pub(crate) fn is_match(&self, argument: &str) -> bool {
+    self.regex.is_match(argument)
+  }
+
+  pub(crate) fn original(&self) -> &str {
+    &self.literal.cooked
+  }
+
+  pub(crate) fn token(&self) -> Token<'src> {
+    self.token
+  }
+}
+
+impl Display for Pattern<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", self.literal)
+  }
+}
+
+impl PartialEq for Pattern<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    self.literal == other.literal
+  }
+}
+
+impl Serialize for Pattern<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_str(self.original())
+  }
+}
@@ -38,6 +38,21 @@ impl PlatformInterface for Platform {
     exit_status.signal()
   }
 
+  fn exit_status(code: i32) -> ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+    ExitStatus::from_raw(code << 8)
+  }
+
+  fn duplicate_stdout() -> io::Result<Stdio> {
+    use std::os::unix::io::AsFd;
+    Ok(io::stdout().as_fd().try_clone_to_owned()?.into())
+  }
+
+  fn duplicate_stderr() -> io::Result<Stdio> {
+    use std::os::unix::io::AsFd;
+    Ok(io::stderr().as_fd().try_clone_to_owned()?.into())
+  }
+
   fn convert_native_path(_working_directory: &Path, path: &Path) -> Result<String, String> {
     path
       .to_str()
@@ -97,6 +112,22 @@ impl PlatformInterface for Platform {
     None
   }
 
+  fn exit_status(code: i32) -> process::ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+    #[allow(clippy::cast_sign_loss)]
+    process::ExitStatus::from_raw(code as u32)
+  }
+
+  fn duplicate_stdout() -> io::Result<Stdio> {
+    use std::os::windows::io::AsHandle;
+    Ok(io::stdout().as_handle().try_clone_to_owned()?.into())
+  }
+
+  fn duplicate_stderr() -> io::Result<Stdio> {
+    use std::os::windows::io::AsHandle;
+    Ok(io::stderr().as_handle().try_clone_to_owned()?.into())
+  }
+
   fn convert_native_path(working_directory: &Path, path: &Path) -> Result<String, String> {
     // Translate path from windows style to unix style
     let mut cygpath = Command::new("cygpath");
@@ -16,6 +16,15 @@ pub(crate) trait PlatformInterface {
   /// signal
   fn signal_from_exit_status(exit_status: ExitStatus) -> Option<i32>;
 
+  /// Construct the exit status of a process which exited with `code`
+  fn exit_status(code: i32) -> ExitStatus;
+
+  /// Duplicate this process's stdout, for use as a child process's stdio
+  fn duplicate_stdout() -> io::Result<Stdio>;
+
+  /// Duplicate this process's stderr, for use as a child process's stdio
+  fn duplicate_stderr() -> io::Result<Stdio>;
+
   /// Translate a path from a "native" path to a path the interpreter expects
   fn convert_native_path(working_directory: &Path, path: &Path) -> Result<String, String>;
 }
@@ -0,0 +1,30 @@
+use super::*;
+
+/// Recipe invocations which have been run, keyed by recipe name and
+/// arguments, along with their outcome. Each invocation has its own
+/// mutex, so that if two dependencies running concurrently share a
+/// dependency, one of them runs it while the other waits for it to finish.
+#[derive(Default)]
+pub(crate) struct Ran(Mutex<BTreeMap<Vec<String>, Output>>);
+
+/// Outcome of an invocation, or `None` if it has not yet run
+type Output = Arc<Mutex<Option<Outcome>>>;
+
+/// Failures are recorded so that an invocation waiting on a shared
+/// dependency which failed does not run it a second time.
+pub(crate) enum Outcome {
+  Failed,
+  Succeeded(String),
+}
+
+impl Ran {
+  pub(crate) fn mutex(&self, invocation: Vec<String>) -> Output {
+    self
+      .0
+      .lock()
+      .unwrap()
+      .entry(invocation)
+      .or_default()
+      .clone()
+  }
+}
@@ -23,6 +23,19 @@ impl Display for DisplayRange<&Range<usize>> {
   }
 }
 
+impl Display for DisplayRange<&RangeInclusive<usize>> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    if self.0.start() == self.0.end() {
+      write!(f, "{}", self.0.start())?;
+    } else if *self.0.end() == usize::MAX {
+      write!(f, "{} or more", self.0.start())?;
+    } else {
+      write!(f, "{} to {}", self.0.start(), self.0.end())?;
+    }
+    Ok(())
+  }
+}
+
 impl<T> RangeExt<T> for Range<T>
 where
   T: PartialOrd,
@@ -78,5 +91,8 @@ mod tests {
     assert_eq!((1..1).display().to_string(), "1");
     assert_eq!((1..2).display().to_string(), "1 to 2");
     assert_eq!((1..usize::MAX).display().to_string(), "1 or more");
+    assert_eq!((1..=1).display().to_string(), "1");
+    assert_eq!((0..=1).display().to_string(), "0 to 1");
+    assert_eq!((1..=usize::MAX).display().to_string(), "1 or more");
   }
 }
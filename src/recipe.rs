@@ -1,6 +1,9 @@
 use {
   super::*,
-  std::process::{ExitStatus, Stdio},
+  std::{
+    process::{ExitStatus, Stdio},
+    time::Instant,
+  },
 };
 
 /// Return a `Error::Signal` if the process was terminated by a signal,
@@ -22,11 +25,12 @@ fn error_from_signal(recipe: &str, line_number: Option<usize>, exit_status: Exit
 /// A recipe, e.g. `foo: bar baz`
 #[derive(PartialEq, Debug, Clone, Serialize)]
 pub(crate) struct Recipe<'src, D = Dependency<'src>> {
-  pub(crate) attributes: BTreeSet<Attribute>,
+  pub(crate) attributes: BTreeSet<Attribute<'src>>,
   pub(crate) body: Vec<Line<'src>>,
   pub(crate) dependencies: Vec<D>,
   pub(crate) doc: Option<&'src str>,
   pub(crate) name: Name<'src>,
+  pub(crate) namepath: String,
   pub(crate) parameters: Vec<Parameter<'src>>,
   pub(crate) priors: usize,
   pub(crate) private: bool,
@@ -41,8 +45,7 @@ impl<'src, D> Recipe<'src, D> {
 
   pub(crate) fn min_arguments(&self) -> usize {
     self
-      .parameters
-      .iter()
+      .positional_parameters()
       .filter(|p| p.default.is_none() && p.kind != ParameterKind::Star)
       .count()
   }
@@ -51,7 +54,88 @@ impl<'src, D> Recipe<'src, D> {
     if self.parameters.iter().any(|p| p.kind.is_variadic()) {
       usize::MAX - 1
     } else {
-      self.parameters.len()
+      self.positional_parameters().count()
+    }
+  }
+
+  /// Parameters which are passed positionally, rather than as options
+  pub(crate) fn positional_parameters(&self) -> impl Iterator<Item = &Parameter<'src>> {
+    self
+      .parameters
+      .iter()
+      .filter(|parameter| !parameter.is_option())
+  }
+
+  /// Parse `argument`, and if needed the argument following it in `rest`, as
+  /// one of the recipe's options. Returns the parameter, its value, and the
+  /// arguments remaining, or `None` if `argument` is not an option.
+  pub(crate) fn parse_option<'run, 'a>(
+    &'run self,
+    argument: &str,
+    rest: &'a [&'a str],
+  ) -> RunResult<'src, Option<(&'run Parameter<'src>, String, &'a [&'a str])>> {
+    if !self.parameters.iter().any(Parameter::is_option) {
+      return Ok(None);
+    }
+
+    let (parameter, inline, option) = if let Some(long) = argument.strip_prefix("--") {
+      if long.is_empty() {
+        return Ok(None);
+      }
+
+      let (long, inline) = match long.split_once('=') {
+        Some((long, value)) => (long, Some(value)),
+        None => (long, None),
+      };
+
+      let parameter = self
+        .parameters
+        .iter()
+        .find(|parameter| parameter.long.as_deref() == Some(long))
+        .ok_or_else(|| Error::UnknownOption {
+          recipe: self.name(),
+          option: argument.into(),
+        })?;
+
+      (parameter, inline, format!("--{long}"))
+    } else {
+      let mut chars = argument.chars();
+
+      let (Some('-'), Some(short), None) = (chars.next(), chars.next(), chars.next()) else {
+        return Ok(None);
+      };
+
+      let Some(parameter) = self
+        .parameters
+        .iter()
+        .find(|parameter| parameter.short == Some(short))
+      else {
+        return Ok(None);
+      };
+
+      (parameter, None, argument.to_owned())
+    };
+
+    if let Some(value) = &parameter.value {
+      if inline.is_some() {
+        return Err(Error::FlagWithValue {
+          recipe: self.name(),
+          option,
+        });
+      }
+      return Ok(Some((parameter, value.clone(), rest)));
+    }
+
+    if let Some(inline) = inline {
+      return Ok(Some((parameter, inline.into(), rest)));
+    }
+
+    match rest.split_first() {
+      Some((value, rest)) => Ok(Some((parameter, (*value).into(), rest))),
+      None => Err(Error::OptionMissingValue {
+        recipe: self.name(),
+        option,
+      }),
     }
   }
 
@@ -63,28 +147,225 @@ impl<'src, D> Recipe<'src, D> {
     self.name.line
   }
 
-  pub(crate) fn confirm(&self) -> RunResult<'src, bool> {
-    if self.attributes.contains(&Attribute::Confirm) {
-      eprint!("Run recipe `{}`? ", self.name);
-      let mut line = String::new();
-      std::io::stdin()
-        .read_line(&mut line)
-        .map_err(|io_error| Error::GetConfirmation { io_error })?;
-      let line = line.trim().to_lowercase();
-      Ok(line == "y" || line == "yes")
-    } else {
-      Ok(true)
+  /// The prompt expression of the recipe's `[confirm]` attribute, if it has
+  /// one
+  pub(crate) fn confirm_prompt(&self) -> Option<&Expression<'src>> {
+    self
+      .attributes
+      .iter()
+      .find_map(|attribute| match attribute {
+        Attribute::Confirm(prompt) => prompt.as_ref(),
+        _ => None,
+      })
+  }
+
+  /// Ask for confirmation to run a recipe with a `[confirm]` attribute. The
+  /// prompt is evaluated with `evaluator`, which should be in the scope of
+  /// the recipe's parameters.
+  pub(crate) fn confirm(&self, evaluator: &mut Evaluator<'src, '_>) -> RunResult<'src, bool> {
+    if !self.attributes.contains(&Attribute::Confirm(None)) {
+      return Ok(true);
+    }
+
+    if !atty::is(atty::Stream::Stdin) {
+      return Err(Error::ConfirmationRequiresTerminal {
+        recipe: self.name(),
+      });
+    }
+
+    let prompt = match self.confirm_prompt() {
+      Some(prompt) => evaluator.evaluate_expression(prompt)?,
+      None => format!("Run recipe `{}`?", self.name),
+    };
+
+    eprint!("{prompt} ");
+    let mut line = String::new();
+    std::io::stdin()
+      .read_line(&mut line)
+      .map_err(|io_error| Error::GetConfirmation { io_error })?;
+    let line = line.trim().to_lowercase();
+    Ok(line == "y" || line == "yes")
+  }
+
+  /// Check command line arguments and option values against parameter
+  /// choices and patterns, so that invalid arguments are rejected before any
+  /// recipe runs
+  pub(crate) fn check_arguments(
+    &self,
+    arguments: &[&str],
+    options: &BTreeMap<&str, String>,
+  ) -> RunResult<'src, ()> {
+    let mut rest = arguments;
+
+    for parameter in self.positional_parameters() {
+      if parameter.kind.is_variadic() {
+        for argument in rest {
+          parameter.check(self.name(), argument)?;
+        }
+        break;
+      }
+
+      let Some((argument, tail)) = rest.split_first() else {
+        break;
+      };
+
+      parameter.check(self.name(), argument)?;
+
+      rest = tail;
     }
+
+    for parameter in &self.parameters {
+      if let Some(value) = options.get(parameter.name.lexeme()) {
+        parameter.check(self.name(), value)?;
+      }
+    }
+
+    Ok(())
   }
 
   pub(crate) fn public(&self) -> bool {
     !self.private && !self.attributes.contains(&Attribute::Private)
   }
 
+  pub(crate) fn groups(&self) -> BTreeSet<String> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| {
+        if let Attribute::Group(group) = attribute {
+          Some(group.cooked.clone())
+        } else {
+          None
+        }
+      })
+      .collect()
+  }
+
+  /// Globs of files which the recipe's outputs are generated from, from
+  /// `[sources]` attributes
+  pub(crate) fn sources(&self) -> Vec<&str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| {
+        if let Attribute::Sources(globs) = attribute {
+          Some(globs.iter().map(|glob| glob.cooked.as_str()))
+        } else {
+          None
+        }
+      })
+      .flatten()
+      .collect()
+  }
+
+  /// Globs of files which the recipe generates, from `[outputs]` attributes
+  pub(crate) fn outputs(&self) -> Vec<&str> {
+    self
+      .attributes
+      .iter()
+      .filter_map(|attribute| {
+        if let Attribute::Outputs(globs) = attribute {
+          Some(globs.iter().map(|glob| glob.cooked.as_str()))
+        } else {
+          None
+        }
+      })
+      .flatten()
+      .collect()
+  }
+
+  /// Environment variables set for the recipe's commands by `[env]`
+  /// attributes
+  pub(crate) fn environment(&self) -> impl Iterator<Item = (&str, &str)> {
+    self.attributes.iter().filter_map(|attribute| {
+      if let Attribute::Env(key, value) = attribute {
+        Some((key.cooked.as_str(), value.cooked.as_str()))
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Interpreter from the `[script]` attribute, which runs the recipe body as
+  /// a script without a shebang line
+  pub(crate) fn script(&self) -> Option<&Shell<'src>> {
+    self.attributes.iter().find_map(|attribute| {
+      if let Attribute::Script(script) = attribute {
+        Some(script)
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Whether the recipe body is run as a single script, either because it
+  /// starts with a shebang line or has a `[script]` attribute
+  pub(crate) fn is_script(&self) -> bool {
+    self.shebang || self.script().is_some()
+  }
+
+  /// Script file extension from the `[extension]` attribute
+  fn extension(&self) -> Option<&str> {
+    self.attributes.iter().find_map(|attribute| {
+      if let Attribute::Extension(extension) = attribute {
+        Some(extension.cooked.as_str())
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Shell from the `[shell]` attribute, which takes precedence over
+  /// `set shell`, `set windows-shell`, and, since the recipe's lines are
+  /// written for it, `--shell`
+  pub(crate) fn shell(&self) -> Option<&Shell<'src>> {
+    self.attributes.iter().find_map(|attribute| {
+      if let Attribute::Shell(shell) = attribute {
+        Some(shell)
+      } else {
+        None
+      }
+    })
+  }
+
   pub(crate) fn change_directory(&self) -> bool {
     !self.attributes.contains(&Attribute::NoCd)
   }
 
+  fn working_directory(&self, context: &RecipeContext) -> Option<PathBuf> {
+    if !self.change_directory() {
+      return None;
+    }
+
+    let setting = context.settings.working_directory.as_ref();
+
+    let attribute = self.attributes.iter().find_map(|attribute| {
+      if let Attribute::WorkingDirectory(path) = attribute {
+        Some(&path.cooked)
+      } else {
+        None
+      }
+    });
+
+    if setting.is_none() && attribute.is_none() {
+      return Some(context.search.working_directory.clone());
+    }
+
+    // the setting and attribute are relative to the directory containing the
+    // justfile or module, even if `--working-directory` was passed
+    let mut directory = context
+      .search
+      .justfile
+      .parent()
+      .unwrap_or(&context.search.working_directory)
+      .to_owned();
+
+    directory.extend(setting);
+    directory.extend(attribute);
+
+    Some(directory)
+  }
+
   pub(crate) fn enabled(&self) -> bool {
     let windows = self.attributes.contains(&Attribute::Windows);
     let linux = self.attributes.contains(&Attribute::Linux);
@@ -99,10 +380,47 @@ impl<'src, D> Recipe<'src, D> {
       || (cfg!(unix) && unix)
   }
 
+  /// Whether the recipe's stdout is captured, so that its output can be used
+  /// by recipes that depend on it
+  pub(crate) fn capture(&self) -> bool {
+    self.attributes.contains(&Attribute::Capture)
+  }
+
+  pub(crate) fn parallel(&self) -> bool {
+    self.attributes.contains(&Attribute::Parallel)
+  }
+
   fn print_exit_message(&self) -> bool {
     !self.attributes.contains(&Attribute::NoExitMessage)
   }
 
+  /// Run `command` to completion, appending its stdout to `captured` if the
+  /// recipe has the `[capture]` attribute. Unlike `Command::output`, standard
+  /// input is inherited, as it is for recipes which are not captured.
+  fn status(
+    &self,
+    command: &mut Command,
+    config: &Config,
+    captured: &mut Vec<u8>,
+  ) -> io::Result<ExitStatus> {
+    if !self.capture() {
+      return InterruptHandler::guard(|| command.status());
+    }
+
+    if !config.verbosity.quiet() {
+      command.stderr(Stdio::inherit());
+    }
+
+    command.stdin(Stdio::inherit());
+    command.stdout(Stdio::piped());
+
+    let output = InterruptHandler::guard(|| command.output())?;
+
+    captured.extend(output.stdout);
+
+    Ok(output.status)
+  }
+
   pub(crate) fn run<'run>(
     &self,
     context: &RecipeContext<'src, 'run>,
@@ -110,7 +428,7 @@ impl<'src, D> Recipe<'src, D> {
     scope: Scope<'src, 'run>,
     search: &'run Search,
     positional: &[String],
-  ) -> RunResult<'src, ()> {
+  ) -> RunResult<'src, String> {
     let config = &context.config;
 
     if config.verbosity.loquacious() {
@@ -123,14 +441,58 @@ impl<'src, D> Recipe<'src, D> {
       );
     }
 
-    let evaluator =
-      Evaluator::recipe_evaluator(context.config, dotenv, &scope, context.settings, search);
+    if config.dry_run {
+      if let Some(working_directory) = self.working_directory(context) {
+        if working_directory != context.search.working_directory {
+          let color = if config.highlight {
+            config.color.command(config.command_color)
+          } else {
+            config.color
+          };
+          eprintln!(
+            "{}",
+            color
+              .stderr()
+              .paint(&format!("cd {}", working_directory.display()))
+          );
+        }
+      }
+    }
+
+    Event::RecipeStart {
+      recipe: &self.namepath,
+      arguments: positional,
+    }
+    .emit(context.events);
+
+    let start = Instant::now();
 
-    if self.shebang {
-      self.run_shebang(context, dotenv, &scope, positional, config, evaluator)
+    let evaluator = Evaluator::recipe_evaluator(context, dotenv, &scope, search);
+
+    let result = if self.is_script() {
+      self.run_script(context, dotenv, &scope, positional, config, evaluator)
     } else {
       self.run_linewise(context, dotenv, &scope, positional, config, evaluator)
+    };
+
+    context.timings.recipe(&self.namepath, start);
+
+    let (exit_code, signal) = match &result {
+      Ok(_) => (Some(0), None),
+      Err(error) => Event::status(error),
+    };
+
+    Event::RecipeFinish {
+      recipe: &self.namepath,
+      arguments: positional,
+      success: result.is_ok(),
+      exit_code,
+      signal,
+      duration_ms: start.elapsed(),
     }
+    .emit(context.events);
+
+    result
   }
 
   fn run_linewise<'run>(
@@ -141,15 +503,21 @@ impl<'src, D> Recipe<'src, D> {
     positional: &[String],
     config: &Config,
     mut evaluator: Evaluator<'src, 'run>,
-  ) -> RunResult<'src, ()> {
+  ) -> RunResult<'src, String> {
+    let shell = self.shell().map_or_else(
+      || context.settings.shell(config).0,
+      |shell| shell.command.cooked.as_str(),
+    );
     let mut lines = self.body.iter().peekable();
     let mut line_number = self.line_number() + 1;
+    let mut captured = Vec::new();
     loop {
       if lines.peek().is_none() {
-        return Ok(());
+        return Ok(String::from_utf8_lossy(&captured).into_owned());
       }
       let mut evaluated = String::new();
       let mut continued = false;
+      let command_line_number = line_number + 1;
       let quiet_command = lines.peek().map_or(false, |line| line.is_quiet());
       let infallible_command = lines.peek().map_or(false, |line| line.is_infallible());
 
@@ -163,7 +531,7 @@ impl<'src, D> Recipe<'src, D> {
         let line = lines.next().unwrap();
         line_number += 1;
         if !comment_line {
-          evaluated += &evaluator.evaluate_line(line, continued)?;
+          evaluated += &evaluator.evaluate_line(line, continued, Some(shell))?;
         }
         if line.is_continuation() && !comment_line {
           continued = true;
@@ -191,9 +559,17 @@ impl<'src, D> Recipe<'src, D> {
         continue;
       }
 
-      if config.dry_run
-        || config.verbosity.loquacious()
-        || !((quiet_command ^ self.quiet) || config.verbosity.quiet())
+      Event::CommandStart {
+        recipe: &self.namepath,
+        line_number: Some(command_line_number),
+        command,
+      }
+      .emit(context.events);
+
+      if config.output_format == OutputFormat::Text
+        && (config.dry_run
+          || config.verbosity.loquacious()
+          || !((quiet_command ^ self.quiet) || config.verbosity.quiet()))
       {
         let color = if config.highlight {
           config.color.command(config.command_color)
@@ -207,27 +583,86 @@ impl<'src, D> Recipe<'src, D> {
         continue;
       }
 
-      let mut cmd = context.settings.shell_command(config);
+      let working_directory = self.working_directory(context);
 
-      if self.change_directory() {
-        cmd.current_dir(&context.search.working_directory);
-      }
+      let configure = |cmd: &mut Command| {
+        if let Some(working_directory) = &working_directory {
+          cmd.current_dir(working_directory);
+        }
 
-      cmd.arg(command);
+        cmd.export(context.settings, dotenv, scope, context.unexports);
 
-      if context.settings.positional_arguments {
-        cmd.arg(self.name.lexeme());
-        cmd.args(positional);
-      }
+        cmd.envs(self.environment());
+      };
 
-      if config.verbosity.quiet() {
-        cmd.stderr(Stdio::null());
-        cmd.stdout(Stdio::null());
-      }
+      let shell = self.shell();
+
+      let builtin = shell.map_or_else(|| context.settings.builtin_shell(config), Shell::is_builtin);
+
+      let start = Instant::now();
+
+      let status = if builtin {
+        let quiet = config.verbosity.quiet();
+
+        let positional = context.settings.positional_arguments.then(|| {
+          iter::once(self.name().to_owned())
+            .chain(positional.iter().cloned())
+            .collect::<Vec<String>>()
+        });
+
+        Ok(
+          InterruptHandler::guard(|| {
+            if self.capture() {
+              BuiltinShell::output(command, quiet, positional.as_deref(), configure).map(|output| {
+                captured.extend(output.stdout);
+                output.status
+              })
+            } else {
+              BuiltinShell::run(command, quiet, positional.as_deref(), configure)
+            }
+          })
+          .map_err(|message| Error::BuiltinShell {
+            recipe: self.name(),
+            line_number: Some(line_number),
+            message,
+          })?,
+        )
+      } else {
+        let mut cmd = shell.map_or_else(|| context.settings.shell_command(config), Shell::command);
+
+        configure(&mut cmd);
+
+        cmd.arg(command);
+
+        if context.settings.positional_arguments {
+          cmd.arg(self.name.lexeme());
+          cmd.args(positional);
+        }
+
+        if config.verbosity.quiet() {
+          cmd.stderr(Stdio::null());
+          cmd.stdout(Stdio::null());
+        }
 
-      cmd.export(context.settings, dotenv, scope);
+        self.status(&mut cmd, config, &mut captured)
+      };
 
-      match InterruptHandler::guard(|| cmd.status()) {
+      context
+        .timings
+        .command(&self.namepath, Some(command_line_number), command, start);
+
+      if let Ok(exit_status) = &status {
+        Event::CommandFinish {
+          recipe: &self.namepath,
+          line_number: Some(command_line_number),
+          exit_code: exit_status.code(),
+          signal: Platform::signal_from_exit_status(*exit_status),
+          duration_ms: start.elapsed(),
+        }
+        .emit(context.events);
+      }
+
+      match status {
         Ok(exit_status) => {
           if let Some(code) = exit_status.code() {
             if code != 0 && !infallible_command {
@@ -256,7 +691,7 @@ impl<'src, D> Recipe<'src, D> {
     }
   }
 
-  pub(crate) fn run_shebang<'run>(
+  pub(crate) fn run_script<'run>(
     &self,
     context: &RecipeContext<'src, 'run>,
     dotenv: &BTreeMap<String, String>,
@@ -264,29 +699,52 @@ impl<'src, D> Recipe<'src, D> {
     positional: &[String],
     config: &Config,
     mut evaluator: Evaluator<'src, 'run>,
-  ) -> RunResult<'src, ()> {
+  ) -> RunResult<'src, String> {
     let mut evaluated_lines = vec![];
+    let mut program = self.script().map(|script| script.command.cooked.clone());
     for line in &self.body {
-      evaluated_lines.push(evaluator.evaluate_line(line, false)?);
+      let evaluated = evaluator.evaluate_line(line, false, program.as_deref())?;
+      if program.is_none() {
+        program = Shebang::new(&evaluated).map(|shebang| shebang.program().to_owned());
+      }
+      evaluated_lines.push(evaluated);
     }
 
-    if config.verbosity.loud() && (config.dry_run || self.quiet) {
+    let script = evaluated_lines.join("\n");
+
+    Event::CommandStart {
+      recipe: &self.namepath,
+      line_number: None,
+      command: &script,
+    }
+    .emit(context.events);
+
+    if config.output_format == OutputFormat::Text
+      && config.verbosity.loud()
+      && (config.dry_run || self.quiet)
+    {
       for line in &evaluated_lines {
         eprintln!("{line}");
       }
     }
 
     if config.dry_run {
-      return Ok(());
+      return Ok(String::new());
     }
 
-    let shebang_line = evaluated_lines.first().ok_or_else(|| Error::Internal {
-      message: "evaluated_lines was empty".to_owned(),
-    })?;
+    let (shebang, body) = if self.script().is_some() {
+      (None, evaluated_lines.as_slice())
+    } else {
+      let shebang_line = evaluated_lines.first().ok_or_else(|| Error::Internal {
+        message: "evaluated_lines was empty".to_owned(),
+      })?;
 
-    let shebang = Shebang::new(shebang_line).ok_or_else(|| Error::Internal {
-      message: format!("bad shebang line: {shebang_line}"),
-    })?;
+      let shebang = Shebang::new(shebang_line).ok_or_else(|| Error::Internal {
+        message: format!("bad shebang line: {shebang_line}"),
+      })?;
+
+      (Some(shebang), &evaluated_lines[1..])
+    };
 
     let mut tempdir_builder = tempfile::Builder::new();
     tempdir_builder.prefix("just");
@@ -299,7 +757,12 @@ impl<'src, D> Recipe<'src, D> {
       io_error: error,
     })?;
     let mut path = tempdir.path().to_path_buf();
-    path.push(shebang.script_filename(self.name()));
+    path.push(match (self.extension(), shebang) {
+      (Some(extension), _) if extension.starts_with('.') => format!("{}{extension}", self.name()),
+      (Some(extension), _) => format!("{}.{extension}", self.name()),
+      (None, Some(shebang)) => shebang.script_filename(self.name()),
+      (None, None) => self.name().to_owned(),
+    });
 
     {
       let mut f = fs::File::create(&path).map_err(|error| Error::TmpdirIo {
@@ -308,19 +771,22 @@ impl<'src, D> Recipe<'src, D> {
       })?;
       let mut text = String::new();
 
-      if shebang.include_shebang_line() {
-        text += &evaluated_lines[0];
-      } else {
+      if let Some(shebang) = shebang {
+        if shebang.include_shebang_line() {
+          text += &evaluated_lines[0];
+        } else {
+          text += "\n";
+        }
+
         text += "\n";
       }
 
-      text += "\n";
       // add blank lines so that lines in the generated script have the same line
       // number as the corresponding lines in the justfile
-      for _ in 1..(self.line_number() + 2) {
+      for _ in 0..=self.line_number() {
         text += "\n";
       }
-      for line in &evaluated_lines[1..] {
+      for line in body {
         text += line;
         text += "\n";
       }
@@ -336,40 +802,67 @@ impl<'src, D> Recipe<'src, D> {
         })?;
     }
 
-    // make the script executable
-    Platform::set_execute_permission(&path).map_err(|error| Error::TmpdirIo {
-      recipe: self.name(),
-      io_error: error,
-    })?;
-
     // create a command to run the script
-    let mut command = Platform::make_shebang_command(
-      &path,
-      if self.change_directory() {
-        Some(&context.search.working_directory)
-      } else {
-        None
-      },
-      shebang,
-    )
-    .map_err(|output_error| Error::Cygpath {
-      recipe: self.name(),
-      output_error,
-    })?;
+    let mut command = if let Some(shebang) = shebang {
+      // make the script executable
+      Platform::set_execute_permission(&path).map_err(|error| Error::TmpdirIo {
+        recipe: self.name(),
+        io_error: error,
+      })?;
+
+      Platform::make_shebang_command(&path, self.working_directory(context).as_deref(), shebang)
+        .map_err(|output_error| Error::Cygpath {
+          recipe: self.name(),
+          output_error,
+        })?
+    } else {
+      let mut command = self.script().unwrap().command();
+
+      command.arg(&path);
+
+      if let Some(working_directory) = self.working_directory(context) {
+        command.current_dir(working_directory);
+      }
+
+      command
+    };
 
     if context.settings.positional_arguments {
       command.args(positional);
     }
 
-    command.export(context.settings, dotenv, scope);
+    command.export(context.settings, dotenv, scope, context.unexports);
+
+    command.envs(self.environment());
+
+    let start = Instant::now();
+
+    let mut captured = Vec::new();
 
     // run it!
-    match InterruptHandler::guard(|| command.status()) {
+    let status = self.status(&mut command, config, &mut captured);
+
+    context
+      .timings
+      .command(&self.namepath, None, &script, start);
+
+    if let Ok(exit_status) = &status {
+      Event::CommandFinish {
+        recipe: &self.namepath,
+        line_number: None,
+        exit_code: exit_status.code(),
+        signal: Platform::signal_from_exit_status(*exit_status),
+        duration_ms: start.elapsed(),
+      }
+      .emit(context.events);
+    }
+
+    match status {
       Ok(exit_status) => exit_status.code().map_or_else(
         || Err(error_from_signal(self.name(), None, exit_status)),
         |code| {
           if code == 0 {
-            Ok(())
+            Ok(String::from_utf8_lossy(&captured).into_owned())
           } else {
             Err(Error::Code {
               recipe: self.name(),
@@ -380,16 +873,36 @@ impl<'src, D> Recipe<'src, D> {
           }
         },
       ),
-      Err(io_error) => Err(Error::Shebang {
-        recipe: self.name(),
-        command: shebang.interpreter.to_owned(),
-        argument: shebang.argument.map(String::from),
-        io_error,
+      Err(io_error) => Err(match shebang {
+        Some(shebang) => Error::Shebang {
+          recipe: self.name(),
+          command: shebang.interpreter.to_owned(),
+          argument: shebang.argument.map(String::from),
+          io_error,
+        },
+        None => Error::Script {
+          recipe: self.name(),
+          command: self.script().unwrap().command.cooked.clone(),
+          io_error,
+        },
       }),
     }
   }
 }
 
+impl<'src> Recipe<'src> {
+  /// Dependencies run before the recipe whose output is captured, which are
+  /// bound by name in the recipe's body and subsequent dependency arguments
+  pub(crate) fn captured(&self) -> impl Iterator<Item = &Recipe<'src>> {
+    self
+      .dependencies
+      .iter()
+      .take(self.priors)
+      .map(|dependency| dependency.recipe.as_ref())
+      .filter(|recipe| recipe.capture())
+  }
+}
+
 impl<'src, D: Display> ColorDisplay for Recipe<'src, D> {
   fn fmt(&self, f: &mut Formatter, color: Color) -> Result<(), fmt::Error> {
     if let Some(doc) = self.doc {
@@ -397,7 +910,7 @@ impl<'src, D: Display> ColorDisplay for Recipe<'src, D> {
     }
 
     for attribute in &self.attributes {
-      writeln!(f, "[{}]", attribute.to_str())?;
+      writeln!(f, "[{attribute}]")?;
     }
 
     if self.quiet {
@@ -429,7 +942,14 @@ impl<'src, D: Display> ColorDisplay for Recipe<'src, D> {
         }
         match fragment {
           Fragment::Text { token } => write!(f, "{}", token.lexeme())?,
-          Fragment::Interpolation { expression, .. } => write!(f, "{{{{ {expression} }}}}")?,
+          Fragment::Interpolation {
+            expression,
+            splat: false,
+          } => write!(f, "{{{{ {expression} }}}}")?,
+          Fragment::Interpolation {
+            expression,
+            splat: true,
+          } => write!(f, "{{{{ *{expression} }}}}")?,
         }
       }
       if i + 1 < self.body.len() {
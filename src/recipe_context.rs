@@ -2,7 +2,12 @@ use super::*;
 
 pub(crate) struct RecipeContext<'src: 'run, 'run> {
   pub(crate) config: &'run Config,
+  pub(crate) events: &'run EventLog,
+  pub(crate) functions: &'run Table<'src, UserFunction<'src>>,
+  pub(crate) jobs: &'run Semaphore,
   pub(crate) scope: Scope<'src, 'run>,
   pub(crate) search: &'run Search,
   pub(crate) settings: &'run Settings<'src>,
+  pub(crate) timings: &'run Timings,
+  pub(crate) unexports: &'run BTreeSet<String>,
 }
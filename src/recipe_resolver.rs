@@ -2,7 +2,7 @@ use {super::*, CompileErrorKind::*};
 
 pub(crate) struct RecipeResolver<'src: 'run, 'run> {
   unresolved_recipes: Table<'src, UnresolvedRecipe<'src>>,
-  resolved_recipes: Table<'src, Rc<Recipe<'src>>>,
+  resolved_recipes: Table<'src, Arc<Recipe<'src>>>,
   assignments: &'run Table<'src, Assignment<'src>>,
 }
 
@@ -10,7 +10,7 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
   pub(crate) fn resolve_recipes(
     unresolved_recipes: Table<'src, UnresolvedRecipe<'src>>,
     assignments: &Table<'src, Assignment<'src>>,
-  ) -> CompileResult<'src, Table<'src, Rc<Recipe<'src>>>> {
+  ) -> CompileResult<'src, Table<'src, Arc<Recipe<'src>>>> {
     let mut resolver = RecipeResolver {
       resolved_recipes: Table::new(),
       unresolved_recipes,
@@ -25,24 +25,41 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
       for parameter in &recipe.parameters {
         if let Some(expression) = &parameter.default {
           for variable in expression.variables() {
-            resolver.resolve_variable(&variable, &[])?;
+            resolver.resolve_variable(&variable, &[], &[])?;
           }
         }
       }
 
-      for dependency in &recipe.dependencies {
+      let captured = recipe.captured().map(Recipe::name).collect::<Vec<&str>>();
+
+      for (i, dependency) in recipe.dependencies.iter().enumerate() {
+        // captured output is only available to subsequent dependencies
+        let captured = if i < recipe.priors {
+          &[]
+        } else {
+          captured.as_slice()
+        };
+
         for argument in &dependency.arguments {
           for variable in argument.variables() {
-            resolver.resolve_variable(&variable, &recipe.parameters)?;
+            resolver.resolve_variable(&variable, &recipe.parameters, captured)?;
           }
         }
       }
 
+      // the prompt is evaluated before dependencies run, so captured output
+      // is not available to it
+      if let Some(prompt) = recipe.confirm_prompt() {
+        for variable in prompt.variables() {
+          resolver.resolve_variable(&variable, &recipe.parameters, &[])?;
+        }
+      }
+
       for line in &recipe.body {
         for fragment in &line.fragments {
           if let Fragment::Interpolation { expression, .. } = fragment {
             for variable in expression.variables() {
-              resolver.resolve_variable(&variable, &recipe.parameters)?;
+              resolver.resolve_variable(&variable, &recipe.parameters, &captured)?;
             }
           }
         }
@@ -56,10 +73,12 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
     &self,
     variable: &Token<'src>,
     parameters: &[Parameter],
+    captured: &[&str],
   ) -> CompileResult<'src, ()> {
     let name = variable.lexeme();
-    let undefined =
-      !self.assignments.contains_key(name) && !parameters.iter().any(|p| p.name.lexeme() == name);
+    let undefined = !self.assignments.contains_key(name)
+      && !parameters.iter().any(|p| p.name.lexeme() == name)
+      && !captured.contains(&name);
 
     if undefined {
       return Err(variable.error(UndefinedVariable { variable: name }));
@@ -72,20 +91,20 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
     &mut self,
     stack: &mut Vec<&'src str>,
     recipe: UnresolvedRecipe<'src>,
-  ) -> CompileResult<'src, Rc<Recipe<'src>>> {
+  ) -> CompileResult<'src, Arc<Recipe<'src>>> {
     if let Some(resolved) = self.resolved_recipes.get(recipe.name()) {
-      return Ok(Rc::clone(resolved));
+      return Ok(Arc::clone(resolved));
     }
 
     stack.push(recipe.name());
 
-    let mut dependencies: Vec<Rc<Recipe>> = Vec::new();
+    let mut dependencies: Vec<Arc<Recipe>> = Vec::new();
     for dependency in &recipe.dependencies {
       let name = dependency.recipe.lexeme();
 
       if let Some(resolved) = self.resolved_recipes.get(name) {
         // dependency already resolved
-        dependencies.push(Rc::clone(resolved));
+        dependencies.push(Arc::clone(resolved));
       } else if stack.contains(&name) {
         let first = stack[0];
         stack.push(first);
@@ -113,8 +132,8 @@ impl<'src: 'run, 'run> RecipeResolver<'src, 'run> {
 
     stack.pop();
 
-    let resolved = Rc::new(recipe.resolve(dependencies)?);
-    self.resolved_recipes.insert(Rc::clone(&resolved));
+    let resolved = Arc::new(recipe.resolve(dependencies)?);
+    self.resolved_recipes.insert(Arc::clone(&resolved));
     Ok(resolved)
   }
 }
@@ -192,4 +211,14 @@ mod tests {
     width:  3,
     kind:   UndefinedVariable{variable: "baz"},
   }
+
+  analysis_error! {
+    name:   unknown_variable_in_confirm_prompt,
+    input:  "[confirm('Deploy ' + env + '?')]\nfoo:",
+    offset: 21,
+    line:   0,
+    column: 21,
+    width:  3,
+    kind:   UndefinedVariable{variable: "env"},
+  }
 }
@@ -3,7 +3,7 @@ use super::*;
 #[derive(Debug)]
 pub(crate) struct Scope<'src: 'run, 'run> {
   parent: Option<&'run Scope<'src, 'run>>,
-  bindings: Table<'src, Binding<'src, String>>,
+  bindings: Table<'src, Binding<'src, Value>>,
 }
 
 impl<'src, 'run> Scope<'src, 'run> {
@@ -21,11 +21,11 @@ impl<'src, 'run> Scope<'src, 'run> {
     }
   }
 
-  pub(crate) fn bind(&mut self, export: bool, name: Name<'src>, value: String) {
+  pub(crate) fn bind(&mut self, export: bool, name: Name<'src>, value: impl Into<Value>) {
     self.bindings.insert(Binding {
       export,
       name,
-      value,
+      value: value.into(),
     });
   }
 
@@ -33,22 +33,18 @@ impl<'src, 'run> Scope<'src, 'run> {
     self.bindings.contains_key(name)
   }
 
-  pub(crate) fn value(&self, name: &str) -> Option<&str> {
+  pub(crate) fn value(&self, name: &str) -> Option<&Value> {
     if let Some(binding) = self.bindings.get(name) {
-      Some(binding.value.as_ref())
+      Some(&binding.value)
     } else {
       self.parent?.value(name)
     }
   }
 
-  pub(crate) fn bindings(&self) -> impl Iterator<Item = &Binding<String>> {
+  pub(crate) fn bindings(&self) -> impl Iterator<Item = &Binding<Value>> {
     self.bindings.values()
   }
 
-  pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
-    self.bindings.keys().copied()
-  }
-
   pub(crate) fn parent(&self) -> Option<&'run Scope<'src, 'run>> {
     self.parent
   }
@@ -1,6 +1,8 @@
 use {super::*, std::path::Component};
 
+pub(crate) const BACKTICK_CACHE_DIRECTORY: &str = ".just/cache/backticks";
 const DEFAULT_JUSTFILE_NAME: &str = JUSTFILE_NAMES[0];
+pub(crate) const GLOBAL_JUSTFILE_ENVIRONMENT_KEY: &str = "JUST_GLOBAL_JUSTFILE";
 const JUSTFILE_NAMES: &[&str] = &["justfile", ".justfile"];
 const PROJECT_ROOT_CHILDREN: &[&str] = &[".bzr", ".git", ".hg", ".svn", "_darcs"];
 
@@ -10,16 +12,16 @@ pub(crate) struct Search {
 }
 
 impl Search {
-  pub(crate) fn find(
-    search_config: &SearchConfig,
-    invocation_directory: &Path,
-  ) -> SearchResult<Self> {
-    match search_config {
-      SearchConfig::FromInvocationDirectory => Self::find_next(invocation_directory),
+  pub(crate) fn find(config: &Config) -> SearchResult<Self> {
+    let invocation_directory = &config.invocation_directory;
+
+    match &config.search_config {
+      SearchConfig::FromInvocationDirectory => Self::find_next(config, invocation_directory),
+      SearchConfig::GlobalJustfile => Self::global(config, invocation_directory),
       SearchConfig::FromSearchDirectory { search_directory } => {
         let search_directory = Self::clean(invocation_directory, search_directory);
 
-        let justfile = Self::justfile(&search_directory)?;
+        let justfile = Self::justfile(config, &search_directory)?;
 
         let working_directory = Self::working_directory_from_justfile(&justfile)?;
 
@@ -48,8 +50,8 @@ impl Search {
     }
   }
 
-  pub(crate) fn find_next(starting_dir: &Path) -> SearchResult<Self> {
-    let justfile = Self::justfile(starting_dir)?;
+  pub(crate) fn find_next(config: &Config, starting_dir: &Path) -> SearchResult<Self> {
+    let justfile = Self::justfile(config, starting_dir)?;
 
     let working_directory = Self::working_directory_from_justfile(&justfile)?;
 
@@ -59,15 +61,61 @@ impl Search {
     })
   }
 
-  pub(crate) fn init(
-    search_config: &SearchConfig,
-    invocation_directory: &Path,
-  ) -> SearchResult<Self> {
-    match search_config {
+  /// Find the global justfile, with the working directory set to the project
+  /// root containing `directory`
+  pub(crate) fn global(config: &Config, directory: &Path) -> SearchResult<Self> {
+    let justfile = Self::global_justfile_paths(directory)
+      .into_iter()
+      .find(|path| path.is_file())
+      .ok_or(SearchError::GlobalJustfileNotFound)?;
+
+    Ok(Self {
+      justfile,
+      working_directory: Self::project_root(config, directory)?,
+    })
+  }
+
+  /// Candidate global justfile paths, in order of precedence. If
+  /// `JUST_GLOBAL_JUSTFILE` is set, it is the only candidate.
+  fn global_justfile_paths(directory: &Path) -> Vec<PathBuf> {
+    if let Some(path) = env::var_os(GLOBAL_JUSTFILE_ENVIRONMENT_KEY).filter(|path| !path.is_empty())
+    {
+      return vec![Self::clean(directory, Path::new(&path))];
+    }
+
+    let mut paths = Vec::new();
+
+    if let Some(config_home) = env::var_os("XDG_CONFIG_HOME")
+      .map(PathBuf::from)
+      .filter(|path| path.is_absolute())
+    {
+      paths.push(config_home.join("just").join(DEFAULT_JUSTFILE_NAME));
+    }
+
+    if let Some(home) = env::var_os("HOME")
+      .or_else(|| env::var_os("USERPROFILE"))
+      .map(PathBuf::from)
+      .filter(|path| path.is_absolute())
+    {
+      paths.push(
+        home
+          .join(".config")
+          .join("just")
+          .join(DEFAULT_JUSTFILE_NAME),
+      );
+    }
+
+    paths
+  }
+
+  pub(crate) fn init(config: &Config) -> SearchResult<Self> {
+    let invocation_directory = &config.invocation_directory;
+
+    match &config.search_config {
       SearchConfig::FromInvocationDirectory => {
-        let working_directory = Self::project_root(invocation_directory)?;
+        let working_directory = Self::project_root(config, invocation_directory)?;
 
-        let justfile = working_directory.join(DEFAULT_JUSTFILE_NAME);
+        let justfile = Self::init_justfile(config, &working_directory)?;
 
         Ok(Self {
           justfile,
@@ -75,12 +123,24 @@ impl Search {
         })
       }
 
+      SearchConfig::GlobalJustfile => {
+        let justfile = Self::global_justfile_paths(invocation_directory)
+          .into_iter()
+          .next()
+          .ok_or(SearchError::GlobalJustfileNotFound)?;
+
+        Ok(Self {
+          justfile,
+          working_directory: Self::project_root(config, invocation_directory)?,
+        })
+      }
+
       SearchConfig::FromSearchDirectory { search_directory } => {
         let search_directory = Self::clean(invocation_directory, search_directory);
 
-        let working_directory = Self::project_root(&search_directory)?;
+        let working_directory = Self::project_root(config, &search_directory)?;
 
-        let justfile = working_directory.join(DEFAULT_JUSTFILE_NAME);
+        let justfile = Self::init_justfile(config, &working_directory)?;
 
         Ok(Self {
           justfile,
@@ -109,36 +169,82 @@ impl Search {
     }
   }
 
-  fn justfile(directory: &Path) -> SearchResult<PathBuf> {
+  fn justfile(config: &Config, directory: &Path) -> SearchResult<PathBuf> {
     for directory in directory.ancestors() {
-      let mut candidates = BTreeSet::new();
+      let candidates = Self::candidates(config, directory)?;
 
-      let entries = fs::read_dir(directory).map_err(|io_error| SearchError::Io {
+      match candidates.len() {
+        0 => {}
+        1 => return Ok(candidates.into_iter().next().unwrap()),
+        _ => return Err(SearchError::MultipleCandidates { candidates }),
+      }
+
+      if Self::contains_any(directory, &config.root_markers) {
+        break;
+      }
+    }
+
+    Err(SearchError::NotFound)
+  }
+
+  /// Justfiles in `directory`, matching the default justfile names or names
+  /// from `--justfile-names`, ignoring case
+  fn candidates(config: &Config, directory: &Path) -> SearchResult<BTreeSet<PathBuf>> {
+    let justfile_names = JUSTFILE_NAMES
+      .iter()
+      .copied()
+      .chain(config.justfile_names.iter().map(String::as_str))
+      .collect::<Vec<&str>>();
+
+    let mut candidates = BTreeSet::new();
+
+    let entries = fs::read_dir(directory).map_err(|io_error| SearchError::Io {
+      io_error,
+      directory: directory.to_owned(),
+    })?;
+    for entry in entries {
+      let entry = entry.map_err(|io_error| SearchError::Io {
         io_error,
         directory: directory.to_owned(),
       })?;
-      for entry in entries {
-        let entry = entry.map_err(|io_error| SearchError::Io {
-          io_error,
-          directory: directory.to_owned(),
-        })?;
-        if let Some(name) = entry.file_name().to_str() {
-          for justfile_name in JUSTFILE_NAMES {
-            if name.eq_ignore_ascii_case(justfile_name) {
-              candidates.insert(entry.path());
-            }
+      if let Some(name) = entry.file_name().to_str() {
+        for justfile_name in &justfile_names {
+          if name.eq_ignore_ascii_case(justfile_name) {
+            candidates.insert(entry.path());
           }
         }
       }
+    }
 
-      match candidates.len() {
-        0 => {}
-        1 => return Ok(candidates.into_iter().next().unwrap()),
-        _ => return Err(SearchError::MultipleCandidates { candidates }),
-      }
+    Ok(candidates)
+  }
+
+  /// The justfile that `--init` should create in `working_directory`, or the
+  /// existing justfile, if there is one
+  fn init_justfile(config: &Config, working_directory: &Path) -> SearchResult<PathBuf> {
+    Ok(
+      Self::candidates(config, working_directory)?
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| working_directory.join(DEFAULT_JUSTFILE_NAME)),
+    )
+  }
+
+  fn contains_any(directory: &Path, names: &[String]) -> bool {
+    names.iter().any(|name| directory.join(name).exists())
+  }
+
+  /// The directory from which to continue searching for a justfile when
+  /// falling back from `justfile`, or `None` if `justfile` is in a directory
+  /// containing a root marker from `--root-markers`
+  pub(crate) fn fallback_directory<'a>(config: &Config, justfile: &'a Path) -> Option<&'a Path> {
+    let directory = justfile.parent()?;
+
+    if Self::contains_any(directory, &config.root_markers) {
+      return None;
     }
 
-    Err(SearchError::NotFound)
+    directory.parent()
   }
 
   fn clean(invocation_directory: &Path, path: &Path) -> PathBuf {
@@ -159,7 +265,7 @@ impl Search {
     clean.into_iter().collect()
   }
 
-  fn project_root(directory: &Path) -> SearchResult<PathBuf> {
+  fn project_root(config: &Config, directory: &Path) -> SearchResult<PathBuf> {
     for directory in directory.ancestors() {
       let entries = fs::read_dir(directory).map_err(|io_error| SearchError::Io {
         io_error,
@@ -171,7 +277,11 @@ impl Search {
           io_error,
           directory: directory.to_owned(),
         })?;
-        for project_root_child in PROJECT_ROOT_CHILDREN.iter().copied() {
+        for project_root_child in PROJECT_ROOT_CHILDREN
+          .iter()
+          .copied()
+          .chain(config.root_markers.iter().map(String::as_str))
+        {
           if entry.file_name() == project_root_child {
             return Ok(directory.to_owned());
           }
@@ -202,7 +312,7 @@ mod tests {
   #[test]
   fn not_found() {
     let tmp = testing::tempdir();
-    match Search::justfile(tmp.path()) {
+    match Search::justfile(&testing::config(&[]), tmp.path()) {
       Err(SearchError::NotFound) => {}
       _ => panic!("No justfile found error was expected"),
     }
@@ -222,7 +332,7 @@ mod tests {
     }
     fs::write(&path, "default:\n\techo ok").unwrap();
     path.pop();
-    match Search::justfile(path.as_path()) {
+    match Search::justfile(&testing::config(&[]), path.as_path()) {
       Err(SearchError::MultipleCandidates { .. }) => {}
       _ => panic!("Multiple candidates error was expected"),
     }
@@ -235,7 +345,7 @@ mod tests {
     path.push(DEFAULT_JUSTFILE_NAME);
     fs::write(&path, "default:\n\techo ok").unwrap();
     path.pop();
-    if let Err(err) = Search::justfile(path.as_path()) {
+    if let Err(err) = Search::justfile(&testing::config(&[]), path.as_path()) {
       panic!("No errors were expected: {err}");
     }
   }
@@ -258,7 +368,7 @@ mod tests {
     path.push(spongebob_case);
     fs::write(&path, "default:\n\techo ok").unwrap();
     path.pop();
-    if let Err(err) = Search::justfile(path.as_path()) {
+    if let Err(err) = Search::justfile(&testing::config(&[]), path.as_path()) {
       panic!("No errors were expected: {err}");
     }
   }
@@ -274,7 +384,7 @@ mod tests {
     fs::create_dir(&path).expect("test justfile search: failed to create intermediary directory");
     path.push("b");
     fs::create_dir(&path).expect("test justfile search: failed to create intermediary directory");
-    if let Err(err) = Search::justfile(path.as_path()) {
+    if let Err(err) = Search::justfile(&testing::config(&[]), path.as_path()) {
       panic!("No errors were expected: {err}");
     }
   }
@@ -293,7 +403,7 @@ mod tests {
     path.pop();
     path.push("b");
     fs::create_dir(&path).expect("test justfile search: failed to create intermediary directory");
-    match Search::justfile(path.as_path()) {
+    match Search::justfile(&testing::config(&[]), path.as_path()) {
       Ok(found_path) => {
         path.pop();
         path.push(DEFAULT_JUSTFILE_NAME);
@@ -320,9 +430,13 @@ mod tests {
     #[cfg(windows)]
     std::os::windows::fs::symlink_file(&src, &justfile).unwrap();
 
-    let search_config = SearchConfig::FromInvocationDirectory;
+    let config = Config {
+      invocation_directory: sub.clone(),
+      search_config: SearchConfig::FromInvocationDirectory,
+      ..testing::config(&[])
+    };
 
-    let search = Search::find(&search_config, &sub).unwrap();
+    let search = Search::find(&config).unwrap();
 
     assert_eq!(search.justfile, justfile);
     assert_eq!(search.working_directory, sub);
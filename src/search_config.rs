@@ -9,6 +9,9 @@ pub(crate) enum SearchConfig {
   FromInvocationDirectory,
   /// As in `Invocation`, but start from `search_directory`.
   FromSearchDirectory { search_directory: PathBuf },
+  /// Use the user's global justfile, with the working directory set to the
+  /// project root containing the invocation directory.
+  GlobalJustfile,
   /// Use user-specified justfile, with the working directory set to the
   /// directory that contains it.
   WithJustfile { justfile: PathBuf },
@@ -0,0 +1,82 @@
+use super::*;
+
+/// A counting semaphore, used to limit the number of recipes which run
+/// concurrently when dependencies are run in parallel.
+pub(crate) struct Semaphore {
+  available: Mutex<usize>,
+  condvar: Condvar,
+}
+
+impl Semaphore {
+  pub(crate) fn new(permits: usize) -> Self {
+    Self {
+      available: Mutex::new(permits.max(1)),
+      condvar: Condvar::new(),
+    }
+  }
+
+  /// Block until a permit is available, returning a guard which releases the
+  /// permit when dropped
+  pub(crate) fn acquire(&self) -> SemaphoreGuard<'_> {
+    let mut available = self.available.lock().unwrap();
+
+    while *available == 0 {
+      available = self.condvar.wait(available).unwrap();
+    }
+
+    *available -= 1;
+
+    SemaphoreGuard { semaphore: self }
+  }
+
+  fn release(&self) {
+    *self.available.lock().unwrap() += 1;
+    self.condvar.notify_one();
+  }
+}
+
+pub(crate) struct SemaphoreGuard<'semaphore> {
+  semaphore: &'semaphore Semaphore,
+}
+
+impl Drop for SemaphoreGuard<'_> {
+  fn drop(&mut self) {
+    self.semaphore.release();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn limits_concurrency() {
+    let semaphore = Semaphore::new(2);
+    let running = Mutex::new(0);
+    let max = Mutex::new(0);
+
+    thread::scope(|scope| {
+      for _ in 0..8 {
+        scope.spawn(|| {
+          let _permit = semaphore.acquire();
+          {
+            let mut running = running.lock().unwrap();
+            *running += 1;
+            let mut max = max.lock().unwrap();
+            *max = cmp::max(*max, *running);
+          }
+          thread::sleep(std::time::Duration::from_millis(10));
+          *running.lock().unwrap() -= 1;
+        });
+      }
+    });
+
+    assert_eq!(*max.lock().unwrap(), 2);
+  }
+
+  #[test]
+  fn zero_permits_is_one() {
+    let semaphore = Semaphore::new(0);
+    drop(semaphore.acquire());
+  }
+}
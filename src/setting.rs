@@ -4,16 +4,20 @@ use super::*;
 pub(crate) enum Setting<'src> {
   AllowDuplicateRecipes(bool),
   DotenvFilename(String),
+  DotenvFiles(Vec<String>),
   DotenvLoad(bool),
+  DotenvOverride(bool),
   DotenvPath(String),
   Export(bool),
   Fallback(bool),
   IgnoreComments(bool),
+  Lazy(bool),
   PositionalArguments(bool),
   Shell(Shell<'src>),
   Tempdir(String),
   WindowsPowerShell(bool),
   WindowsShell(Shell<'src>),
+  WorkingDirectory(String),
 }
 
 impl<'src> Display for Setting<'src> {
@@ -21,13 +25,28 @@ impl<'src> Display for Setting<'src> {
     match self {
       Setting::AllowDuplicateRecipes(value)
       | Setting::DotenvLoad(value)
+      | Setting::DotenvOverride(value)
       | Setting::Export(value)
       | Setting::Fallback(value)
       | Setting::IgnoreComments(value)
+      | Setting::Lazy(value)
       | Setting::PositionalArguments(value)
       | Setting::WindowsPowerShell(value) => write!(f, "{value}"),
+      Setting::DotenvFiles(files) => {
+        write!(f, "[")?;
+        for (i, file) in files.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{file:?}")?;
+        }
+        write!(f, "]")
+      }
       Setting::Shell(shell) | Setting::WindowsShell(shell) => write!(f, "{shell}"),
-      Setting::DotenvFilename(value) | Setting::DotenvPath(value) | Setting::Tempdir(value) => {
+      Setting::DotenvFilename(value)
+      | Setting::DotenvPath(value)
+      | Setting::Tempdir(value)
+      | Setting::WorkingDirectory(value) => {
         write!(f, "{value:?}")
       }
     }
@@ -1,5 +1,6 @@
 use super::*;
 
+pub(crate) const BUILTIN_SHELL: &str = "builtin";
 pub(crate) const DEFAULT_SHELL: &str = "sh";
 pub(crate) const DEFAULT_SHELL_ARGS: &[&str] = &["-cu"];
 pub(crate) const WINDOWS_POWERSHELL_SHELL: &str = "powershell.exe";
@@ -10,16 +11,20 @@ pub(crate) const WINDOWS_POWERSHELL_ARGS: &[&str] = &["-NoLogo", "-Command"];
 pub(crate) struct Settings<'src> {
   pub(crate) allow_duplicate_recipes: bool,
   pub(crate) dotenv_filename: Option<String>,
+  pub(crate) dotenv_files: Option<Vec<String>>,
   pub(crate) dotenv_load: Option<bool>,
+  pub(crate) dotenv_override: bool,
   pub(crate) dotenv_path: Option<PathBuf>,
   pub(crate) export: bool,
   pub(crate) fallback: bool,
   pub(crate) ignore_comments: bool,
+  pub(crate) lazy: bool,
   pub(crate) positional_arguments: bool,
   pub(crate) shell: Option<Shell<'src>>,
   pub(crate) tempdir: Option<String>,
   pub(crate) windows_powershell: bool,
   pub(crate) windows_shell: Option<Shell<'src>>,
+  pub(crate) working_directory: Option<PathBuf>,
 }
 
 impl<'src> Settings<'src> {
@@ -34,9 +39,15 @@ impl<'src> Settings<'src> {
         Setting::DotenvFilename(filename) => {
           settings.dotenv_filename = Some(filename);
         }
+        Setting::DotenvFiles(files) => {
+          settings.dotenv_files = Some(files);
+        }
         Setting::DotenvLoad(dotenv_load) => {
           settings.dotenv_load = Some(dotenv_load);
         }
+        Setting::DotenvOverride(dotenv_override) => {
+          settings.dotenv_override = dotenv_override;
+        }
         Setting::DotenvPath(path) => {
           settings.dotenv_path = Some(PathBuf::from(path));
         }
@@ -49,6 +60,9 @@ impl<'src> Settings<'src> {
         Setting::IgnoreComments(ignore_comments) => {
           settings.ignore_comments = ignore_comments;
         }
+        Setting::Lazy(lazy) => {
+          settings.lazy = lazy;
+        }
         Setting::PositionalArguments(positional_arguments) => {
           settings.positional_arguments = positional_arguments;
         }
@@ -64,6 +78,9 @@ impl<'src> Settings<'src> {
         Setting::Tempdir(tempdir) => {
           settings.tempdir = Some(tempdir);
         }
+        Setting::WorkingDirectory(working_directory) => {
+          settings.working_directory = Some(working_directory.into());
+        }
       }
     }
 
@@ -80,7 +97,25 @@ impl<'src> Settings<'src> {
     cmd
   }
 
+  /// Whether recipe lines and backticks should be run by the builtin shell
+  pub(crate) fn builtin_shell(&self, config: &Config) -> bool {
+    self.configured_shell(config).0 == BUILTIN_SHELL
+  }
+
+  /// The shell used to run recipe lines and backticks. If the builtin shell
+  /// is configured, the default shell is returned, for use by `--choose` and
+  /// `--shell-command`.
   pub(crate) fn shell<'a>(&'a self, config: &'a Config) -> (&'a str, Vec<&'a str>) {
+    let shell = self.configured_shell(config);
+
+    if shell.0 == BUILTIN_SHELL {
+      (DEFAULT_SHELL, DEFAULT_SHELL_ARGS.to_vec())
+    } else {
+      shell
+    }
+  }
+
+  fn configured_shell<'a>(&'a self, config: &'a Config) -> (&'a str, Vec<&'a str>) {
     match (&config.shell, &config.shell_args) {
       (Some(shell), Some(shell_args)) => (shell, shell_args.iter().map(String::as_ref).collect()),
       (Some(shell), None) => (shell, DEFAULT_SHELL_ARGS.to_vec()),
@@ -212,6 +247,29 @@ mod tests {
     assert_eq!(settings.shell(&config), ("asdf.exe", vec!["-nope"]));
   }
 
+  #[test]
+  fn builtin_shell() {
+    let settings = Settings {
+      shell: Some(Shell {
+        command: StringLiteral {
+          kind: StringKind::from_token_start("\"").unwrap(),
+          raw: "builtin",
+          cooked: "builtin".to_string(),
+        },
+        arguments: Vec::new(),
+      }),
+      ..Default::default()
+    };
+
+    let config = Config {
+      shell_command: false,
+      ..testing::config(&[])
+    };
+
+    assert!(settings.builtin_shell(&config));
+    assert_eq!(settings.shell(&config), ("sh", vec!["-cu"]));
+  }
+
   #[test]
   fn shell_present_but_not_shell_args() {
     let settings = Settings {
@@ -30,7 +30,7 @@ impl<'line> Shebang<'line> {
     })
   }
 
-  fn interpreter_filename(&self) -> &str {
+  fn interpreter_filename(&self) -> &'line str {
     self
       .interpreter
       .split(|c| matches!(c, '/' | '\\'))
@@ -38,6 +38,23 @@ impl<'line> Shebang<'line> {
       .unwrap_or(self.interpreter)
   }
 
+  /// The program that runs the script, which is the first argument if the
+  /// interpreter is `env`
+  pub(crate) fn program(&self) -> &'line str {
+    if self.interpreter_filename() == "env" {
+      if let Some(program) = self
+        .argument
+        .into_iter()
+        .flat_map(str::split_whitespace)
+        .find(|word| !word.starts_with('-'))
+      {
+        return program;
+      }
+    }
+
+    self.interpreter
+  }
+
   pub(crate) fn script_filename(&self, recipe: &str) -> String {
     match self.interpreter_filename() {
       "cmd" | "cmd.exe" => format!("{recipe}.bat"),
@@ -1,11 +1,25 @@
 use super::*;
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Ord, PartialOrd, Eq)]
 pub(crate) struct Shell<'src> {
   pub(crate) arguments: Vec<StringLiteral<'src>>,
   pub(crate) command: StringLiteral<'src>,
 }
 
+impl Shell<'_> {
+  pub(crate) fn command(&self) -> Command {
+    let mut cmd = Command::new(&self.command.cooked);
+
+    cmd.args(self.arguments.iter().map(|argument| &argument.cooked));
+
+    cmd
+  }
+
+  pub(crate) fn is_builtin(&self) -> bool {
+    self.command.cooked == settings::BUILTIN_SHELL
+  }
+}
+
 impl<'src> Display for Shell<'src> {
   fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
     write!(f, "[{}", self.command)?;
@@ -0,0 +1,73 @@
+use super::*;
+
+/// The family of the shell that a recipe line or script is run with, which
+/// determines how `{{ *… }}` splats are quoted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ShellKind {
+  Posix,
+  PowerShell,
+}
+
+impl ShellKind {
+  /// The kind of the shell run by `command`, or `None` if splats cannot be
+  /// quoted for it
+  pub(crate) fn new(command: &str) -> Option<Self> {
+    let filename = command.rsplit(['/', '\\']).next().unwrap_or(command);
+
+    match filename.strip_suffix(".exe").unwrap_or(filename) {
+      settings::BUILTIN_SHELL | "ash" | "bash" | "dash" | "ksh" | "mksh" | "sh" | "zsh" => {
+        Some(Self::Posix)
+      }
+      "powershell" | "pwsh" => Some(Self::PowerShell),
+      _ => None,
+    }
+  }
+
+  /// Quote `word` so that it is passed to the shell as a single argument
+  pub(crate) fn quote(self, word: &str) -> String {
+    match self {
+      Self::Posix => format!("'{}'", word.replace('\'', "'\\''")),
+      Self::PowerShell => {
+        let mut quoted = String::from("'");
+        for c in word.chars() {
+          // PowerShell treats typographic single quotes like `'`
+          if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+            quoted.push(c);
+          }
+          quoted.push(c);
+        }
+        quoted.push('\'');
+        quoted
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn new() {
+    assert_eq!(ShellKind::new("sh"), Some(ShellKind::Posix));
+    assert_eq!(ShellKind::new("/usr/bin/bash"), Some(ShellKind::Posix));
+    assert_eq!(ShellKind::new("builtin"), Some(ShellKind::Posix));
+    assert_eq!(ShellKind::new("pwsh"), Some(ShellKind::PowerShell));
+    assert_eq!(
+      ShellKind::new("C:\\Windows\\powershell.exe"),
+      Some(ShellKind::PowerShell)
+    );
+    assert_eq!(ShellKind::new("cmd.exe"), None);
+    assert_eq!(ShellKind::new("python3"), None);
+  }
+
+  #[test]
+  fn quote() {
+    assert_eq!(ShellKind::Posix.quote("it's"), "'it'\\''s'");
+    assert_eq!(ShellKind::PowerShell.quote("it's"), "'it''s'");
+    assert_eq!(
+      ShellKind::PowerShell.quote("it\u{2019}s"),
+      "'it\u{2019}\u{2019}s'"
+    );
+  }
+}
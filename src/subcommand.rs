@@ -9,6 +9,7 @@ pub(crate) enum Subcommand {
     overrides: BTreeMap<String, String>,
     chooser: Option<String>,
   },
+  ClearCache,
   Command {
     arguments: Vec<OsString>,
     binary: OsString,
@@ -23,9 +24,13 @@ pub(crate) enum Subcommand {
     overrides: BTreeMap<String, String>,
     variable: Option<String>,
   },
-  Format,
+  Format {
+    justfiles: Vec<PathBuf>,
+  },
+  Groups,
   Init,
   List,
+  Lsp,
   Run {
     arguments: Vec<String>,
     overrides: BTreeMap<String, String>,
@@ -51,7 +56,11 @@ impl Subcommand {
         return Ok(());
       }
       Completions { shell } => return Self::completions(shell),
+      Format { justfiles } if !justfiles.is_empty() => {
+        return Self::format_justfiles(config, loader, justfiles)
+      }
       Init => return Self::init(config),
+      Lsp => return Self::lsp(config),
       Run {
         arguments,
         overrides,
@@ -59,28 +68,33 @@ impl Subcommand {
       _ => {}
     }
 
-    let search = Search::find(&config.search_config, &config.invocation_directory)?;
+    let search = Search::find(config)?;
 
     if let Edit = self {
       return Self::edit(&search);
     }
 
-    let (src, ast, justfile) = Self::compile(config, loader, &search)?;
+    let compilation = Self::compile(config, loader, &search)?;
+    let justfile = &compilation.justfile;
+    let ast = compilation.root_ast();
+    let src = compilation.root_src();
 
     match self {
       Choose { overrides, chooser } => {
         Self::choose(config, justfile, &search, overrides, chooser.as_deref())?;
       }
+      ClearCache => Self::clear_cache(&search.working_directory, justfile)?,
       Command { overrides, .. } | Evaluate { overrides, .. } => {
         justfile.run(config, &search, overrides, &[])?;
       }
       Dump => Self::dump(config, ast, justfile)?,
-      Format => Self::format(config, &search, src, ast)?,
-      List => Self::list(config, justfile),
+      Format { .. } => Self::format(config, &search, src, ast)?,
+      Groups => Self::groups(config, justfile),
+      List => Self::list(config, 0, justfile),
       Show { ref name } => Self::show(config, name, justfile)?,
       Summary => Self::summary(config, justfile),
       Variables => Self::variables(justfile),
-      Changelog | Completions { .. } | Edit | Init | Run { .. } => unreachable!(),
+      Changelog | Completions { .. } | Edit | Init | Lsp | Run { .. } => unreachable!(),
     }
 
     Ok(())
@@ -108,12 +122,13 @@ impl Subcommand {
 
       let mut unknown_recipes_errors = None;
 
-      loop {
-        let search = match Search::find_next(&path) {
-          Err(SearchError::NotFound) => match unknown_recipes_errors {
-            Some(err) => return Err(err),
-            None => return Err(SearchError::NotFound.into()),
-          },
+      let mut searched = Vec::new();
+
+      let err = loop {
+        let search = match Search::find_next(config, &path) {
+          Err(SearchError::NotFound) => {
+            break unknown_recipes_errors.unwrap_or_else(|| SearchError::NotFound.into())
+          }
           Err(err) => return Err(err.into()),
           Ok(search) => {
             if config.verbosity.loquacious() && path != starting_path {
@@ -134,27 +149,39 @@ impl Subcommand {
         };
 
         match Self::run_inner(config, loader, arguments, overrides, &search) {
-          Err((err @ Error::UnknownRecipes { .. }, true)) => {
-            match search.justfile.parent().unwrap().parent() {
+          Err((err @ Error::UnknownRecipes { .. }, fallback)) => {
+            searched.push(Self::canonical(&search.justfile));
+            match Search::fallback_directory(config, &search.justfile).filter(|_| fallback) {
               Some(parent) => {
                 unknown_recipes_errors.get_or_insert(err);
                 path = parent.into();
               }
-              None => return Err(err),
+              None => break err,
             }
           }
           result => return result.map_err(|(err, _fallback)| err),
         }
+      };
+
+      // The global justfile is consulted last, if no justfile was found or
+      // none contained the recipes, unless it was already searched as part
+      // of the project
+      let search = match Search::global(config, &starting_path) {
+        Ok(search) if !searched.contains(&Self::canonical(&search.justfile)) => search,
+        _ => return Err(err),
+      };
+
+      if config.verbosity.loquacious() {
+        eprintln!("Trying {}", search.justfile.display());
+      }
+
+      match Self::run_inner(config, loader, arguments, overrides, &search) {
+        Err((Error::UnknownRecipes { .. }, _fallback)) => Err(err),
+        result => result.map_err(|(err, _fallback)| err),
       }
     } else {
-      Self::run_inner(
-        config,
-        loader,
-        arguments,
-        overrides,
-        &Search::find(&config.search_config, &config.invocation_directory)?,
-      )
-      .map_err(|(err, _fallback)| err)
+      Self::run_inner(config, loader, arguments, overrides, &Search::find(config)?)
+        .map_err(|(err, _fallback)| err)
     }
   }
 
@@ -165,29 +192,31 @@ impl Subcommand {
     overrides: &BTreeMap<String, String>,
     search: &Search,
   ) -> Result<(), (Error<'src>, bool)> {
-    let (_src, _ast, justfile) =
-      Self::compile(config, loader, search).map_err(|err| (err, false))?;
+    let compilation = Self::compile(config, loader, search).map_err(|err| (err, false))?;
+    let justfile = &compilation.justfile;
     justfile
       .run(config, search, overrides, arguments)
       .map_err(|err| (err, justfile.settings.fallback))
   }
 
+  fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.into())
+  }
+
   fn compile<'src>(
     config: &Config,
     loader: &'src Loader,
     search: &Search,
-  ) -> Result<(&'src str, Ast<'src>, Justfile<'src>), Error<'src>> {
-    let src = loader.load(&search.justfile)?;
-
-    let (ast, justfile) = Compiler::compile(src)?;
+  ) -> Result<Compilation<'src>, Error<'src>> {
+    let compilation = Compiler::compile(loader, &search.justfile)?;
 
     if config.verbosity.loud() {
-      for warning in &justfile.warnings {
+      for warning in &compilation.justfile.warnings {
         eprintln!("{}", warning.color_display(config.color.stderr()));
       }
     }
 
-    Ok((src, ast, justfile))
+    Ok(compilation)
   }
 
   fn changelog() {
@@ -196,7 +225,7 @@ impl Subcommand {
 
   fn choose<'src>(
     config: &Config,
-    justfile: Justfile<'src>,
+    justfile: &Justfile<'src>,
     search: &Search,
     overrides: &BTreeMap<String, String>,
     chooser: Option<&str>,
@@ -326,7 +355,23 @@ impl Subcommand {
     Ok(())
   }
 
-  fn dump(config: &Config, ast: Ast, justfile: Justfile) -> Result<(), Error<'static>> {
+  fn clear_cache(working_directory: &Path, justfile: &Justfile) -> Result<(), Error<'static>> {
+    let path = working_directory.join(search::BACKTICK_CACHE_DIRECTORY);
+
+    match fs::remove_dir_all(&path) {
+      Ok(()) => {}
+      Err(io_error) if io_error.kind() == io::ErrorKind::NotFound => {}
+      Err(io_error) => return Err(Error::Cache { path, io_error }),
+    }
+
+    for module in justfile.modules.values() {
+      Self::clear_cache(module.source.parent().unwrap(), module)?;
+    }
+
+    Ok(())
+  }
+
+  fn dump(config: &Config, ast: &Ast, justfile: &Justfile) -> Result<(), Error<'static>> {
     match config.dump_format {
       DumpFormat::Json => {
         serde_json::to_writer(io::stdout(), &justfile)
@@ -360,53 +405,104 @@ impl Subcommand {
     Ok(())
   }
 
-  fn format(config: &Config, search: &Search, src: &str, ast: Ast) -> Result<(), Error<'static>> {
-    config.require_unstable("The `--fmt` command is currently unstable.")?;
+  fn format(config: &Config, search: &Search, src: &str, ast: &Ast) -> Result<(), Error<'static>> {
+    if Self::format_justfile(config, &search.justfile, None, src, ast)? {
+      Ok(())
+    } else {
+      Err(Error::FormatCheckFoundDiff)
+    }
+  }
 
-    let formatted = ast.to_string();
+  /// Format justfiles given as arguments to `--fmt`. They are parsed, but not
+  /// analyzed, since files meant to be imported may not be valid on their own.
+  fn format_justfiles<'src>(
+    config: &Config,
+    loader: &'src Loader,
+    justfiles: &[PathBuf],
+  ) -> Result<(), Error<'src>> {
+    let mut formatted = true;
+
+    for justfile in justfiles {
+      let path = config.invocation_directory.join(justfile);
+      let (relative, src) = loader.load(&path, &path)?;
+      let tokens = Lexer::lex(relative, src)?;
+      let ast = Parser::parse(&tokens)?;
+      formatted &= Self::format_justfile(config, &path, Some(justfile), src, &ast)?;
+    }
+
+    if formatted {
+      Ok(())
+    } else {
+      Err(Error::FormatCheckFoundDiff)
+    }
+  }
+
+  /// Overwrite the justfile at `path` with its formatted contents. With
+  /// `--check`, instead print a diff, headed with `name` if given, and return
+  /// whether the justfile was already formatted.
+  fn format_justfile(
+    config: &Config,
+    path: &Path,
+    name: Option<&Path>,
+    src: &str,
+    ast: &Ast,
+  ) -> Result<bool, Error<'static>> {
+    let formatted = JustfileFormatter::format(src, ast);
 
     if config.check {
-      return if formatted == src {
-        Ok(())
-      } else {
-        if !config.verbosity.quiet() {
-          use similar::{ChangeTag, TextDiff};
-
-          let diff = TextDiff::configure()
-            .algorithm(similar::Algorithm::Patience)
-            .diff_lines(src, &formatted);
-
-          for op in diff.ops() {
-            for change in diff.iter_changes(op) {
-              let (symbol, color) = match change.tag() {
-                ChangeTag::Delete => ("-", config.color.stdout().diff_deleted()),
-                ChangeTag::Equal => (" ", config.color.stdout()),
-                ChangeTag::Insert => ("+", config.color.stdout().diff_added()),
-              };
-
-              print!("{}{symbol}{change}{}", color.prefix(), color.suffix());
-            }
+      if formatted == src {
+        return Ok(true);
+      }
+
+      if !config.verbosity.quiet() {
+        use similar::{ChangeTag, TextDiff};
+
+        if let Some(name) = name {
+          let color = config.color.stdout().banner();
+          println!("{}--- {}{}", color.prefix(), name.display(), color.suffix());
+          println!("{}+++ {}{}", color.prefix(), name.display(), color.suffix());
+        }
+
+        let diff = TextDiff::configure()
+          .algorithm(similar::Algorithm::Patience)
+          .diff_lines(src, &formatted);
+
+        for op in diff.ops() {
+          for change in diff.iter_changes(op) {
+            let (symbol, color) = match change.tag() {
+              ChangeTag::Delete => ("-", config.color.stdout().diff_deleted()),
+              ChangeTag::Equal => (" ", config.color.stdout()),
+              ChangeTag::Insert => ("+", config.color.stdout().diff_added()),
+            };
+
+            print!("{}{symbol}{change}{}", color.prefix(), color.suffix());
           }
         }
+      }
 
-        Err(Error::FormatCheckFoundDiff)
-      };
+      return Ok(false);
     }
 
-    fs::write(&search.justfile, formatted).map_err(|io_error| Error::WriteJustfile {
-      justfile: search.justfile.clone(),
-      io_error,
-    })?;
+    if formatted != src {
+      fs::write(path, formatted).map_err(|io_error| Error::WriteJustfile {
+        justfile: path.into(),
+        io_error,
+      })?;
 
-    if config.verbosity.loud() {
-      eprintln!("Wrote justfile to `{}`", search.justfile.display());
+      if config.verbosity.loud() {
+        eprintln!("Wrote justfile to `{}`", path.display());
+      }
     }
 
-    Ok(())
+    Ok(true)
+  }
+
+  fn lsp(config: &Config) -> Result<(), Error<'static>> {
+    Lsp::new(io::stdin().lock(), io::stdout().lock(), config.unstable).run()
   }
 
   fn init(config: &Config) -> Result<(), Error<'static>> {
-    let search = Search::init(&config.search_config, &config.invocation_directory)?;
+    let search = Search::init(config)?;
 
     if search.justfile.is_file() {
       Err(Error::InitExists {
@@ -425,7 +521,14 @@ impl Subcommand {
     }
   }
 
-  fn list(config: &Config, justfile: Justfile) {
+  fn groups(config: &Config, justfile: &Justfile) {
+    println!("Recipe groups:");
+    for group in justfile.public_groups(config.unsorted) {
+      println!("{}{group}", config.list_prefix);
+    }
+  }
+
+  fn list(config: &Config, level: usize, justfile: &Justfile) {
     // Construct a target to alias map.
     let mut recipe_aliases: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
     for alias in justfile.aliases.values() {
@@ -453,7 +556,7 @@ impl Subcommand {
 
         for parameter in &recipe.parameters {
           line_width += UnicodeWidthStr::width(
-            format!(" {}", parameter.color_display(Color::never())).as_str(),
+            format!(" {}", parameter.usage().color_display(Color::never())).as_str(),
           );
         }
 
@@ -466,81 +569,168 @@ impl Subcommand {
     let max_line_width = cmp::min(line_widths.values().copied().max().unwrap_or(0), 30);
 
     let doc_color = config.color.stdout().doc();
-    print!("{}", config.list_heading);
 
-    for recipe in justfile.public_recipes(config.unsorted) {
-      let name = recipe.name();
+    if level == 0 {
+      print!("{}", config.list_heading);
+    }
 
-      for (i, name) in iter::once(&name)
-        .chain(recipe_aliases.get(name).unwrap_or(&Vec::new()))
-        .enumerate()
-      {
-        print!("{}{name}", config.list_prefix);
-        for parameter in &recipe.parameters {
-          print!(" {}", parameter.color_display(config.color.stdout()));
-        }
+    let list_prefix = config.list_prefix.repeat(level + 1);
 
-        // Declaring this outside of the nested loops will probably be more efficient,
-        // but it creates all sorts of lifetime issues with variables inside the loops.
-        // If this is inlined like the docs say, it shouldn't make any difference.
-        let print_doc = |doc| {
-          print!(
-            " {:padding$}{} {}",
-            "",
-            doc_color.paint("#"),
-            doc_color.paint(doc),
-            padding = max_line_width
-              .saturating_sub(line_widths.get(name).copied().unwrap_or(max_line_width))
-          );
-        };
+    let recipes = justfile.public_recipes(config.unsorted);
+
+    let mut sections = vec![(
+      None,
+      recipes
+        .iter()
+        .filter(|recipe| recipe.groups().is_empty())
+        .copied()
+        .collect::<Vec<&Recipe<Dependency>>>(),
+    )];
+
+    for group in justfile.public_groups(config.unsorted) {
+      let recipes = recipes
+        .iter()
+        .filter(|recipe| recipe.groups().contains(&group))
+        .copied()
+        .collect();
+      sections.push((Some(group), recipes));
+    }
 
-        match (i, recipe.doc) {
-          (0, Some(doc)) => print_doc(doc),
-          (0, None) => (),
-          _ => {
-            let alias_doc = format!("alias for `{}`", recipe.name);
-            print_doc(&alias_doc);
+    let mut first = true;
+
+    for (group, recipes) in sections {
+      if recipes.is_empty() {
+        continue;
+      }
+
+      if !first {
+        println!();
+      }
+
+      first = false;
+
+      if let Some(group) = group {
+        println!("{list_prefix}[{group}]");
+      }
+
+      for recipe in recipes {
+        let name = recipe.name();
+
+        for (i, name) in iter::once(&name)
+          .chain(recipe_aliases.get(name).unwrap_or(&Vec::new()))
+          .enumerate()
+        {
+          print!("{list_prefix}{name}");
+          for parameter in &recipe.parameters {
+            print!(
+              " {}",
+              parameter.usage().color_display(config.color.stdout())
+            );
+          }
+
+          // Declaring this outside of the nested loops will probably be more efficient,
+          // but it creates all sorts of lifetime issues with variables inside the loops.
+          // If this is inlined like the docs say, it shouldn't make any difference.
+          let print_doc = |doc| {
+            print!(
+              " {:padding$}{} {}",
+              "",
+              doc_color.paint("#"),
+              doc_color.paint(doc),
+              padding = max_line_width
+                .saturating_sub(line_widths.get(name).copied().unwrap_or(max_line_width))
+            );
+          };
+
+          match (i, recipe.doc) {
+            (0, Some(doc)) => print_doc(doc),
+            (0, None) => (),
+            _ => {
+              let alias_doc = format!("alias for `{}`", recipe.name);
+              print_doc(&alias_doc);
+            }
           }
+          println!();
         }
-        println!();
       }
     }
+
+    for (name, module) in &justfile.modules {
+      println!("{list_prefix}{name}:");
+      Self::list(config, level + 1, module);
+    }
   }
 
-  fn show<'src>(config: &Config, name: &str, justfile: Justfile<'src>) -> Result<(), Error<'src>> {
+  fn show<'src>(config: &Config, path: &str, justfile: &Justfile<'src>) -> Result<(), Error<'src>> {
+    let mut justfile = justfile;
+
+    let mut components = path.split("::").collect::<Vec<&str>>();
+
+    let name = components.pop().unwrap();
+
+    for component in components {
+      if let Some(module) = justfile.modules.get(component) {
+        justfile = module;
+      } else {
+        return Err(Error::UnknownRecipes {
+          recipes: vec![path.to_owned()],
+          suggestion: None,
+        });
+      }
+    }
+
+    // Variables unexported in the recipe's module are shown before the recipe,
+    // since they affect the environment it runs in
+    let unexports = || {
+      for name in &justfile.unexports {
+        println!("unexport {name}");
+      }
+    };
+
     if let Some(alias) = justfile.get_alias(name) {
       let recipe = justfile.get_recipe(alias.target.name.lexeme()).unwrap();
+      unexports();
       println!("{alias}");
       println!("{}", recipe.color_display(config.color.stdout()));
       Ok(())
     } else if let Some(recipe) = justfile.get_recipe(name) {
+      unexports();
       println!("{}", recipe.color_display(config.color.stdout()));
       Ok(())
     } else {
       Err(Error::UnknownRecipes {
-        recipes: vec![name.to_owned()],
+        recipes: vec![path.to_owned()],
         suggestion: justfile.suggest_recipe(name),
       })
     }
   }
 
-  fn summary(config: &Config, justfile: Justfile) {
-    if justfile.count() == 0 {
+  fn summary(config: &Config, justfile: &Justfile) {
+    fn public_recipes(config: &Config, justfile: &Justfile, namepaths: &mut Vec<String>) {
+      namepaths.extend(
+        justfile
+          .public_recipes(config.unsorted)
+          .iter()
+          .map(|recipe| recipe.namepath.clone()),
+      );
+
+      for module in justfile.modules.values() {
+        public_recipes(config, module, namepaths);
+      }
+    }
+
+    if justfile.count() == 0 && justfile.modules.is_empty() {
       if config.verbosity.loud() {
         eprintln!("Justfile contains no recipes.");
       }
     } else {
-      let summary = justfile
-        .public_recipes(config.unsorted)
-        .iter()
-        .map(|recipe| recipe.name())
-        .collect::<Vec<&str>>()
-        .join(" ");
-      println!("{summary}");
+      let mut namepaths = Vec::new();
+      public_recipes(config, justfile, &mut namepaths);
+      println!("{}", namepaths.join(" "));
     }
   }
 
-  fn variables(justfile: Justfile) {
+  fn variables(justfile: &Justfile) {
     for (i, (_, assignment)) in justfile.assignments.iter().enumerate() {
       if i > 0 {
         print!(" ");
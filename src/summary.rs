@@ -13,24 +13,28 @@
 //! of existing justfiles.
 
 use {
-  crate::compiler::Compiler,
-  std::{collections::BTreeMap, fs, io, path::Path},
+  crate::{
+    color::Color, color_display::ColorDisplay, compiler::Compiler, error::Error, loader::Loader,
+  },
+  std::{collections::BTreeMap, io, path::Path},
 };
 
 mod full {
   pub(crate) use crate::{
-    assignment::Assignment, conditional_operator::ConditionalOperator, dependency::Dependency,
-    expression::Expression, fragment::Fragment, justfile::Justfile, line::Line,
-    parameter::Parameter, parameter_kind::ParameterKind, recipe::Recipe, thunk::Thunk,
+    assignment::Assignment, condition::Condition, conditional_operator::ConditionalOperator,
+    dependency::Dependency, expression::Expression, fragment::Fragment, justfile::Justfile,
+    line::Line, parameter::Parameter, parameter_kind::ParameterKind, recipe::Recipe, thunk::Thunk,
   };
 }
 
 pub fn summary(path: &Path) -> Result<Result<Summary, String>, io::Error> {
-  let text = fs::read_to_string(path)?;
+  let loader = Loader::new(false);
 
-  match Compiler::compile(&text) {
-    Ok((_, justfile)) => Ok(Ok(Summary::new(justfile))),
-    Err(compilation_error) => Ok(Err(compilation_error.to_string())),
+  match Compiler::compile(&loader, path) {
+    Ok(compilation) => Ok(Ok(Summary::new(compilation.justfile))),
+    Err(Error::Load { io_error, .. }) => Err(io_error),
+    Err(Error::Compile { compile_error }) => Ok(Err(compile_error.to_string())),
+    Err(error) => Ok(Err(error.color_display(Color::never()).to_string())),
   }
 }
 
@@ -98,17 +102,34 @@ impl Recipe {
 
 #[derive(Eq, PartialEq, Hash, Ord, PartialOrd, Debug, Clone)]
 pub struct Parameter {
+  pub choices: Vec<String>,
   pub kind: ParameterKind,
   pub name: String,
   pub default: Option<Expression>,
+  pub long: Option<String>,
+  pub pattern: Option<String>,
+  pub short: Option<char>,
+  pub value: Option<String>,
 }
 
 impl Parameter {
   fn new(parameter: &full::Parameter) -> Parameter {
     Parameter {
+      choices: parameter
+        .choices
+        .iter()
+        .map(|choice| choice.cooked.clone())
+        .collect(),
       kind: ParameterKind::new(parameter.kind),
       name: parameter.name.lexeme().to_owned(),
       default: parameter.default.as_ref().map(Expression::new),
+      long: parameter.long.clone(),
+      pattern: parameter
+        .pattern
+        .as_ref()
+        .map(|pattern| pattern.original().to_owned()),
+      short: parameter.short,
+      value: parameter.value.clone(),
     }
   }
 }
@@ -147,6 +168,7 @@ impl Line {
 pub enum Fragment {
   Text { text: String },
   Expression { expression: Expression },
+  Splat { expression: Expression },
 }
 
 impl Fragment {
@@ -155,7 +177,16 @@ impl Fragment {
       full::Fragment::Text { token } => Fragment::Text {
         text: token.lexeme().to_owned(),
       },
-      full::Fragment::Interpolation { expression } => Fragment::Expression {
+      full::Fragment::Interpolation {
+        expression,
+        splat: false,
+      } => Fragment::Expression {
+        expression: Expression::new(expression),
+      },
+      full::Fragment::Interpolation {
+        expression,
+        splat: true,
+      } => Fragment::Splat {
         expression: Expression::new(expression),
       },
     }
@@ -191,11 +222,9 @@ pub enum Expression {
     rhs: Box<Expression>,
   },
   Conditional {
-    lhs: Box<Expression>,
-    rhs: Box<Expression>,
+    condition: Condition,
     then: Box<Expression>,
     otherwise: Box<Expression>,
-    operator: ConditionalOperator,
   },
   Join {
     lhs: Option<Box<Expression>>,
@@ -221,10 +250,12 @@ impl Expression {
           name: name.lexeme().to_owned(),
           arguments: Vec::new(),
         },
-        full::Thunk::Unary { name, arg, .. } => Expression::Call {
-          name: name.lexeme().to_owned(),
-          arguments: vec![Expression::new(arg)],
-        },
+        full::Thunk::Unary { name, arg, .. } | full::Thunk::UnaryValue { name, arg, .. } => {
+          Expression::Call {
+            name: name.lexeme().to_owned(),
+            arguments: vec![Expression::new(arg)],
+          }
+        }
         full::Thunk::UnaryOpt {
           name,
           args: (a, opt_b),
@@ -244,6 +275,9 @@ impl Expression {
         }
         full::Thunk::Binary {
           name, args: [a, b], ..
+        }
+        | full::Thunk::BinaryValue {
+          name, args: [a, b], ..
         } => Expression::Call {
           name: name.lexeme().to_owned(),
           arguments: vec![Expression::new(a), Expression::new(b)],
@@ -270,6 +304,27 @@ impl Expression {
           name: name.lexeme().to_owned(),
           arguments: vec![Expression::new(a), Expression::new(b), Expression::new(c)],
         },
+        full::Thunk::Cached {
+          name,
+          contents,
+          files,
+          ..
+        } => {
+          let mut arguments = vec![Expression::Backtick {
+            command: contents.clone(),
+          }];
+          for file in files {
+            arguments.push(Expression::new(file));
+          }
+          Expression::Call {
+            name: name.lexeme().to_owned(),
+            arguments,
+          }
+        }
+        full::Thunk::User { name, args } => Expression::Call {
+          name: name.lexeme().to_owned(),
+          arguments: args.iter().map(Expression::new).collect(),
+        },
       },
       Concatenation { lhs, rhs } => Expression::Concatenation {
         lhs: Box::new(Expression::new(lhs)),
@@ -280,16 +335,12 @@ impl Expression {
         rhs: Box::new(Expression::new(rhs)),
       },
       Conditional {
-        lhs,
-        operator,
+        condition,
         otherwise,
-        rhs,
         then,
       } => Expression::Conditional {
-        lhs: Box::new(Expression::new(lhs)),
-        operator: ConditionalOperator::new(*operator),
+        condition: Condition::new(condition),
         otherwise: Box::new(Expression::new(otherwise)),
-        rhs: Box::new(Expression::new(rhs)),
         then: Box::new(Expression::new(then)),
       },
       StringLiteral { string_literal } => Expression::String {
@@ -303,11 +354,65 @@ impl Expression {
   }
 }
 
+#[derive(Eq, PartialEq, Hash, Ord, PartialOrd, Debug, Clone)]
+pub enum Condition {
+  And {
+    lhs: Box<Condition>,
+    rhs: Box<Condition>,
+  },
+  Comparison {
+    lhs: Box<Expression>,
+    rhs: Box<Expression>,
+    operator: ConditionalOperator,
+  },
+  In {
+    lhs: Box<Expression>,
+    list: Vec<Expression>,
+  },
+  Not {
+    condition: Box<Condition>,
+  },
+  Or {
+    lhs: Box<Condition>,
+    rhs: Box<Condition>,
+  },
+}
+
+impl Condition {
+  fn new(condition: &full::Condition) -> Condition {
+    use full::Condition::*;
+    match condition {
+      And { lhs, rhs } => Condition::And {
+        lhs: Box::new(Condition::new(lhs)),
+        rhs: Box::new(Condition::new(rhs)),
+      },
+      Comparison { lhs, rhs, operator } => Condition::Comparison {
+        lhs: Box::new(Expression::new(lhs)),
+        rhs: Box::new(Expression::new(rhs)),
+        operator: ConditionalOperator::new(*operator),
+      },
+      Group { contents } => Condition::new(contents),
+      In { lhs, list } => Condition::In {
+        lhs: Box::new(Expression::new(lhs)),
+        list: list.iter().map(Expression::new).collect(),
+      },
+      Not { condition } => Condition::Not {
+        condition: Box::new(Condition::new(condition)),
+      },
+      Or { lhs, rhs } => Condition::Or {
+        lhs: Box::new(Condition::new(lhs)),
+        rhs: Box::new(Condition::new(rhs)),
+      },
+    }
+  }
+}
+
 #[derive(Eq, PartialEq, Hash, Ord, PartialOrd, Debug, Clone)]
 pub enum ConditionalOperator {
   Equality,
   Inequality,
   RegexMatch,
+  RegexMismatch,
 }
 
 impl ConditionalOperator {
@@ -316,6 +421,7 @@ impl ConditionalOperator {
       full::ConditionalOperator::Equality => Self::Equality,
       full::ConditionalOperator::Inequality => Self::Inequality,
       full::ConditionalOperator::RegexMatch => Self::RegexMatch,
+      full::ConditionalOperator::RegexMismatch => Self::RegexMismatch,
     }
   }
 }
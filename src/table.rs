@@ -21,6 +21,10 @@ impl<'key, V: Keyed<'key>> Table<'key, V> {
     self.map.len()
   }
 
+  pub(crate) fn is_empty(&self) -> bool {
+    self.map.is_empty()
+  }
+
   pub(crate) fn get(&self, key: &str) -> Option<&V> {
     self.map.get(key)
   }
@@ -1,8 +1,8 @@
 use {super::*, crate::compiler::Compiler, pretty_assertions::assert_eq};
 
 pub(crate) fn compile(text: &str) -> Justfile {
-  match Compiler::compile(text) {
-    Ok((_, justfile)) => justfile,
+  match Compiler::test_compile(text) {
+    Ok(justfile) => justfile,
     Err(error) => panic!("Expected successful compilation but got error:\n {error}"),
   }
 }
@@ -60,17 +60,24 @@ pub(crate) fn analysis_error(
   length: usize,
   kind: CompileErrorKind,
 ) {
-  let tokens = Lexer::lex(src).expect("Lexing failed in parse test...");
+  let tokens = Lexer::test_lex(src).expect("Lexing failed in parse test...");
 
   let ast = Parser::parse(&tokens).expect("Parsing failed in analysis test...");
 
-  match Analyzer::analyze(&ast) {
+  let root = PathBuf::from("justfile");
+  let mut asts = HashMap::new();
+  asts.insert(root.clone(), ast);
+  let mut paths = HashMap::new();
+  paths.insert(root.clone(), root.clone());
+
+  match Analyzer::analyze(&paths, &asts, &root, None, "") {
     Ok(_) => panic!("Analysis unexpectedly succeeded"),
     Err(have) => {
       let want = CompileError {
         token: Token {
           kind: have.token.kind,
           src,
+          path: "justfile".as_ref(),
           offset,
           line,
           column,
@@ -97,9 +104,8 @@ macro_rules! run_error {
       let search = $crate::testing::search(&config);
 
       if let Subcommand::Run{ overrides, arguments } = &config.subcommand {
-        match $crate::compiler::Compiler::compile(&$crate::unindent::unindent($src))
+        match $crate::compiler::Compiler::test_compile(&$crate::unindent::unindent($src))
           .expect("Expected successful compilation")
-          .1
           .run(
             &config,
             &search,
@@ -20,6 +20,12 @@ pub(crate) enum Thunk<'src> {
     function: fn(&FunctionContext, &str, Option<&str>) -> Result<String, String>,
     args: (Box<Expression<'src>>, Box<Option<Expression<'src>>>),
   },
+  UnaryValue {
+    name: Name<'src>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    function: fn(&FunctionContext, Value) -> Result<Value, String>,
+    arg: Box<Expression<'src>>,
+  },
   Binary {
     name: Name<'src>,
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
@@ -32,12 +38,31 @@ pub(crate) enum Thunk<'src> {
     function: fn(&FunctionContext, &str, &str, &[String]) -> Result<String, String>,
     args: ([Box<Expression<'src>>; 2], Vec<Expression<'src>>),
   },
+  BinaryValue {
+    name: Name<'src>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    function: fn(&FunctionContext, Value, Value) -> Result<Value, String>,
+    args: [Box<Expression<'src>>; 2],
+  },
   Ternary {
     name: Name<'src>,
     #[derivative(Debug = "ignore", PartialEq = "ignore")]
     function: fn(&FunctionContext, &str, &str, &str) -> Result<String, String>,
     args: [Box<Expression<'src>>; 3],
   },
+  /// `cached(`contents`, files…)`, a backtick whose output is cached until
+  /// its contents or the contents of `files` change
+  Cached {
+    name: Name<'src>,
+    contents: String,
+    token: Token<'src>,
+    files: Vec<Expression<'src>>,
+  },
+  /// A call to a user-defined function, which is checked by the analyzer
+  User {
+    name: Name<'src>,
+    args: Vec<Expression<'src>>,
+  },
 }
 
 impl<'src> Thunk<'src> {
@@ -46,9 +71,13 @@ impl<'src> Thunk<'src> {
       Self::Nullary { name, .. }
       | Self::Unary { name, .. }
       | Self::UnaryOpt { name, .. }
+      | Self::UnaryValue { name, .. }
       | Self::Binary { name, .. }
       | Self::BinaryPlus { name, .. }
-      | Self::Ternary { name, .. } => name,
+      | Self::BinaryValue { name, .. }
+      | Self::Ternary { name, .. }
+      | Self::Cached { name, .. }
+      | Self::User { name, .. } => name,
     }
   }
 
@@ -56,65 +85,83 @@ impl<'src> Thunk<'src> {
     name: Name<'src>,
     mut arguments: Vec<Expression<'src>>,
   ) -> CompileResult<'src, Thunk<'src>> {
-    function::get(name.lexeme()).map_or(
-      Err(name.error(CompileErrorKind::UnknownFunction {
-        function: name.lexeme(),
-      })),
-      |function| match (function, arguments.len()) {
-        (Function::Nullary(function), 0) => Ok(Thunk::Nullary { function, name }),
-        (Function::Unary(function), 1) => Ok(Thunk::Unary {
+    // Calls to functions that aren't built in are resolved by the analyzer,
+    // since user-defined functions may be declared after they are called
+    let Some(function) = function::get(name.lexeme()) else {
+      return Ok(Thunk::User {
+        name,
+        args: arguments,
+      });
+    };
+
+    match (function, arguments.len()) {
+      (Function::Nullary(function), 0) => Ok(Thunk::Nullary { function, name }),
+      (Function::Unary(function), 1) => Ok(Thunk::Unary {
+        function,
+        arg: Box::new(arguments.pop().unwrap()),
+        name,
+      }),
+      (Function::UnaryOpt(function), 1..=2) => {
+        let a = Box::new(arguments.remove(0));
+        let b = match arguments.pop() {
+          Some(value) => Box::new(Some(value)),
+          None => Box::new(None),
+        };
+        Ok(Thunk::UnaryOpt {
           function,
-          arg: Box::new(arguments.pop().unwrap()),
+          args: (a, b),
           name,
-        }),
-        (Function::UnaryOpt(function), 1..=2) => {
-          let a = Box::new(arguments.remove(0));
-          let b = match arguments.pop() {
-            Some(value) => Box::new(Some(value)),
-            None => Box::new(None),
-          };
-          Ok(Thunk::UnaryOpt {
-            function,
-            args: (a, b),
-            name,
-          })
-        }
-        (Function::Binary(function), 2) => {
-          let b = Box::new(arguments.pop().unwrap());
-          let a = Box::new(arguments.pop().unwrap());
-          Ok(Thunk::Binary {
-            function,
-            args: [a, b],
-            name,
-          })
-        }
-        (Function::BinaryPlus(function), 2..=usize::MAX) => {
-          let rest = arguments.drain(2..).collect();
-          let b = Box::new(arguments.pop().unwrap());
-          let a = Box::new(arguments.pop().unwrap());
-          Ok(Thunk::BinaryPlus {
-            function,
-            args: ([a, b], rest),
-            name,
-          })
-        }
-        (Function::Ternary(function), 3) => {
-          let c = Box::new(arguments.pop().unwrap());
-          let b = Box::new(arguments.pop().unwrap());
-          let a = Box::new(arguments.pop().unwrap());
-          Ok(Thunk::Ternary {
-            function,
-            args: [a, b, c],
-            name,
-          })
-        }
-        (function, _) => Err(name.error(CompileErrorKind::FunctionArgumentCountMismatch {
-          function: name.lexeme(),
-          found: arguments.len(),
-          expected: function.argc(),
-        })),
-      },
-    )
+        })
+      }
+      (Function::UnaryValue(function), 1) => Ok(Thunk::UnaryValue {
+        function,
+        arg: Box::new(arguments.pop().unwrap()),
+        name,
+      }),
+      (Function::Binary(function), 2) => {
+        let b = Box::new(arguments.pop().unwrap());
+        let a = Box::new(arguments.pop().unwrap());
+        Ok(Thunk::Binary {
+          function,
+          args: [a, b],
+          name,
+        })
+      }
+      (Function::BinaryPlus(function), 2..=usize::MAX) => {
+        let rest = arguments.drain(2..).collect();
+        let b = Box::new(arguments.pop().unwrap());
+        let a = Box::new(arguments.pop().unwrap());
+        Ok(Thunk::BinaryPlus {
+          function,
+          args: ([a, b], rest),
+          name,
+        })
+      }
+      (Function::BinaryValue(function), 2) => {
+        let b = Box::new(arguments.pop().unwrap());
+        let a = Box::new(arguments.pop().unwrap());
+        Ok(Thunk::BinaryValue {
+          function,
+          args: [a, b],
+          name,
+        })
+      }
+      (Function::Ternary(function), 3) => {
+        let c = Box::new(arguments.pop().unwrap());
+        let b = Box::new(arguments.pop().unwrap());
+        let a = Box::new(arguments.pop().unwrap());
+        Ok(Thunk::Ternary {
+          function,
+          args: [a, b, c],
+          name,
+        })
+      }
+      (function, _) => Err(name.error(CompileErrorKind::FunctionArgumentCountMismatch {
+        function: name.lexeme(),
+        found: arguments.len(),
+        expected: function.argc(),
+      })),
+    }
   }
 }
 
@@ -123,7 +170,9 @@ impl Display for Thunk<'_> {
     use Thunk::*;
     match self {
       Nullary { name, .. } => write!(f, "{}()", name.lexeme()),
-      Unary { name, arg, .. } => write!(f, "{}({arg})", name.lexeme()),
+      Unary { name, arg, .. } | UnaryValue { name, arg, .. } => {
+        write!(f, "{}({arg})", name.lexeme())
+      }
       UnaryOpt {
         name, args: (a, b), ..
       } => {
@@ -135,6 +184,9 @@ impl Display for Thunk<'_> {
       }
       Binary {
         name, args: [a, b], ..
+      }
+      | BinaryValue {
+        name, args: [a, b], ..
       } => write!(f, "{}({a}, {b})", name.lexeme()),
       BinaryPlus {
         name,
@@ -152,6 +204,25 @@ impl Display for Thunk<'_> {
         args: [a, b, c],
         ..
       } => write!(f, "{}({a}, {b}, {c})", name.lexeme()),
+      Cached {
+        name, token, files, ..
+      } => {
+        write!(f, "{}({}", name.lexeme(), token.lexeme())?;
+        for file in files {
+          write!(f, ", {file}")?;
+        }
+        write!(f, ")")
+      }
+      User { name, args } => {
+        write!(f, "{}(", name.lexeme())?;
+        for (i, arg) in args.iter().enumerate() {
+          if i > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{arg}")?;
+        }
+        write!(f, ")")
+      }
     }
   }
 }
@@ -166,7 +237,7 @@ impl<'src> Serialize for Thunk<'src> {
     seq.serialize_element(self.name())?;
     match self {
       Self::Nullary { .. } => {}
-      Self::Unary { arg, .. } => seq.serialize_element(&arg)?,
+      Self::Unary { arg, .. } | Self::UnaryValue { arg, .. } => seq.serialize_element(&arg)?,
       Self::UnaryOpt {
         args: (a, opt_b), ..
       } => {
@@ -175,7 +246,7 @@ impl<'src> Serialize for Thunk<'src> {
           seq.serialize_element(b)?;
         }
       }
-      Self::Binary { args, .. } => {
+      Self::Binary { args, .. } | Self::BinaryValue { args, .. } => {
         for arg in args {
           seq.serialize_element(arg)?;
         }
@@ -190,6 +261,19 @@ impl<'src> Serialize for Thunk<'src> {
           seq.serialize_element(arg)?;
         }
       }
+      Self::Cached {
+        contents, files, ..
+      } => {
+        seq.serialize_element(&("evaluate", contents))?;
+        for file in files {
+          seq.serialize_element(file)?;
+        }
+      }
+      Self::User { args, .. } => {
+        for arg in args {
+          seq.serialize_element(arg)?;
+        }
+      }
     }
     seq.end()
   }
@@ -0,0 +1,184 @@
+use {
+  super::*,
+  std::{
+    thread::ThreadId,
+    time::{Duration, Instant},
+  },
+};
+
+/// Wall times of recipes and linewise commands, recorded with `--timings`
+/// and reported after all recipes have run.
+pub(crate) struct Timings {
+  enabled: bool,
+  spans: Mutex<Spans>,
+  start: Instant,
+}
+
+#[derive(Default)]
+struct Spans {
+  spans: Vec<Span>,
+  threads: Vec<ThreadId>,
+}
+
+struct Span {
+  command: Option<String>,
+  duration: Duration,
+  line_number: Option<usize>,
+  recipe: String,
+  start: Duration,
+  thread: usize,
+}
+
+impl Timings {
+  pub(crate) fn new(config: &Config) -> Self {
+    Self {
+      enabled: config.timings,
+      spans: Mutex::default(),
+      start: Instant::now(),
+    }
+  }
+
+  /// Record that `recipe` ran from `start` until now
+  pub(crate) fn recipe(&self, recipe: &str, start: Instant) {
+    self.record(recipe, None, None, start);
+  }
+
+  /// Record that `command`, on `line_number` of `recipe`, ran from `start`
+  /// until now. Shebang recipes have no line number.
+  pub(crate) fn command(
+    &self,
+    recipe: &str,
+    line_number: Option<usize>,
+    command: &str,
+    start: Instant,
+  ) {
+    self.record(recipe, line_number, Some(command), start);
+  }
+
+  fn record(
+    &self,
+    recipe: &str,
+    line_number: Option<usize>,
+    command: Option<&str>,
+    start: Instant,
+  ) {
+    if !self.enabled {
+      return;
+    }
+
+    let duration = start.elapsed();
+
+    let mut spans = self.spans.lock().unwrap();
+
+    let id = thread::current().id();
+
+    let thread = if let Some(thread) = spans.threads.iter().position(|thread| *thread == id) {
+      thread
+    } else {
+      spans.threads.push(id);
+      spans.threads.len() - 1
+    };
+
+    spans.spans.push(Span {
+      command: command.map(str::to_owned),
+      duration,
+      line_number,
+      recipe: recipe.into(),
+      start: start.saturating_duration_since(self.start),
+      thread,
+    });
+  }
+
+  /// Print a table of recorded spans to stderr, slowest first, and write a
+  /// Chrome trace of them to `--timings-trace`, if given
+  pub(crate) fn report(&self, config: &Config) -> RunResult<'static, ()> {
+    if !self.enabled {
+      return Ok(());
+    }
+
+    let mut spans = self.spans.lock().unwrap();
+
+    spans
+      .spans
+      .sort_by(|a, b| b.duration.cmp(&a.duration).then(a.start.cmp(&b.start)));
+
+    let recipe_width = spans
+      .spans
+      .iter()
+      .map(|span| UnicodeWidthStr::width(span.recipe.as_str()))
+      .fold("Recipe".len(), cmp::max);
+
+    let color = config.color.stderr();
+
+    eprintln!(
+      "{}",
+      color.banner().paint(&format!(
+        "{:>10}  {:recipe_width$}  {:>4}  Command",
+        "Duration", "Recipe", "Line"
+      ))
+    );
+
+    for span in &spans.spans {
+      let line_number = span
+        .line_number
+        .map(|line_number| line_number.to_string())
+        .unwrap_or_default();
+
+      let command = span
+        .command
+        .as_deref()
+        .and_then(|command| command.lines().next())
+        .unwrap_or_default();
+
+      let row = format!(
+        "{:>9.3}s  {:recipe_width$}  {line_number:>4}  {command}",
+        span.duration.as_secs_f64(),
+        span.recipe,
+      );
+
+      eprintln!("{}", row.trim_end());
+    }
+
+    if let Some(path) = &config.timings_trace {
+      let path = config.invocation_directory.join(path);
+
+      let events = spans
+        .spans
+        .iter()
+        .map(|span| {
+          let (name, category) = match &span.command {
+            Some(command) => (command.lines().next().unwrap_or_default(), "command"),
+            None => (span.recipe.as_str(), "recipe"),
+          };
+
+          serde_json::json!({
+            "name": name,
+            "cat": category,
+            "ph": "X",
+            "ts": span.start.as_secs_f64() * 1e6,
+            "dur": span.duration.as_secs_f64() * 1e6,
+            "pid": 1,
+            "tid": span.thread,
+            "args": {
+              "recipe": span.recipe,
+              "line_number": span.line_number,
+              "command": span.command,
+            },
+          })
+        })
+        .collect::<Vec<serde_json::Value>>();
+
+      let trace = serde_json::json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ms",
+      });
+
+      fs::write(&path, format!("{trace}\n")).map_err(|io_error| Error::TimingsTrace {
+        path: path.clone(),
+        io_error,
+      })?;
+    }
+
+    Ok(())
+  }
+}
@@ -6,6 +6,7 @@ pub(crate) struct Token<'src> {
   pub(crate) length: usize,
   pub(crate) line: usize,
   pub(crate) column: usize,
+  pub(crate) path: &'src Path,
   pub(crate) src: &'src str,
   pub(crate) kind: TokenKind,
 }
@@ -52,6 +53,14 @@ impl<'src> ColorDisplay for Token<'src> {
           i += c.len_utf8();
         }
         let line_number_width = line_number.to_string().len();
+        writeln!(
+          f,
+          "{0:1$}——▶ {2}:{line_number}:{3}",
+          "",
+          line_number_width,
+          self.path.display(),
+          self.column.ordinal(),
+        )?;
         writeln!(f, "{0:1$} |", "", line_number_width)?;
         writeln!(f, "{line_number} | {space_line}")?;
         write!(f, "{0:1$} |", "", line_number_width)?;
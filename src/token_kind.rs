@@ -6,7 +6,11 @@ pub(crate) enum TokenKind {
   Asterisk,
   At,
   Backtick,
+  Bang,
   BangEquals,
+  BangTilde,
+  Bar,
+  BarBar,
   BraceL,
   BraceR,
   BracketL,
@@ -48,7 +52,11 @@ impl Display for TokenKind {
         Asterisk => "'*'",
         At => "'@'",
         Backtick => "backtick",
+        Bang => "'!'",
         BangEquals => "'!='",
+        BangTilde => "'!~'",
+        Bar => "'|'",
+        BarBar => "'||'",
         BraceL => "'{'",
         BraceR => "'}'",
         BracketL => "'['",
@@ -5,7 +5,7 @@ pub(crate) type UnresolvedRecipe<'src> = Recipe<'src, UnresolvedDependency<'src>
 impl<'src> UnresolvedRecipe<'src> {
   pub(crate) fn resolve(
     self,
-    resolved: Vec<Rc<Recipe<'src>>>,
+    resolved: Vec<Arc<Recipe<'src>>>,
   ) -> CompileResult<'src, Recipe<'src>> {
     assert_eq!(
       self.dependencies.len(),
@@ -32,6 +32,19 @@ impl<'src> UnresolvedRecipe<'src> {
             }),
         );
       }
+
+      if let Some(parameter) = resolved.parameters.iter().find(|parameter| {
+        parameter.is_option() && parameter.default.is_none() && parameter.value.is_none()
+      }) {
+        return Err(
+          unresolved
+            .recipe
+            .error(CompileErrorKind::DependencyRequiresOption {
+              dependency: unresolved.recipe.lexeme(),
+              option: parameter.option_names().join("|"),
+            }),
+        );
+      }
     }
 
     let dependencies = self
@@ -48,6 +61,7 @@ impl<'src> UnresolvedRecipe<'src> {
       body: self.body,
       doc: self.doc,
       name: self.name,
+      namepath: self.namepath,
       parameters: self.parameters,
       private: self.private,
       quiet: self.quiet,
@@ -0,0 +1,48 @@
+use super::*;
+
+/// A user-defined function, as in `fn name(a, b) := body`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct UserFunction<'src> {
+  pub(crate) body: Expression<'src>,
+  pub(crate) name: Name<'src>,
+  pub(crate) parameters: Vec<Name<'src>>,
+}
+
+impl<'src> UserFunction<'src> {
+  pub(crate) fn has_parameter(&self, name: &str) -> bool {
+    self
+      .parameters
+      .iter()
+      .any(|parameter| parameter.lexeme() == name)
+  }
+
+  /// Variables referenced by the body which are not parameters, and so must
+  /// be assignments
+  pub(crate) fn globals(&self) -> impl Iterator<Item = Token<'src>> + '_ {
+    self
+      .body
+      .variables()
+      .filter(|variable| !self.has_parameter(variable.lexeme()))
+  }
+}
+
+impl<'src> Keyed<'src> for UserFunction<'src> {
+  fn key(&self) -> &'src str {
+    self.name.lexeme()
+  }
+}
+
+impl Display for UserFunction<'_> {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{} {}(", Keyword::Fn, self.name)?;
+
+    for (i, parameter) in self.parameters.iter().enumerate() {
+      if i > 0 {
+        write!(f, ", ")?;
+      }
+      write!(f, "{parameter}")?;
+    }
+
+    write!(f, ") := {}", self.body)
+  }
+}
@@ -0,0 +1,56 @@
+use super::*;
+
+/// The value of an expression or binding, either a string or a list of
+/// strings. Lists are produced by variadic parameters and list functions like
+/// `split`, and are joined with spaces when used where a string is expected.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+  List(Vec<String>),
+  String(String),
+}
+
+impl Value {
+  /// Convert into a list. Strings are treated as single-element lists.
+  pub(crate) fn into_list(self) -> Vec<String> {
+    match self {
+      Self::List(list) => list,
+      Self::String(string) => vec![string],
+    }
+  }
+
+  /// Convert into a string, joining list elements with spaces
+  pub(crate) fn into_string(self) -> String {
+    match self {
+      Self::List(list) => list.join(" "),
+      Self::String(string) => string,
+    }
+  }
+
+  /// Expand into words quoted for `shell`, one for each element of a list,
+  /// or a single word for a string
+  pub(crate) fn quoted(&self, shell: ShellKind) -> String {
+    match self {
+      Self::List(list) => list
+        .iter()
+        .map(|word| shell.quote(word))
+        .collect::<Vec<String>>()
+        .join(" "),
+      Self::String(string) => shell.quote(string),
+    }
+  }
+}
+
+impl Display for Value {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::List(list) => write!(f, "{}", list.join(" ")),
+      Self::String(string) => write!(f, "{string}"),
+    }
+  }
+}
+
+impl From<String> for Value {
+  fn from(string: String) -> Self {
+    Self::String(string)
+  }
+}
@@ -8,73 +8,115 @@ impl<'expression, 'src> Variables<'expression, 'src> {
   pub(crate) fn new(root: &'expression Expression<'src>) -> Variables<'expression, 'src> {
     Variables { stack: vec![root] }
   }
-}
 
-impl<'expression, 'src> Iterator for Variables<'expression, 'src> {
-  type Item = Token<'src>;
+  /// Push the expressions in `condition` onto the stack in reverse order, so
+  /// that they are popped in source order
+  fn push_condition(&mut self, condition: &'expression Condition<'src>) {
+    match condition {
+      Condition::And { lhs, rhs } | Condition::Or { lhs, rhs } => {
+        self.push_condition(rhs);
+        self.push_condition(lhs);
+      }
+      Condition::Comparison { lhs, rhs, .. } => {
+        self.stack.push(rhs);
+        self.stack.push(lhs);
+      }
+      Condition::Group { contents } => self.push_condition(contents),
+      Condition::In { lhs, list } => {
+        for expression in list.iter().rev() {
+          self.stack.push(expression);
+        }
+        self.stack.push(lhs);
+      }
+      Condition::Not { condition } => self.push_condition(condition),
+    }
+  }
 
-  fn next(&mut self) -> Option<Token<'src>> {
-    loop {
-      match self.stack.pop()? {
-        Expression::StringLiteral { .. } | Expression::Backtick { .. } => {}
-        Expression::Call { thunk } => match thunk {
-          Thunk::Nullary { .. } => {}
-          Thunk::Unary { arg, .. } => self.stack.push(arg),
-          Thunk::UnaryOpt {
-            args: (a, opt_b), ..
-          } => {
-            self.stack.push(a);
-            if let Some(b) = opt_b.as_ref() {
-              self.stack.push(b);
-            }
-          }
-          Thunk::Binary { args, .. } => {
-            for arg in args.iter().rev() {
-              self.stack.push(arg);
-            }
-          }
-          Thunk::BinaryPlus {
-            args: ([a, b], rest),
-            ..
-          } => {
-            let first: &[&Expression] = &[a, b];
-            for arg in first.iter().copied().chain(rest).rev() {
-              self.stack.push(arg);
-            }
+  /// Pop the next subexpression, in source order, pushing its children
+  pub(crate) fn next_expression(&mut self) -> Option<&'expression Expression<'src>> {
+    let expression = self.stack.pop()?;
+
+    match expression {
+      Expression::Call { thunk } => match thunk {
+        Thunk::Nullary { .. } => {}
+        Thunk::Unary { arg, .. } | Thunk::UnaryValue { arg, .. } => self.stack.push(arg),
+        Thunk::UnaryOpt {
+          args: (a, opt_b), ..
+        } => {
+          self.stack.push(a);
+          if let Some(b) = opt_b.as_ref() {
+            self.stack.push(b);
           }
-          Thunk::Ternary { args, .. } => {
-            for arg in args.iter().rev() {
-              self.stack.push(arg);
-            }
+        }
+        Thunk::Binary { args, .. } | Thunk::BinaryValue { args, .. } => {
+          for arg in args.iter().rev() {
+            self.stack.push(arg);
           }
-        },
-        Expression::Conditional {
-          lhs,
-          rhs,
-          then,
-          otherwise,
+        }
+        Thunk::BinaryPlus {
+          args: ([a, b], rest),
           ..
         } => {
-          self.stack.push(otherwise);
-          self.stack.push(then);
-          self.stack.push(rhs);
-          self.stack.push(lhs);
+          let first: &[&Expression] = &[a, b];
+          for arg in first.iter().copied().chain(rest).rev() {
+            self.stack.push(arg);
+          }
         }
-        Expression::Variable { name, .. } => return Some(name.token()),
-        Expression::Concatenation { lhs, rhs } => {
-          self.stack.push(rhs);
-          self.stack.push(lhs);
+        Thunk::Ternary { args, .. } => {
+          for arg in args.iter().rev() {
+            self.stack.push(arg);
+          }
         }
-        Expression::Join { lhs, rhs } => {
-          self.stack.push(rhs);
-          if let Some(lhs) = lhs {
-            self.stack.push(lhs);
+        Thunk::Cached { files, .. } => {
+          for file in files.iter().rev() {
+            self.stack.push(file);
           }
         }
-        Expression::Group { contents } => {
-          self.stack.push(contents);
+        Thunk::User { args, .. } => {
+          for arg in args.iter().rev() {
+            self.stack.push(arg);
+          }
+        }
+      },
+      Expression::Conditional {
+        condition,
+        then,
+        otherwise,
+      } => {
+        self.stack.push(otherwise);
+        self.stack.push(then);
+        self.push_condition(condition);
+      }
+      Expression::StringLiteral { .. }
+      | Expression::Backtick { .. }
+      | Expression::Variable { .. } => {}
+      Expression::Concatenation { lhs, rhs } => {
+        self.stack.push(rhs);
+        self.stack.push(lhs);
+      }
+      Expression::Join { lhs, rhs } => {
+        self.stack.push(rhs);
+        if let Some(lhs) = lhs {
+          self.stack.push(lhs);
         }
       }
+      Expression::Group { contents } => {
+        self.stack.push(contents);
+      }
+    }
+
+    Some(expression)
+  }
+}
+
+impl<'expression, 'src> Iterator for Variables<'expression, 'src> {
+  type Item = Token<'src>;
+
+  fn next(&mut self) -> Option<Token<'src>> {
+    loop {
+      if let Expression::Variable { name } = self.next_expression()? {
+        return Some(name.token());
+      }
     }
   }
 }
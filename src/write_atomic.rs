@@ -0,0 +1,18 @@
+use super::*;
+
+/// Write `contents` to `path` by writing them to a temporary file in the same
+/// directory and renaming it into place, so that concurrent readers never see
+/// a partially written file
+pub(crate) fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+  let parent = path.parent().unwrap();
+
+  fs::create_dir_all(parent)?;
+
+  let mut file = tempfile::NamedTempFile::new_in(parent)?;
+
+  file.write_all(contents)?;
+
+  file.persist(path)?;
+
+  Ok(())
+}
@@ -8,6 +8,7 @@ test! {
   stdout: "",
   stderr: "
     error: Expected keyword `true` or `false` but found identifier `fals`
+     ——▶ justfile:1:15
       |
     1 | set export := fals
       |               ^^^^
@@ -23,6 +24,7 @@ test! {
   stdout: "",
   stderr: "
     error: Expected keyword `true` or `false` but found `end of line`
+     ——▶ justfile:1:15
       |
     1 | set export := 
       |               ^
@@ -33,6 +33,7 @@ fn duplicate_attributes_are_disallowed() {
     .stderr(
       "
       error: Recipe attribute `no-exit-message` first used on line 1 is duplicated on line 2
+       ——▶ justfile:2:2
         |
       2 | [no-exit-message]
         |  ^^^^^^^^^^^^^^^
@@ -71,7 +72,8 @@ fn multiple_attributes_one_line_error_message() {
     )
     .stderr(
       "
-        error: Expected ']' or ',', but found identifier
+        error: Expected ']', ',', or '(', but found identifier
+         ——▶ justfile:1:17
           |
         1 | [macos, windows linux]
           |                 ^^^^^
@@ -95,6 +97,7 @@ fn multiple_attributes_one_line_duplicate_check() {
     .stderr(
       "
       error: Recipe attribute `linux` first used on line 1 is duplicated on line 2
+       ——▶ justfile:2:2
         |
       2 | [linux]
         |  ^^^^^
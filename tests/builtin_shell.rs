@@ -0,0 +1,359 @@
+use super::*;
+
+#[test]
+fn simple_commands() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo:
+        echo hello 'a  b' \"c  d\"
+      ",
+    )
+    .stdout("hello a  b c  d\n")
+    .stderr("echo hello 'a  b' \"c  d\"\n")
+    .run();
+}
+
+#[test]
+fn shell_flag() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      foo:
+        @echo hello
+      ",
+    )
+    .args(["--shell", "builtin"])
+    .stdout("hello\n")
+    .run();
+}
+
+#[test]
+fn and_and_or() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo:
+        @false && echo and || echo or
+        @true && echo and || echo or
+      ",
+    )
+    .stdout("or\nand\n")
+    .run();
+}
+
+#[test]
+fn failing_command() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo:
+        @false
+      ",
+    )
+    .stderr("error: Recipe `foo` failed on line 4 with exit code 1\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn command_not_found() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo:
+        @-nonexistent-command
+        @nonexistent-command || echo fallback
+      ",
+    )
+    .stdout("fallback\n")
+    .stderr_regex("nonexistent-command: .*\nnonexistent-command: .*\n")
+    .run();
+}
+
+#[test]
+fn environment_variables() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      export FOO := 'foo'
+
+      bar:
+        @echo $FOO ${FOO}bar \"$FOO\" '$FOO'
+        @FOO=baz printenv FOO
+      ",
+    )
+    .stdout("foo foobar foo $FOO\nbaz\n")
+    .run();
+}
+
+#[test]
+fn empty_unquoted_expansions_are_dropped() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      export EMPTY := ''
+
+      foo:
+        @printf '[%s]\\n' $EMPTY x \"$EMPTY\"
+      ",
+    )
+    .stdout("[x]\n[]\n")
+    .run();
+}
+
+#[test]
+fn positional_arguments() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+      set positional-arguments
+
+      foo *args:
+        @printf '%s\\n' $0 $# \"$1\" \"$@\"
+      ",
+    )
+    .args(["foo", "a b", "c"])
+    .stdout("foo\n2\na b\na b\nc\n")
+    .run();
+}
+
+#[test]
+fn positional_arguments_require_setting() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo *args:
+        @echo $1
+      ",
+    )
+    .args(["foo", "a"])
+    .stderr(
+      "error: Recipe `foo` line 4 could not be run by the builtin shell: `$1` is not supported by the builtin shell without `set positional-arguments`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn unset_variable() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo:
+        @echo $UNSET_VARIABLE
+      ",
+    )
+    .stderr(
+      "error: Recipe `foo` line 4 could not be run by the builtin shell: `UNSET_VARIABLE` is not set\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn redirections() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo:
+        @echo foo > out.txt
+        @echo bar >> out.txt
+        @cat < out.txt
+        @echo baz >&2
+        @cat out.txt missing.txt 2> /dev/null || echo failed
+      ",
+    )
+    .stdout("foo\nbar\nfoo\nbar\nfailed\n")
+    .stderr("baz\n")
+    .run();
+}
+
+#[test]
+fn globs_are_errors() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo:
+        @echo '*' \\? '~'
+        @echo *.txt
+      ",
+    )
+    .stdout("* ? ~\n")
+    .stderr(
+      "error: Recipe `foo` line 5 could not be run by the builtin shell: `*` is not supported by the builtin shell\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn tilde_is_an_error() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo:
+        @echo ~
+      ",
+    )
+    .stderr(
+      "error: Recipe `foo` line 4 could not be run by the builtin shell: `~` is not supported by the builtin shell\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn comments() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo:
+        # comment
+        @echo foo # comment
+      ",
+    )
+    .stdout("foo\n")
+    .stderr("# comment\n")
+    .run();
+}
+
+#[test]
+fn unsupported_syntax() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo:
+        echo foo | cat
+      ",
+    )
+    .stderr(
+      "
+      echo foo | cat
+      error: Recipe `foo` line 4 could not be run by the builtin shell: `|` is not supported by the builtin shell
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn backticks() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      bar := `echo $FOO bar`
+
+      baz:
+        @echo {{ bar }}
+      ",
+    )
+    .env("FOO", "foo")
+    .stdout("foo bar\n")
+    .run();
+}
+
+#[test]
+fn backtick_errors() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      foo := `echo foo; echo bar`
+
+      bar:
+      ",
+    )
+    .stderr(
+      "
+      error: Backtick could not be run by the builtin shell: `;` is not supported by the builtin shell
+       ——▶ justfile:3:8
+        |
+      3 | foo := `echo foo; echo bar`
+        |        ^^^^^^^^^^^^^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn working_directory() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      [no-cd]
+      foo:
+        @cat file.txt
+      ",
+    )
+    .write("sub/file.txt", "sub")
+    .args(["--justfile", "../justfile", "foo"])
+    .current_dir("sub")
+    .stdout("sub")
+    .run();
+}
+
+#[test]
+fn shell_command_uses_default_shell() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+      ",
+    )
+    .args(["--shell-command", "--command", "echo $0"])
+    .stdout("sh\n")
+    .run();
+}
@@ -0,0 +1,274 @@
+use super::*;
+
+const JUSTFILE: &str = "
+  foo := cached(`echo run >> runs.txt; echo foo`)
+
+  bar:
+    @echo {{ foo }}
+";
+
+const KEYED_JUSTFILE: &str = "
+  foo := cached(`echo run >> runs.txt; cat key.txt`, 'key.txt')
+
+  bar:
+    @echo {{ foo }}
+";
+
+fn runs(tempdir: &TempDir) -> usize {
+  fs::read_to_string(tempdir.path().join("runs.txt"))
+    .unwrap()
+    .lines()
+    .count()
+}
+
+#[test]
+fn output_is_cached() {
+  let tempdir = Test::new().justfile(JUSTFILE).stdout("foo\n").run().tempdir;
+
+  assert_eq!(runs(&tempdir), 1);
+
+  let tempdir = Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .stdout("foo\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(runs(&tempdir), 1);
+}
+
+#[test]
+fn cache_is_stored_next_to_justfile() {
+  let tempdir = Test::new().justfile(JUSTFILE).stdout("foo\n").run().tempdir;
+
+  let entries = fs::read_dir(tempdir.path().join(".just/cache/backticks"))
+    .unwrap()
+    .count();
+
+  assert_eq!(entries, 1);
+}
+
+#[test]
+fn changing_command_invalidates_cache() {
+  let tempdir = Test::new().justfile(JUSTFILE).stdout("foo\n").run().tempdir;
+
+  let tempdir = Test::with_tempdir(tempdir)
+    .justfile(
+      "
+      foo := cached(`echo run >> runs.txt; echo bar`)
+
+      bar:
+        @echo {{ foo }}
+      ",
+    )
+    .stdout("bar\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(runs(&tempdir), 2);
+}
+
+#[test]
+fn changing_key_file_invalidates_cache() {
+  let tempdir = Test::new()
+    .justfile(KEYED_JUSTFILE)
+    .write("key.txt", "a")
+    .stdout("a\n")
+    .run()
+    .tempdir;
+
+  let tempdir = Test::with_tempdir(tempdir)
+    .justfile(KEYED_JUSTFILE)
+    .stdout("a\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(runs(&tempdir), 1);
+
+  let tempdir = Test::with_tempdir(tempdir)
+    .justfile(KEYED_JUSTFILE)
+    .write("key.txt", "b")
+    .stdout("b\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(runs(&tempdir), 2);
+}
+
+#[test]
+fn missing_key_file() {
+  Test::new()
+    .justfile(KEYED_JUSTFILE)
+    .stderr_regex(
+      "(?s)error: Call to function `cached` failed: Failed to open file at `.*key.txt.*`: .*",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn clear_cache() {
+  let tempdir = Test::new().justfile(JUSTFILE).stdout("foo\n").run().tempdir;
+
+  let tempdir = Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .arg("--clear-cache")
+    .run()
+    .tempdir;
+
+  assert!(!tempdir.path().join(".just/cache/backticks").exists());
+
+  let tempdir = Test::with_tempdir(tempdir)
+    .justfile(JUSTFILE)
+    .stdout("foo\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(runs(&tempdir), 2);
+}
+
+#[test]
+fn clear_cache_preserves_sources_cache() {
+  let tempdir = Test::new()
+    .justfile(JUSTFILE)
+    .write(".just/cache/sources/digest", "foo")
+    .arg("--clear-cache")
+    .run()
+    .tempdir;
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join(".just/cache/sources/digest")).unwrap(),
+    "foo",
+  );
+}
+
+#[test]
+fn clear_cache_without_cache() {
+  Test::new().justfile(JUSTFILE).arg("--clear-cache").run();
+}
+
+#[test]
+fn failing_backtick_is_not_cached() {
+  let tempdir = Test::new()
+    .justfile(
+      "
+      foo := cached(`echo run >> runs.txt; exit 1`)
+
+      bar:
+        @echo {{ foo }}
+      ",
+    )
+    .stderr(
+      "
+      error: Backtick failed with exit code 1
+       ——▶ justfile:1:15
+        |
+      1 | foo := cached(`echo run >> runs.txt; exit 1`)
+        |               ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run()
+    .tempdir;
+
+  assert!(!tempdir.path().join(".just/cache").exists());
+}
+
+#[test]
+fn dry_run_does_not_cache() {
+  let tempdir = Test::new()
+    .justfile(JUSTFILE)
+    .arg("--dry-run")
+    .stderr("echo `echo run >> runs.txt; echo foo`\n")
+    .run()
+    .tempdir;
+
+  assert!(!tempdir.path().join(".just/cache").exists());
+  assert!(!tempdir.path().join("runs.txt").exists());
+}
+
+#[test]
+fn first_argument_must_be_backtick() {
+  Test::new()
+    .justfile(
+      "
+      foo := cached('echo foo')
+      ",
+    )
+    .stderr(
+      "
+      error: Function `cached` must be called with a backtick as its first argument
+       ——▶ justfile:1:8
+        |
+      1 | foo := cached('echo foo')
+        |        ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn requires_argument() {
+  Test::new()
+    .justfile(
+      "
+      foo := cached()
+      ",
+    )
+    .stderr(
+      "
+      error: Function `cached` must be called with a backtick as its first argument
+       ——▶ justfile:1:8
+        |
+      1 | foo := cached()
+        |        ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile("foo := cached(`echo foo`, 'key.txt')")
+    .arg("--dump")
+    .stdout("foo := cached(`echo foo`, 'key.txt')\n")
+    .run();
+}
+
+#[test]
+fn name_may_be_used_for_variables() {
+  Test::new()
+    .justfile(
+      "
+      cached := 'foo'
+
+      bar:
+        @echo {{ cached }}
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn user_function_may_not_shadow() {
+  Test::new()
+    .justfile(
+      "
+      fn cached(x) := x
+      ",
+    )
+    .stderr(
+      "
+      error: Function `cached` shadows built-in function of the same name
+       ——▶ justfile:1:4
+        |
+      1 | fn cached(x) := x
+        |    ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
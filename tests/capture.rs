@@ -0,0 +1,318 @@
+use super::*;
+
+#[test]
+fn output_is_bound_in_dependent_recipe() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      version:
+        @echo 1.2.3
+
+      release: version
+        @echo releasing {{ version }}
+      ",
+    )
+    .arg("release")
+    .stdout("releasing 1.2.3\n")
+    .run();
+}
+
+#[test]
+fn output_is_trimmed() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      version:
+        @echo
+        @echo '  1.2.3  '
+        @echo
+
+      release: version
+        @echo '[{{ version }}]'
+      ",
+    )
+    .arg("release")
+    .stdout("[1.2.3]\n")
+    .run();
+}
+
+#[test]
+fn output_is_printed_when_run_from_command_line() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      version:
+        @echo 1.2.3
+      ",
+    )
+    .stdout("1.2.3\n")
+    .run();
+}
+
+#[test]
+fn stderr_is_not_captured() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      version:
+        echo 1.2.3
+
+      release: version
+        @echo {{ version }}
+      ",
+    )
+    .arg("release")
+    .stdout("1.2.3\n")
+    .stderr("echo 1.2.3\n")
+    .run();
+}
+
+#[test]
+fn recipe_runs_once() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      version:
+        @echo running >&2
+        @echo 1.2.3
+
+      build: version
+        @echo build {{ version }}
+
+      release: build version
+        @echo release {{ version }}
+      ",
+    )
+    .arg("release")
+    .stdout("build 1.2.3\nrelease 1.2.3\n")
+    .stderr("running\n")
+    .run();
+}
+
+#[test]
+fn parallel_dependencies() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      foo:
+        @echo foo
+
+      [capture]
+      bar:
+        @echo bar
+
+      [parallel]
+      baz: foo bar
+        @echo {{ foo }} {{ bar }}
+      ",
+    )
+    .arg("baz")
+    .stdout("foo bar\n")
+    .run();
+}
+
+#[test]
+fn subsequent_dependency_arguments() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      version:
+        @echo 1.2.3
+
+      release: version && (tag version)
+
+      tag name:
+        @echo tag {{ name }}
+      ",
+    )
+    .arg("release")
+    .stdout("tag 1.2.3\n")
+    .run();
+}
+
+#[test]
+fn prior_dependency_arguments_cannot_use_output() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      version:
+        @echo 1.2.3
+
+      release: version (tag version)
+
+      tag name:
+      ",
+    )
+    .arg("release")
+    .stderr(
+      "
+      error: Variable `version` not defined
+       ——▶ justfile:5:23
+        |
+      5 | release: version (tag version)
+        |                       ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn output_is_not_bound_without_dependency() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      version:
+        @echo 1.2.3
+
+      release:
+        @echo {{ version }}
+      ",
+    )
+    .arg("release")
+    .stderr(
+      "
+      error: Variable `version` not defined
+       ——▶ justfile:6:12
+        |
+      6 |   @echo {{ version }}
+        |            ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn script_recipe() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      version:
+        #!/bin/sh
+        echo 1.2.3
+
+      release: version
+        @echo {{ version }}
+      ",
+    )
+    .arg("release")
+    .stdout("1.2.3\n")
+    .run();
+}
+
+#[test]
+fn builtin_shell() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['builtin']
+
+      [capture]
+      version:
+        @echo 1.2.3
+
+      release: version
+        @echo {{ version }}
+      ",
+    )
+    .arg("release")
+    .stdout("1.2.3\n")
+    .run();
+}
+
+#[test]
+fn failure() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      version:
+        @exit 1
+
+      release: version
+        @echo {{ version }}
+      ",
+    )
+    .arg("release")
+    .stderr("error: Recipe `version` failed on line 3 with exit code 1\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn capture_with_sources_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      [sources('*.c')]
+      version:
+        @echo 1.2.3
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe `version` has both `[capture]` and `[sources]` attributes
+       ——▶ justfile:3:1
+        |
+      3 | version:
+        | ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn capture_with_outputs_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+      [capture, outputs('version.txt')]
+      version:
+        @echo 1.2.3
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe `version` has both `[capture]` and `[outputs]` attributes
+       ——▶ justfile:2:1
+        |
+      2 | version:
+        | ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn stdin_is_inherited() {
+  Test::new()
+    .justfile(
+      "
+      [capture]
+      name:
+        @cat
+
+      greet: name
+        @echo Hello, {{ name }}!
+      ",
+    )
+    .arg("greet")
+    .stdin("world")
+    .stdout("Hello, world!\n")
+    .run();
+}
@@ -43,9 +43,10 @@ test! {
     error: The argument '--command <COMMAND>' requires a value but none was supplied
 
     USAGE:
-        just{EXE_SUFFIX} --color <COLOR> --dump-format <FORMAT> --shell <SHELL> \
-        <--changelog|--choose|--command <COMMAND>|--completions <SHELL>|--dump|--edit|\
-        --evaluate|--fmt|--init|--list|--show <RECIPE>|--summary|--variables>
+        just{EXE_SUFFIX} --color <COLOR> --dump-format <FORMAT> --output-format <FORMAT> \
+        --shell <SHELL> \
+        <--changelog|--choose|--clear-cache|--command <COMMAND>|--completions <SHELL>|--dump|--edit|\
+        --evaluate|--fmt|--groups|--init|--list|--lsp|--show <RECIPE>|--summary|--variables>
 
     For more information try --help
   "),
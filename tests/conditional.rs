@@ -61,6 +61,7 @@ test! {
   stdout: "",
   stderr: "
     error: Variable `b` not defined
+     ——▶ justfile:1:9
       |
     1 | a := if b == '' { '' } else { '' }
       |         ^
@@ -79,6 +80,7 @@ test! {
   stdout: "",
   stderr: "
     error: Variable `b` not defined
+     ——▶ justfile:1:15
       |
     1 | a := if '' == b { '' } else { '' }
       |               ^
@@ -97,6 +99,7 @@ test! {
   stdout: "",
   stderr: "
     error: Variable `b` not defined
+     ——▶ justfile:1:20
       |
     1 | a := if '' == '' { b } else { '' }
       |                    ^
@@ -115,6 +118,7 @@ test! {
   stdout: "",
   stderr: "
     error: Variable `b` not defined
+     ——▶ justfile:1:32
       |
     1 | a := if '' == '' { '' } else { b }
       |                                ^
@@ -132,7 +136,8 @@ test! {
   ",
   stdout: "",
   stderr: "
-    error: Expected '!=', '==', '=~', '+', or '/', but found identifier
+    error: Expected '!=', '!~', '==', '=~', '+', or '/', but found identifier
+     ——▶ justfile:1:12
       |
     1 | a := if '' a '' { '' } else { b }
       |            ^
@@ -177,6 +182,7 @@ test! {
   stdout: "",
   stderr: "
     error: Expected keyword `else` but found `end of line`
+     ——▶ justfile:1:54
       |
     1 | TEST := if path_exists('/bin/bash') == 'true' {'yes'}
       |                                                      ^
@@ -192,9 +198,180 @@ test! {
   stdout: "",
   stderr: "
     error: Expected keyword `else` but found identifier `els`
+     ——▶ justfile:1:55
       |
     1 | TEST := if path_exists('/bin/bash') == 'true' {'yes'} els {'no'}
       |                                                       ^^^
   ",
   status: EXIT_FAILURE,
 }
+
+test! {
+  name: and,
+  justfile: "
+    a := if 'a' == 'a' && 'b' == 'b' { 'yes' } else { 'no' }
+    b := if 'a' == 'a' && 'b' == 'c' { 'yes' } else { 'no' }
+
+    foo:
+      echo {{ a }} {{ b }}
+  ",
+  stdout: "yes no\n",
+  stderr: "echo yes no\n",
+}
+
+test! {
+  name: or,
+  justfile: "
+    a := if 'a' == 'b' || 'b' == 'b' { 'yes' } else { 'no' }
+    b := if 'a' == 'b' || 'b' == 'c' { 'yes' } else { 'no' }
+
+    foo:
+      echo {{ a }} {{ b }}
+  ",
+  stdout: "yes no\n",
+  stderr: "echo yes no\n",
+}
+
+test! {
+  name: and_binds_tighter_than_or,
+  justfile: "
+    a := if 'a' == 'a' || 'a' == 'b' && 'a' == 'c' { 'yes' } else { 'no' }
+
+    foo:
+      echo {{ a }}
+  ",
+  stdout: "yes\n",
+  stderr: "echo yes\n",
+}
+
+test! {
+  name: not,
+  justfile: "
+    a := if !'a' == 'b' { 'yes' } else { 'no' }
+    b := if !!'a' == 'b' { 'yes' } else { 'no' }
+
+    foo:
+      echo {{ a }} {{ b }}
+  ",
+  stdout: "yes no\n",
+  stderr: "echo yes no\n",
+}
+
+test! {
+  name: grouped_condition,
+  justfile: "
+    a := if ('a' == 'a' || 'a' == 'b') && 'a' == 'c' { 'yes' } else { 'no' }
+    b := if !('a' == 'b' || 'a' == 'c') { 'yes' } else { 'no' }
+    c := if (('a' == 'a')) { 'yes' } else { 'no' }
+
+    foo:
+      echo {{ a }} {{ b }} {{ c }}
+  ",
+  stdout: "no yes yes\n",
+  stderr: "echo no yes yes\n",
+}
+
+test! {
+  name: grouped_expression_in_comparison,
+  justfile: "
+    a := if ('a' + 'b') == 'ab' && ('a') + 'b' == 'ab' { 'yes' } else { 'no' }
+
+    foo:
+      echo {{ a }}
+  ",
+  stdout: "yes\n",
+  stderr: "echo yes\n",
+}
+
+test! {
+  name: and_short_circuits,
+  justfile: "
+    a := if 'a' == 'b' && `exit 1` == '' { 'yes' } else { 'no' }
+
+    foo:
+      echo {{ a }}
+  ",
+  stdout: "no\n",
+  stderr: "echo no\n",
+}
+
+test! {
+  name: or_short_circuits,
+  justfile: "
+    a := if 'a' == 'a' || `exit 1` == '' { 'yes' } else { 'no' }
+
+    foo:
+      echo {{ a }}
+  ",
+  stdout: "yes\n",
+  stderr: "echo yes\n",
+}
+
+test! {
+  name: in_list,
+  justfile: "
+    x := 'b'
+    a := if x in ['a', 'b'] { 'yes' } else { 'no' }
+    b := if x in ['a', 'c',] { 'yes' } else { 'no' }
+    c := if x in [] { 'yes' } else { 'no' }
+    d := if !x in ['a' + 'b', x] { 'yes' } else { 'no' }
+
+    foo:
+      echo {{ a }} {{ b }} {{ c }} {{ d }}
+  ",
+  stdout: "yes no no no\n",
+  stderr: "echo yes no no no\n",
+}
+
+test! {
+  name: in_list_short_circuits,
+  justfile: "
+    a := if 'a' in ['a', `exit 1`] { 'yes' } else { 'no' }
+
+    foo:
+      echo {{ a }}
+  ",
+  stdout: "yes\n",
+  stderr: "echo yes\n",
+}
+
+test! {
+  name: in_requires_list,
+  justfile: "
+    a := if 'a' in 'a' { 'yes' } else { 'no' }
+  ",
+  stderr: "
+    error: Expected '[', but found string
+     ——▶ justfile:1:16
+      |
+    1 | a := if 'a' in 'a' { 'yes' } else { 'no' }
+      |                ^^^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: undefined_variable_in_condition,
+  justfile: "
+    a := if 'a' == 'a' && 'b' in ['c', d] { 'yes' } else { 'no' }
+  ",
+  stderr: "
+    error: Variable `d` not defined
+     ——▶ justfile:1:36
+      |
+    1 | a := if 'a' == 'a' && 'b' in ['c', d] { 'yes' } else { 'no' }
+      |                                    ^
+  ",
+  status: EXIT_FAILURE,
+}
+
+test! {
+  name: dump_boolean_operators,
+  justfile: "
+    a := if !('a' == 'b' || 'c' !~ 'd') && 'e' in ['f', 'g'] { 'yes' } else { 'no' }
+  ",
+  args: ("--dump"),
+  stdout: "
+    a := if !('a' == 'b' || 'c' !~ 'd') && 'e' in ['f', 'g'] { 'yes' } else { 'no' }
+  ",
+}
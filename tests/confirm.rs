@@ -45,9 +45,11 @@ fn confirm_recipe() {
             echo confirmed
         ",
     )
-    .stderr("Run recipe `requires_confirmation`? echo confirmed\n")
-    .stdout("confirmed\n")
+    .stderr(
+      "error: Recipe `requires_confirmation` requires confirmation, but standard input is not a terminal. Use `--yes` to confirm automatically.\n",
+    )
     .stdin("y")
+    .status(EXIT_FAILURE)
     .run();
 }
 
@@ -64,9 +66,11 @@ fn recipe_with_confirm_recipe_dependency() {
             echo confirmed
         ",
     )
-    .stderr("Run recipe `requires_confirmation`? echo confirmed\necho confirmed2\n")
-    .stdout("confirmed\nconfirmed2\n")
+    .stderr(
+      "error: Recipe `requires_confirmation` requires confirmation, but standard input is not a terminal. Use `--yes` to confirm automatically.\n",
+    )
     .stdin("y")
+    .status(EXIT_FAILURE)
     .run();
 }
 
@@ -80,9 +84,12 @@ fn do_not_confirm_recipe() {
             echo confirmed
         ",
     )
-    .stderr("Run recipe `requires_confirmation`? error: Recipe `requires_confirmation` was not confirmed\n")
+    .stderr(
+      "error: Recipe `requires_confirmation` requires confirmation, but standard input is not a terminal. Use `--yes` to confirm automatically.\n",
+    )
+    .stdin("n")
     .stdout("")
-    .status(1)
+    .status(EXIT_FAILURE)
     .run();
 }
 
@@ -99,7 +106,192 @@ fn do_not_confirm_recipe_with_confirm_recipe_dependency() {
             echo confirmed
         ",
     )
-    .stderr("Run recipe `requires_confirmation`? error: Recipe `requires_confirmation` was not confirmed\n")
-    .status(1)
+    .stderr(
+      "error: Recipe `requires_confirmation` requires confirmation, but standard input is not a terminal. Use `--yes` to confirm automatically.\n",
+    )
+    .stdin("n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn confirm_without_terminal_fails_before_dependencies_run() {
+  Test::new()
+    .justfile(
+      "
+        [confirm]
+        deploy: build
+          echo deploy
+
+        build:
+          echo build
+      ",
+    )
+    .stderr(
+      "error: Recipe `deploy` requires confirmation, but standard input is not a terminal. Use `--yes` to confirm automatically.\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn confirm_with_prompt_and_yes() {
+  Test::new()
+    .justfile(
+      "
+        [confirm('Deploy ' + env + ' to production?')]
+        deploy env:
+          echo {{env}}
+      ",
+    )
+    .args(["--yes", "deploy", "staging"])
+    .stderr("echo staging\n")
+    .stdout("staging\n")
+    .run();
+}
+
+#[test]
+fn confirm_with_prompt_without_terminal_fails() {
+  Test::new()
+    .justfile(
+      "
+        [confirm('Deploy ' + env + ' to production?')]
+        deploy env:
+          echo {{env}}
+      ",
+    )
+    .args(["deploy", "staging"])
+    .stderr(
+      "error: Recipe `deploy` requires confirmation, but standard input is not a terminal. Use `--yes` to confirm automatically.\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn confirm_prompt_with_undefined_variable() {
+  Test::new()
+    .justfile(
+      "
+        [confirm('Deploy ' + env + ' to production?')]
+        deploy:
+          echo deploy
+      ",
+    )
+    .stderr(
+      "
+        error: Variable `env` not defined
+         ——▶ justfile:1:22
+          |
+        1 | [confirm('Deploy ' + env + ' to production?')]
+          |                      ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn confirm_prompt_may_use_variables() {
+  Test::new()
+    .justfile(
+      "
+        target := 'production'
+
+        [confirm('Deploy to ' + target + '?')]
+        deploy:
+          echo {{target}}
+      ",
+    )
+    .arg("--yes")
+    .stdout("production\n")
+    .stderr("echo production\n")
+    .run();
+}
+
+#[test]
+fn confirm_prompt_is_dumped() {
+  Test::new()
+    .justfile(
+      "
+        [confirm('Deploy ' + env + '?')]
+        deploy env:
+          echo {{env}}
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        [confirm('Deploy ' + env + '?')]
+        deploy env:
+            echo {{ env }}
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn confirm_with_too_many_arguments() {
+  Test::new()
+    .justfile(
+      "
+        [confirm('a', 'b')]
+        deploy:
+      ",
+    )
+    .stderr(
+      "
+        error: Attribute `confirm` called with 2 arguments but takes 0 to 1 argument
+         ——▶ justfile:1:2
+          |
+        1 | [confirm('a', 'b')]
+          |  ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn only_confirm_prompt_may_be_expression() {
+  Test::new()
+    .justfile(
+      "
+        [group('a' + 'b')]
+        foo:
+      ",
+    )
+    .stderr(
+      "
+        error: Arguments of attribute `group` must be string literals
+         ——▶ justfile:1:2
+          |
+        1 | [group('a' + 'b')]
+          |  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn confirm_prompt_interpolation_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+        [confirm('Deploy {{env}} to production?')]
+        deploy env:
+      ",
+    )
+    .stderr(
+      "
+        error: `[confirm]` prompts do not support `{{…}}` interpolation, use concatenation instead, for example `[confirm('Deploy ' + env + '?')]`
+         ——▶ justfile:1:2
+          |
+        1 | [confirm('Deploy {{env}} to production?')]
+          |  ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
     .run();
 }
@@ -5,6 +5,7 @@ test! {
   justfile: "(]",
   stderr: "
     error: Mismatched closing delimiter `]`. (Did you mean to close the `(` on line 1?)
+     ——▶ justfile:1:2
       |
     1 | (]
       |  ^
@@ -17,6 +18,7 @@ test! {
   justfile: "]",
   stderr: "
     error: Unexpected closing delimiter `]`
+     ——▶ justfile:1:1
       |
     1 | ]
       | ^
@@ -96,6 +98,7 @@ test! {
   stdout: "",
   stderr: "
     error: Unterminated interpolation
+     ——▶ justfile:2:8
       |
     2 |   echo {{ (
       |        ^^
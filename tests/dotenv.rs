@@ -73,10 +73,11 @@ fn path_not_found() {
     )
     .args(["--dotenv-path", ".env.prod"])
     .stderr(if cfg!(windows) {
-      "error: Failed to load environment file: The system cannot find the file specified. (os \
-       error 2)\n"
+      "error: Failed to load environment file `.env.prod`: The system cannot find the file \
+       specified. (os error 2)\n"
     } else {
-      "error: Failed to load environment file: No such file or directory (os error 2)\n"
+      "error: Failed to load environment file `.env.prod`: No such file or directory (os error \
+       2)\n"
     })
     .status(EXIT_FAILURE)
     .run();
@@ -245,3 +246,276 @@ fn program_argument_has_priority_for_dotenv_path() {
     .status(EXIT_SUCCESS)
     .run();
 }
+
+#[test]
+fn dotenv_files_later_files_override_earlier_files() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env", "local.env"]
+
+        foo:
+          @echo $FOO $BAR
+      "#,
+    )
+    .tree(tree! {
+      "base.env": "FOO=foo\nBAR=bar",
+      "local.env": "BAR=baz",
+    })
+    .stdout("foo baz\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_skips_missing_files() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["missing.env", "base.env"]
+
+        foo:
+          @echo $FOO
+      "#,
+    )
+    .write("base.env", "FOO=foo")
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_are_not_searched_for_in_parent_directories() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env"]
+
+        foo:
+          @echo ${FOO:-unset}
+      "#,
+    )
+    .write(
+      "subdir/justfile",
+      "set dotenv-files := ['base.env']\nfoo:\n  @echo ${FOO:-unset}",
+    )
+    .write("base.env", "FOO=foo")
+    .current_dir("subdir")
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_expand_variables_from_earlier_files() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env", "local.env"]
+
+        foo:
+          @echo $URL
+      "#,
+    )
+    .tree(tree! {
+      "base.env": "HOST=localhost",
+      "local.env": "URL=http://${HOST}:8080",
+    })
+    .stdout("http://localhost:8080\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_expand_underscore_names_from_earlier_files() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env", "local.env"]
+
+        foo:
+          @echo $BAR
+      "#,
+    )
+    .tree(tree! {
+      "base.env": "FOO=foo\nFOO_BAR=foo_bar",
+      "local.env": "BAR=$FOO_BAR",
+    })
+    .stdout("foo_bar\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_expanded_values_are_not_split_or_substituted() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env", "local.env"]
+
+        foo:
+          @echo "$B|$C|$D"
+      "#,
+    )
+    .tree(tree! {
+      "base.env": r#"A="x  \$y""#,
+      "local.env": "B=${A}\nC=\"<$A>\"\nD='${A}'",
+    })
+    .stdout("x  $y|<x  $y>|${A}\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_expansion_prefers_environment_by_default() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env", "local.env"]
+
+        foo:
+          @echo $URL
+      "#,
+    )
+    .tree(tree! {
+      "base.env": "HOST=dotenv",
+      "local.env": "URL=${HOST}",
+    })
+    .env("HOST", "environment")
+    .stdout("environment\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_expansion_with_dotenv_override() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env", "local.env"]
+        set dotenv-override
+
+        foo:
+          @echo $URL
+      "#,
+    )
+    .tree(tree! {
+      "base.env": "HOST=dotenv",
+      "local.env": "URL=${HOST}",
+    })
+    .env("HOST", "environment")
+    .stdout("dotenv\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_errors_name_file() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env", "local.env"]
+
+        foo:
+      "#,
+    )
+    .tree(tree! {
+      "base.env": "A=a\nB=b",
+      "local.env": "C=c\nD d",
+    })
+    .stderr_regex(
+      "error: Failed to load environment file `.*local.env`: Error parsing line: 'D d', error at \
+       line index: 2\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dotenv_files_expand_variables_in_file_names() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env", "$STAGE.env", "${STAGE}.local.env"]
+
+        foo:
+          @echo $FOO
+      "#,
+    )
+    .tree(tree! {
+      "base.env": "STAGE=prod\nFOO=default",
+      "prod.env": "FOO=prod",
+      "prod.local.env": "FOO=prod-local",
+    })
+    .stdout("prod-local\n")
+    .run();
+}
+
+#[test]
+fn dotenv_files_do_not_override_environment_by_default() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env"]
+
+        foo:
+          @echo $FOO
+      "#,
+    )
+    .write("base.env", "FOO=dotenv")
+    .env("FOO", "environment")
+    .stdout("environment\n")
+    .run();
+}
+
+#[test]
+fn dotenv_override_overrides_environment() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env"]
+        set dotenv-override
+
+        foo:
+          @echo $FOO
+      "#,
+    )
+    .write("base.env", "FOO=dotenv")
+    .env("FOO", "environment")
+    .stdout("dotenv\n")
+    .run();
+}
+
+#[test]
+fn dotenv_override_with_dotenv_load() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-load
+        set dotenv-override
+
+        foo:
+          @echo $DOTENV_KEY
+      "#,
+    )
+    .env("DOTENV_KEY", "environment")
+    .stdout("dotenv-value\n")
+    .run();
+}
+
+#[test]
+fn evaluate_shows_dotenv_sources() {
+  Test::new()
+    .justfile(
+      r#"
+        set dotenv-files := ["base.env", "local.env"]
+
+        foo := "bar"
+      "#,
+    )
+    .tree(tree! {
+      "base.env": "A=a\nB=b",
+      "local.env": "B=\"c d\"",
+    })
+    .arg("--evaluate")
+    .stdout(
+      r#"
+        foo := "bar"
+        A="a" # base.env
+        B="c d" # local.env
+      "#,
+    )
+    .run();
+}
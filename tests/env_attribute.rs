@@ -0,0 +1,148 @@
+use super::*;
+
+#[test]
+fn env_attribute_sets_variable_in_linewise_recipe() {
+  Test::new()
+    .justfile(
+      r#"
+        [env("FOO", "bar")]
+        foo:
+          @echo $FOO
+      "#,
+    )
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn env_attribute_sets_variable_in_shebang_recipe() {
+  Test::new()
+    .justfile(
+      r#"
+        [env("FOO", "bar")]
+        foo:
+          #!/bin/sh
+          echo $FOO
+      "#,
+    )
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn env_attribute_only_applies_to_recipe() {
+  Test::new()
+    .justfile(
+      r#"
+        foo: bar
+          @echo ${FOO:-unset}
+
+        [env("FOO", "bar")]
+        bar:
+          @echo $FOO
+      "#,
+    )
+    .stdout("bar\nunset\n")
+    .run();
+}
+
+#[test]
+fn env_attribute_overrides_inherited_and_exported_variables() {
+  Test::new()
+    .justfile(
+      r#"
+        export BAR := 'exported'
+
+        [env("FOO", "attribute"), env("BAR", "attribute")]
+        foo:
+          @echo $FOO $BAR
+      "#,
+    )
+    .env("FOO", "inherited")
+    .stdout("attribute attribute\n")
+    .run();
+}
+
+#[test]
+fn env_attribute_overrides_unexport() {
+  Test::new()
+    .justfile(
+      r#"
+        unexport FOO
+
+        [env("FOO", "attribute")]
+        foo:
+          @echo $FOO
+      "#,
+    )
+    .env("FOO", "inherited")
+    .stdout("attribute\n")
+    .run();
+}
+
+#[test]
+fn env_attribute_requires_two_arguments() {
+  Test::new()
+    .justfile(
+      r#"
+        [env("FOO")]
+        foo:
+      "#,
+    )
+    .stderr(
+      "
+        error: Attribute `env` called with 1 argument but takes 2 arguments
+         ——▶ justfile:1:2
+          |
+        1 | [env(\"FOO\")]
+          |  ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn duplicate_env_attribute_variable() {
+  Test::new()
+    .justfile(
+      r#"
+        [env("FOO", "a")]
+        [env("FOO", "b")]
+        foo:
+      "#,
+    )
+    .stderr(
+      "
+        error: Recipe has multiple `[env]` attributes for environment variable `FOO`
+         ——▶ justfile:3:1
+          |
+        3 | foo:
+          | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn show_reports_env_attributes() {
+  Test::new()
+    .justfile(
+      r#"
+        [env("FOO", "bar")]
+        foo:
+          echo $FOO
+      "#,
+    )
+    .args(["--show", "foo"])
+    .stdout(
+      r#"
+        [env("FOO", "bar")]
+        foo:
+            echo $FOO
+      "#,
+    )
+    .run();
+}
@@ -146,6 +146,7 @@ fn print_error_from_parent_if_recipe_not_found_in_current() {
     .stderr(
       "
       error: Variable `bar` not defined
+       ——▶ justfile:2:9
         |
       2 |  echo {{bar}}
         |         ^^^
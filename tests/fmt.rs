@@ -1,12 +1,15 @@
 use super::*;
 
 test! {
-  name: unstable_not_passed,
-  justfile: "",
-  args: ("--fmt"),
+  name: unstable_not_required,
+  justfile: "x:=``\n",
+  args: ("--fmt", "--check"),
+  stdout: "
+    -x:=``
+    +x := ``
+  ",
   stderr: "
-    error: The `--fmt` command is currently unstable. \
-    Invoke `just` with the `--unstable` flag to enable unstable features.
+    error: Formatted justfile differs from original.
   ",
   status: EXIT_FAILURE,
 }
@@ -313,6 +316,17 @@ test! {
   ",
 }
 
+test! {
+  name: assignment_if_else_if,
+  justfile: "
+    foo := if 'a' == 'b' { 'x' } else if 'a' == 'a' { 'y' } else { 'z' }
+  ",
+  args: ("--dump"),
+  stdout: "
+    foo := if 'a' == 'b' { 'x' } else if 'a' == 'a' { 'y' } else { 'z' }
+  ",
+}
+
 test! {
   name: assignment_nullary_function,
   justfile: "
@@ -1063,3 +1077,325 @@ fn exported_parameter() {
     .stdout("foo +$f:\n")
     .run();
 }
+
+#[test]
+fn comments_are_preserved() {
+  Test::new()
+    .justfile(
+      "
+        # leading comment
+        set shell := ['bash', '-c'] # setting comment
+        x:='a'   # assignment comment
+        [private]
+        alias b:=build # alias comment
+        unexport FOO # unexport comment
+        # doc comment
+        build: # recipe comment
+          # body comment
+          echo {{x}}
+      ",
+    )
+    .args(["--fmt", "--check"])
+    .stdout(
+      "
+         # leading comment
+         set shell := ['bash', '-c'] # setting comment
+        -x:='a'   # assignment comment
+        +x := 'a' # assignment comment
+         [private]
+        -alias b:=build # alias comment
+        +alias b := build # alias comment
+         unexport FOO # unexport comment
+        +
+         # doc comment
+         build: # recipe comment
+        -  # body comment
+        -  echo {{x}}
+        +    # body comment
+        +    echo {{ x }}
+      ",
+    )
+    .stderr("error: Formatted justfile differs from original.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn blank_lines_are_preserved_and_collapsed() {
+  let tempdir = Test::new()
+    .justfile(
+      "
+
+        a := 'a'
+        b := 'b'
+
+
+        c := 'c'
+        # comment
+
+
+
+        d := 'd'
+        foo:
+          echo foo
+
+
+        bar:
+          echo bar
+
+
+      ",
+    )
+    .arg("--fmt")
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("justfile")).unwrap(),
+      "a := 'a'\nb := 'b'\n\nc := 'c'\n# comment\n\nd := 'd'\n\nfoo:\n    echo foo\n\nbar:\n    echo bar\n",
+  );
+}
+
+#[test]
+fn blank_lines_in_recipe_bodies_are_preserved() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+            #!/usr/bin/env bash
+            echo a
+
+            echo b
+      ",
+    )
+    .args(["--fmt", "--check"])
+    .run();
+}
+
+#[test]
+fn long_recipe_headers_are_wrapped() {
+  let tempdir = Test::new()
+    .justfile(
+      "
+        recipe-with-a-long-name first-parameter second-parameter='default': dependency-one dependency-two && dependency-three
+
+        dependency-one:
+
+        dependency-two:
+
+        dependency-three:
+      ",
+    )
+    .arg("--fmt")
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("justfile")).unwrap(),
+    "recipe-with-a-long-name first-parameter second-parameter='default': \\
+  dependency-one dependency-two && dependency-three
+
+dependency-one:
+
+dependency-two:
+
+dependency-three:
+",
+  );
+}
+
+#[test]
+fn long_assignments_are_wrapped_before_operators() {
+  let tempdir = Test::new()
+    .justfile(
+      "
+        path := '/usr/local/share/some-application' / 'some-subdirectory' / 'some-file-name' + '.txt'
+      ",
+    )
+    .arg("--fmt")
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("justfile")).unwrap(),
+    "path := '/usr/local/share/some-application' / 'some-subdirectory' \\
+  / 'some-file-name' + '.txt'
+",
+  );
+}
+
+#[test]
+fn else_if_is_preserved() {
+  Test::new()
+    .justfile(
+      "
+        a := 'b'
+        e := if a in ['b', 'c'] { 'in' } else if a == 'd' { 'eq' } else { 'ne' }
+      ",
+    )
+    .args(["--fmt", "--check"])
+    .run();
+}
+
+#[test]
+fn formatting_is_idempotent() {
+  let justfile = "
+    # comment
+    set export # comment
+
+    x := 'aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa' + 'bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb' # comment
+    [private]
+    foo a b c d e f g h i j k l m n o p q r s t u v w x y z: bar bar bar bar bar bar bar # comment
+      echo {{x}}
+
+    bar:
+  ";
+
+  let tempdir = Test::new()
+    .justfile(justfile)
+    .arg("--fmt")
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .run()
+    .tempdir;
+
+  Test::with_tempdir(tempdir)
+    .no_justfile()
+    .args(["--fmt", "--check"])
+    .run();
+}
+
+#[test]
+fn alias_attributes_are_preserved() {
+  Test::new()
+    .justfile(
+      "
+        [private]
+        alias b := build
+
+        build:
+      ",
+    )
+    .args(["--fmt", "--check"])
+    .run();
+}
+
+#[test]
+fn attribute_comments_are_preserved() {
+  Test::new()
+    .justfile(
+      "
+        [private] # alias comment
+        alias b := build
+
+        [group('ci')] # group comment
+        [no-cd] # no-cd comment
+        build: test # header comment
+
+        test:
+      ",
+    )
+    .args(["--fmt", "--check"])
+    .run();
+}
+
+#[test]
+fn attribute_comments_are_moved_with_attributes() {
+  let tempdir = Test::new()
+    .justfile(
+      "
+        [no-cd]  # no-cd comment
+        [group('ci')]  # group comment
+        build:  # header comment
+      ",
+    )
+    .arg("--fmt")
+    .stderr_regex("Wrote justfile to `.*`\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("justfile")).unwrap(),
+    "[group('ci')] # group comment
+[no-cd] # no-cd comment
+build: # header comment
+",
+  );
+}
+
+#[test]
+fn check_multiple_justfiles() {
+  Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .write("a.just", "a := 'a'\n")
+    .write("b.just", "b:='b'\n")
+    .write("c/justfile", "c:='c'\n")
+    .args(["--fmt", "--check", "a.just", "b.just", "c/justfile"])
+    .stdout(
+      "
+        --- b.just
+        +++ b.just
+        -b:='b'
+        +b := 'b'
+        --- c/justfile
+        +++ c/justfile
+        -c:='c'
+        +c := 'c'
+      ",
+    )
+    .stderr("error: Formatted justfile differs from original.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn check_multiple_formatted_justfiles() {
+  Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .write("a.just", "a := 'a'\n")
+    .write("b.just", "b := b\n")
+    .args(["--fmt", "--check", "a.just", "b.just"])
+    .run();
+}
+
+#[test]
+fn format_multiple_justfiles() {
+  let tempdir = Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .write("a.just", "a:='a'\n")
+    .write("b.just", "b := 'b'\n")
+    .args(["--fmt", "a.just", "b.just"])
+    .stderr_regex("Wrote justfile to `.*a.just`\n")
+    .run()
+    .tempdir;
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("a.just")).unwrap(),
+    "a := 'a'\n"
+  );
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("b.just")).unwrap(),
+    "b := 'b'\n"
+  );
+}
+
+#[test]
+fn format_justfile_with_parse_error() {
+  Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .write("a.just", "a := \n")
+    .args(["--fmt", "--check", "a.just"])
+    .stderr(
+      "error: Expected backtick, identifier, '(', '/', or string, but found end of line\n \
+       ——▶ a.just:1:6\n  |\n1 | a := \n  |      ^\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
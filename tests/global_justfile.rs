@@ -0,0 +1,221 @@
+use super::*;
+
+#[test]
+fn global_justfile_flag() {
+  Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .write("global/justfile", "@foo:\n  echo global")
+    .env("JUST_GLOBAL_JUSTFILE", "global/justfile")
+    .args(["--global-justfile", "foo"])
+    .stdout("global\n")
+    .run();
+}
+
+#[test]
+fn global_justfile_short_flag() {
+  Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .write("global/justfile", "@foo:\n  echo global")
+    .env("JUST_GLOBAL_JUSTFILE", "global/justfile")
+    .args(["-g", "foo"])
+    .stdout("global\n")
+    .run();
+}
+
+#[test]
+fn global_justfile_in_xdg_config_home() {
+  let test = Test::new();
+
+  let config_home = test.tempdir.path().join("config");
+
+  test
+    .no_justfile()
+    .test_round_trip(false)
+    .write("config/just/justfile", "@foo:\n  echo global")
+    .env("XDG_CONFIG_HOME", config_home.to_str().unwrap())
+    .env("JUST_GLOBAL_JUSTFILE", "")
+    .args(["--global-justfile", "foo"])
+    .stdout("global\n")
+    .run();
+}
+
+#[test]
+fn global_justfile_not_found() {
+  Test::new()
+    .no_justfile()
+    .env("JUST_GLOBAL_JUSTFILE", "missing/justfile")
+    .args(["--global-justfile", "foo"])
+    .stderr("error: No global justfile found\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn global_justfile_working_directory_is_project_root() {
+  Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .tree(tree! {
+      global: {
+        justfile: "@foo:\n  cat marker",
+      },
+      project: {
+        ".git": {},
+        marker: "root",
+        sub: {},
+      },
+    })
+    .current_dir("project/sub")
+    .env("JUST_GLOBAL_JUSTFILE", "../../global/justfile")
+    .args(["--global-justfile", "foo"])
+    .stdout("root")
+    .run();
+}
+
+#[test]
+fn global_justfile_conflicts_with_justfile() {
+  Test::new()
+    .args(["--global-justfile", "--justfile", "justfile"])
+    .stderr_regex(
+      "error: The argument '--justfile <JUSTFILE>' cannot be used with '--global-justfile'(.|\\n)+",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn fallback_to_global_justfile() {
+  Test::new()
+    .justfile(
+      "
+      set fallback
+
+      @bar:
+        echo project
+      ",
+    )
+    .write("global/justfile", "@foo:\n  echo global")
+    .env("JUST_GLOBAL_JUSTFILE", "global/justfile")
+    .arg("foo")
+    .stdout("global\n")
+    .run();
+}
+
+#[test]
+fn fallback_to_global_justfile_after_parent_directories() {
+  Test::new()
+    .justfile(
+      "
+      set fallback
+
+      @bar:
+        echo parent
+      ",
+    )
+    .write("sub/justfile", "set fallback\n@baz:\n  echo sub")
+    .write(
+      "global/justfile",
+      "@foo:\n  echo global\n@bar:\n  echo global",
+    )
+    .current_dir("sub")
+    .env("JUST_GLOBAL_JUSTFILE", "../global/justfile")
+    .args(["bar", "foo"])
+    .stdout("global\nglobal\n")
+    .run();
+}
+
+#[test]
+fn global_justfile_consulted_without_fallback() {
+  Test::new()
+    .justfile(
+      "
+      @bar:
+        echo project
+      ",
+    )
+    .write("global/justfile", "@foo:\n  echo global")
+    .env("JUST_GLOBAL_JUSTFILE", "global/justfile")
+    .arg("foo")
+    .stdout("global\n")
+    .run();
+}
+
+#[test]
+fn global_justfile_consulted_without_project_justfile() {
+  Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .write("global/justfile", "@foo:\n  echo global")
+    .env("JUST_GLOBAL_JUSTFILE", "global/justfile")
+    .arg("foo")
+    .stdout("global\n")
+    .run();
+}
+
+#[test]
+fn no_justfile_and_unknown_recipe_in_global_justfile() {
+  Test::new()
+    .no_justfile()
+    .write("global/justfile", "@bar:\n  echo global")
+    .env("JUST_GLOBAL_JUSTFILE", "global/justfile")
+    .arg("foo")
+    .stderr("error: No justfile found\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn project_recipes_take_precedence_over_global_justfile() {
+  Test::new()
+    .justfile(
+      "
+      @foo:
+        echo project
+      ",
+    )
+    .write("global/justfile", "@foo:\n  echo global")
+    .env("JUST_GLOBAL_JUSTFILE", "global/justfile")
+    .arg("foo")
+    .stdout("project\n")
+    .run();
+}
+
+#[test]
+fn unknown_recipe_in_global_justfile_reports_project_error() {
+  Test::new()
+    .justfile(
+      "
+      set fallback
+
+      @bar:
+        echo project
+      ",
+    )
+    .write("global/justfile", "@baz:\n  echo global")
+    .env("JUST_GLOBAL_JUSTFILE", "global/justfile")
+    .arg("foo")
+    .stderr("error: Justfile does not contain recipe `foo`.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn fallback_to_global_justfile_verbose_message() {
+  Test::new()
+    .justfile(
+      "
+      set fallback
+
+      @bar:
+        echo project
+      ",
+    )
+    .write("global/justfile", "@foo:\n  echo global")
+    .env("JUST_GLOBAL_JUSTFILE", "global/justfile")
+    .args(["--verbose", "foo"])
+    .stdout("global\n")
+    .stderr_regex("Trying .*global.justfile\n===> Running recipe `foo`...\necho global\n")
+    .run();
+}
@@ -0,0 +1,249 @@
+use super::*;
+
+#[test]
+fn list_with_groups() {
+  Test::new()
+    .justfile(
+      "
+      [group('alpha')]
+      a:
+      # Doc comment
+      [group('alpha')]
+      [group('beta')]
+      b:
+      c:
+      [group('multi word group')]
+      d:
+      [group('alpha')]
+      e:
+      [group('beta')]
+      [group('alpha')]
+      f:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+      Available recipes:
+          c
+
+          [alpha]
+          a
+          b # Doc comment
+          e
+          f
+
+          [beta]
+          b # Doc comment
+          f
+
+          [multi word group]
+          d
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn list_with_groups_unsorted() {
+  Test::new()
+    .justfile(
+      "
+      [group('beta')]
+      [group('alpha')]
+      f:
+
+      [group('alpha')]
+      e:
+
+      [group('beta')]
+      [group('alpha')]
+      a:
+
+      c:
+      ",
+    )
+    .args(["--list", "--unsorted"])
+    .stdout(
+      "
+      Available recipes:
+          c
+
+          [alpha]
+          f
+          e
+          a
+
+          [beta]
+          f
+          a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn list_groups() {
+  Test::new()
+    .justfile(
+      "
+      [group('B')]
+      bar:
+
+      [group('A')]
+      [group('B')]
+      foo:
+
+      baz:
+      ",
+    )
+    .arg("--groups")
+    .stdout(
+      "
+      Recipe groups:
+          A
+          B
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn list_groups_unsorted() {
+  Test::new()
+    .justfile(
+      "
+      [group('Z')]
+      baz:
+
+      [group('B')]
+      bar:
+
+      [group('A')]
+      [group('B')]
+      foo:
+      ",
+    )
+    .args(["--groups", "--unsorted"])
+    .stdout(
+      "
+      Recipe groups:
+          Z
+          B
+          A
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn list_groups_with_custom_prefix() {
+  Test::new()
+    .justfile(
+      "
+      [group('B')]
+      foo:
+
+      [group('A')]
+      [group('B')]
+      bar:
+      ",
+    )
+    .args(["--groups", "--list-prefix", "..."])
+    .stdout(
+      "
+      Recipe groups:
+      ...A
+      ...B
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn private_recipe_groups_are_not_listed() {
+  Test::new()
+    .justfile(
+      "
+      [group('a')]
+      foo:
+
+      [group('b')]
+      _bar:
+      ",
+    )
+    .arg("--groups")
+    .stdout(
+      "
+      Recipe groups:
+          a
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn group_attribute_requires_argument() {
+  Test::new()
+    .justfile(
+      "
+      [group]
+      foo:
+      ",
+    )
+    .stderr(
+      "
+      error: Attribute `group` called with 0 arguments but takes 1 argument
+       ——▶ justfile:1:2
+        |
+      1 | [group]
+        |  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn duplicate_group_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+      [group('a')]
+      [group('a')]
+      foo:
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe attribute `group` first used on line 1 is duplicated on line 2
+       ——▶ justfile:2:2
+        |
+      2 | [group('a')]
+        |  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn groups_are_dumped() {
+  Test::new()
+    .justfile(
+      "
+      [group('a')]
+      [group(\"b\")]
+      foo:
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+      [group('a')]
+      [group(\"b\")]
+      foo:
+      ",
+    )
+    .run();
+}
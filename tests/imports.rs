@@ -0,0 +1,405 @@
+use super::*;
+
+#[test]
+fn import_succeeds() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        b:
+          @echo B
+      ",
+    })
+    .justfile(
+      "
+        import './import.justfile'
+
+        a: b
+          @echo A
+      ",
+    )
+    .arg("a")
+    .stdout("B\nA\n")
+    .run();
+}
+
+#[test]
+fn import_may_appear_after_recipes() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        b:
+          @echo B
+      ",
+    })
+    .justfile(
+      "
+        a: b
+          @echo A
+
+        import './import.justfile'
+      ",
+    )
+    .arg("a")
+    .stdout("B\nA\n")
+    .run();
+}
+
+#[test]
+fn imports_are_recursive_and_relative_to_importing_file() {
+  Test::new()
+    .tree(tree! {
+      foo: {
+        "bar.just": "
+          import 'baz.just'
+
+          bar: baz
+            @echo BAR
+        ",
+        "baz.just": "
+          baz:
+            @echo BAZ
+        ",
+      },
+    })
+    .justfile(
+      "
+        import 'foo/bar.just'
+      ",
+    )
+    .arg("bar")
+    .stdout("BAZ\nBAR\n")
+    .run();
+}
+
+#[test]
+fn imported_variables_are_merged() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        x := 'imported'
+      ",
+    })
+    .justfile(
+      "
+        import 'import.justfile'
+
+        a:
+          @echo {{x}}
+      ",
+    )
+    .stdout("imported\n")
+    .run();
+}
+
+#[test]
+fn first_recipe_in_root_justfile_is_default() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        b:
+          @echo B
+      ",
+    })
+    .justfile(
+      "
+        import 'import.justfile'
+
+        a:
+          @echo A
+      ",
+    )
+    .stdout("A\n")
+    .run();
+}
+
+#[test]
+fn missing_import_file_error() {
+  Test::new()
+    .justfile(
+      "
+        import './import.justfile'
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Could not find source file for import.
+         ——▶ justfile:1:8
+          |
+        1 | import './import.justfile'
+          |        ^^^^^^^^^^^^^^^^^^^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn errors_in_imported_files_report_correct_location() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        b:
+          @echo {{y}}
+      ",
+    })
+    .justfile(
+      "
+        a:
+
+        import 'import.justfile'
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Variable `y` not defined
+         ——▶ import.justfile:2:11
+          |
+        2 |   @echo {{y}}
+          |           ^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn duplicate_recipes_in_imported_files_are_errors() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        a:
+          @echo IMPORTED
+      ",
+    })
+    .justfile(
+      "
+        import 'import.justfile'
+
+        a:
+          @echo A
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Recipe `a` first defined at justfile:3 is redefined at import.justfile:1
+         ——▶ import.justfile:1:1
+          |
+        1 | a:
+          | ^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn duplicate_variables_in_imported_files_are_errors() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        x := 'b'
+      ",
+    })
+    .justfile(
+      "
+        import 'import.justfile'
+
+        x := 'a'
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Variable `x` first defined at justfile:3 is redefined at import.justfile:1
+         ——▶ import.justfile:1:1
+          |
+        1 | x := 'b'
+          | ^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn duplicate_aliases_in_imported_files_are_errors() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        alias b := a
+      ",
+    })
+    .justfile(
+      "
+        import 'import.justfile'
+
+        alias b := a
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Alias `b` first defined at justfile:3 is redefined at import.justfile:1
+         ——▶ import.justfile:1:7
+          |
+        1 | alias b := a
+          |       ^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn duplicate_settings_in_imported_files_are_errors() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        set export
+      ",
+    })
+    .justfile(
+      "
+        import 'import.justfile'
+
+        set export
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Setting `export` first set at justfile:3 is redefined at import.justfile:1
+         ——▶ import.justfile:1:5
+          |
+        1 | set export
+          |     ^^^^^^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn duplicate_functions_in_imported_files_are_errors() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        fn f() := 'b'
+      ",
+    })
+    .justfile(
+      "
+        import 'import.justfile'
+
+        fn f() := 'a'
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Function `f` first defined at justfile:3 is redefined at import.justfile:1
+         ——▶ import.justfile:1:4
+          |
+        1 | fn f() := 'b'
+          |    ^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn circular_import() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "
+        import 'justfile'
+      ",
+    })
+    .justfile(
+      "
+        import 'import.justfile'
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr_regex("error: Import `.*justfile` in `.*import.justfile` is circular\n")
+    .run();
+}
+
+#[test]
+fn diamond_import() {
+  Test::new()
+    .tree(tree! {
+      "b.just": "
+        import 'd.just'
+
+        b: d
+          @echo B
+      ",
+      "c.just": "
+        import 'd.just'
+
+        c: d
+          @echo C
+      ",
+      "d.just": "
+        d:
+          @echo D
+      ",
+    })
+    .justfile(
+      "
+        import 'b.just'
+        import 'c.just'
+
+        a: b c
+      ",
+    )
+    .stdout("D\nB\nC\n")
+    .run();
+}
+
+#[test]
+fn circular_import_through_diamond() {
+  Test::new()
+    .tree(tree! {
+      "b.just": "
+        import 'd.just'
+      ",
+      "c.just": "
+        import 'd.just'
+      ",
+      "d.just": "
+        import 'c.just'
+      ",
+    })
+    .justfile(
+      "
+        import 'b.just'
+        import 'c.just'
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr_regex("error: Import `.*(c|d).just` in `.*(c|d).just` is circular\n")
+    .run();
+}
+
+#[test]
+fn import_is_dumped() {
+  Test::new()
+    .tree(tree! {
+      "import.justfile": "",
+    })
+    .justfile(
+      "
+        import 'import.justfile'
+
+        a:
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+        import 'import.justfile'
+
+        a:
+      ",
+    )
+    .run();
+}
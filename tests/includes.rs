@@ -70,7 +70,7 @@ fn trailing_include() {
     )
     .arg("--unstable")
     .status(EXIT_FAILURE)
-    .stderr("error: Expected character `=`\n  |\n2 | !include ./include.justfile\n  |  ^\n")
+    .stderr("error: Unknown start of token:\n ——▶ justfile:2:10\n  |\n2 | !include ./include.justfile\n  |          ^\n")
     .run();
 }
 
@@ -204,3 +204,39 @@ fn fmt_compatibility() {
     .status(EXIT_SUCCESS)
     .run();
 }
+
+#[test]
+fn root_marker_is_project_root() {
+  let tmp = temptree! {
+    workspace: {
+      ".just-root": "",
+      sub: {},
+    },
+  };
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tmp.path().join("workspace/sub"))
+    .env("JUST_ROOT_MARKERS", ".just-root")
+    .arg("--init")
+    .output()
+    .unwrap();
+
+  assert!(output.status.success());
+
+  assert_eq!(
+    fs::read_to_string(tmp.path().join("workspace/justfile")).unwrap(),
+    EXPECTED
+  );
+}
+
+#[test]
+fn exists_with_configured_justfile_name() {
+  Test::new()
+    .no_justfile()
+    .write("Justfile.build", "foo:")
+    .env("JUST_JUSTFILE_NAMES", "Justfile.build")
+    .arg("--init")
+    .status(EXIT_FAILURE)
+    .stderr_regex("error: Justfile `.*Justfile.build` already exists\n")
+    .run();
+}
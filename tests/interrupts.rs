@@ -81,6 +81,24 @@ fn interrupt_backtick() {
   );
 }
 
+#[test]
+#[ignore]
+fn interrupt_parallel() {
+  interrupt_test(
+    &[],
+    "
+        [parallel]
+        default: a b
+
+        a:
+          @sleep 1
+
+        b:
+          @sleep 1
+      ",
+  );
+}
+
 #[test]
 #[ignore]
 fn interrupt_command() {
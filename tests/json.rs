@@ -18,6 +18,8 @@ fn alias() {
     ",
     json!({
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "aliases": {
         "f": {
           "name": "f",
@@ -33,6 +35,7 @@ fn alias() {
           "dependencies": [],
           "doc": null,
           "name": "foo",
+          "namepath": "foo",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -43,7 +46,9 @@ fn alias() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
@@ -51,9 +56,12 @@ fn alias() {
         "shell": null,
         "tempdir" : null,
         "ignore_comments": false,
+        "lazy": false,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -73,21 +81,28 @@ fn assignment() {
         }
       },
       "first": null,
+      "functions": {},
+      "modules": {},
       "recipes": {},
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -105,6 +120,8 @@ fn body() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "recipes": {
         "foo": {
           "attributes": [],
@@ -115,6 +132,7 @@ fn body() {
           "dependencies": [],
           "doc": null,
           "name": "foo",
+          "namepath": "foo",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -125,17 +143,68 @@ fn body() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
+      "warnings": [],
+    }),
+  );
+}
+
+#[test]
+fn conditions() {
+  test(
+    "foo := if !('a' == 'b' || 'c' !~ 'd') && 'e' in ['f', 'g'] { 'h' } else { 'i' }",
+    json!({
+      "aliases": {},
+      "assignments": {
+        "foo": {
+          "export": false,
+          "name": "foo",
+          "value": [
+            "if",
+            ["&&", ["!", ["||", ["==", "a", "b"], ["!~", "c", "d"]]], ["in", "e", ["f", "g"]]],
+            "h",
+            "i",
+          ],
+        }
+      },
+      "first": null,
+      "functions": {},
+      "modules": {},
+      "recipes": {},
+      "settings": {
+        "allow_duplicate_recipes": false,
+        "dotenv_filename": null,
+        "dotenv_files": null,
+        "dotenv_load": null,
+        "dotenv_override": false,
+        "dotenv_path": null,
+        "export": false,
+        "fallback": false,
+        "ignore_comments": false,
+        "lazy": false,
+        "positional_arguments": false,
+        "shell": null,
+        "tempdir" : null,
+        "windows_powershell": false,
+        "windows_shell": null,
+        "working_directory": null,
+      },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -152,11 +221,14 @@ fn dependencies() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "recipes": {
         "bar": {
           "attributes": [],
           "doc": null,
           "name": "bar",
+          "namepath": "bar",
           "body": [],
           "dependencies": [{
             "arguments": [],
@@ -173,6 +245,7 @@ fn dependencies() {
           "dependencies": [],
           "doc": null,
           "name": "foo",
+          "namepath": "foo",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -184,17 +257,22 @@ fn dependencies() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -223,6 +301,8 @@ fn dependency_argument() {
     json!({
       "aliases": {},
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "assignments": {
         "x": {
           "export": false,
@@ -234,6 +314,7 @@ fn dependency_argument() {
         "bar": {
           "doc": null,
           "name": "bar",
+          "namepath": "bar",
           "body": [],
           "dependencies": [{
             "arguments": [
@@ -262,12 +343,18 @@ fn dependency_argument() {
           "dependencies": [],
           "doc": null,
           "name": "foo",
+          "namepath": "foo",
           "parameters": [
             {
+              "choices": [],
               "name": "args",
               "export": false,
               "default": null,
               "kind": "star",
+              "pattern": null,
+              "long": null,
+              "short": null,
+              "value": null,
             }
           ],
           "priors": 0,
@@ -280,17 +367,22 @@ fn dependency_argument() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -308,6 +400,8 @@ fn duplicate_recipes() {
     ",
     json!({
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "aliases": {
         "f": {
           "attributes": [],
@@ -322,12 +416,18 @@ fn duplicate_recipes() {
           "dependencies": [],
           "doc": null,
           "name": "foo",
+          "namepath": "foo",
           "parameters": [
             {
+              "choices": [],
               "name": "bar",
               "export": false,
               "default": null,
               "kind": "singular",
+              "pattern": null,
+              "long": null,
+              "short": null,
+              "value": null,
             },
           ],
           "priors": 0,
@@ -340,17 +440,22 @@ fn duplicate_recipes() {
       "settings": {
         "allow_duplicate_recipes": true,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -363,6 +468,8 @@ fn doc_comment() {
     json!({
       "aliases": {},
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "assignments": {},
       "recipes": {
         "foo": {
@@ -370,6 +477,7 @@ fn doc_comment() {
           "dependencies": [],
           "doc": "hello",
           "name": "foo",
+          "namepath": "foo",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -381,17 +489,22 @@ fn doc_comment() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -405,21 +518,83 @@ fn empty_justfile() {
       "aliases": {},
       "assignments": {},
       "first": null,
+      "functions": {},
+      "modules": {},
       "recipes": {},
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
+      "warnings": [],
+    }),
+  );
+}
+
+#[test]
+fn function() {
+  test(
+    "
+      fn greet(greeting, name) := greeting + ' ' + name
+
+      x := greet('hello', 'bob')
+    ",
+    json!({
+      "aliases": {},
+      "assignments": {
+        "x": {
+          "export": false,
+          "name": "x",
+          "value": ["call", "greet", "hello", "bob"],
+        }
+      },
+      "first": null,
+      "functions": {
+        "greet": {
+          "body": [
+            "concatenate",
+            ["variable", "greeting"],
+            ["concatenate", " ", ["variable", "name"]],
+          ],
+          "name": "greet",
+          "parameters": ["greeting", "name"],
+        }
+      },
+      "modules": {},
+      "recipes": {},
+      "settings": {
+        "allow_duplicate_recipes": false,
+        "dotenv_filename": null,
+        "dotenv_files": null,
+        "dotenv_load": null,
+        "dotenv_override": false,
+        "dotenv_path": null,
+        "export": false,
+        "fallback": false,
+        "ignore_comments": false,
+        "lazy": false,
+        "positional_arguments": false,
+        "shell": null,
+        "tempdir" : null,
+        "windows_powershell": false,
+        "windows_shell": null,
+        "working_directory": null,
+      },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -439,6 +614,8 @@ fn parameters() {
     json!({
       "aliases": {},
       "first": "a",
+      "functions": {},
+      "modules": {},
       "assignments": {},
       "recipes": {
         "a": {
@@ -447,6 +624,7 @@ fn parameters() {
           "dependencies": [],
           "doc": null,
           "name": "a",
+          "namepath": "a",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -458,12 +636,18 @@ fn parameters() {
           "dependencies": [],
           "doc": null,
           "name": "b",
+          "namepath": "b",
           "parameters": [
             {
+              "choices": [],
               "name": "x",
               "export": false,
               "default": null,
               "kind": "singular",
+              "pattern": null,
+              "long": null,
+              "short": null,
+              "value": null,
             },
           ],
           "priors": 0,
@@ -477,12 +661,18 @@ fn parameters() {
           "dependencies": [],
           "doc": null,
           "name": "c",
+          "namepath": "c",
           "parameters": [
             {
+              "choices": [],
               "name": "x",
               "export": false,
               "default": "y",
               "kind": "singular",
+              "pattern": null,
+              "long": null,
+              "short": null,
+              "value": null,
             }
           ],
           "priors": 0,
@@ -496,12 +686,18 @@ fn parameters() {
           "dependencies": [],
           "doc": null,
           "name": "d",
+          "namepath": "d",
           "parameters": [
             {
+              "choices": [],
               "name": "x",
               "export": false,
               "default": null,
               "kind": "plus",
+              "pattern": null,
+              "long": null,
+              "short": null,
+              "value": null,
             }
           ],
           "priors": 0,
@@ -515,12 +711,18 @@ fn parameters() {
           "dependencies": [],
           "doc": null,
           "name": "e",
+          "namepath": "e",
           "parameters": [
             {
+              "choices": [],
               "name": "x",
               "export": false,
               "default": null,
               "kind": "star",
+              "pattern": null,
+              "long": null,
+              "short": null,
+              "value": null,
             }
           ],
           "priors": 0,
@@ -534,12 +736,18 @@ fn parameters() {
           "dependencies": [],
           "doc": null,
           "name": "f",
+          "namepath": "f",
           "parameters": [
             {
+              "choices": [],
               "name": "x",
               "export": true,
               "default": null,
               "kind": "singular",
+              "pattern": null,
+              "long": null,
+              "short": null,
+              "value": null,
             }
           ],
           "priors": 0,
@@ -552,17 +760,22 @@ fn parameters() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -580,12 +793,15 @@ fn priors() {
       "aliases": {},
       "assignments": {},
       "first": "a",
+      "functions": {},
+      "modules": {},
       "recipes": {
         "a": {
           "body": [],
           "dependencies": [],
           "doc": null,
           "name": "a",
+          "namepath": "a",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -607,6 +823,7 @@ fn priors() {
           ],
           "doc": null,
           "name": "b",
+          "namepath": "b",
           "private": false,
           "quiet": false,
           "shebang": false,
@@ -619,6 +836,7 @@ fn priors() {
           "dependencies": [],
           "doc": null,
           "name": "c",
+          "namepath": "c",
           "parameters": [],
           "private": false,
           "quiet": false,
@@ -631,17 +849,22 @@ fn priors() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -655,12 +878,15 @@ fn private() {
       "aliases": {},
       "assignments": {},
       "first": "_foo",
+      "functions": {},
+      "modules": {},
       "recipes": {
         "_foo": {
           "body": [],
           "dependencies": [],
           "doc": null,
           "name": "_foo",
+          "namepath": "_foo",
           "parameters": [],
           "priors": 0,
           "private": true,
@@ -672,17 +898,22 @@ fn private() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -696,12 +927,15 @@ fn quiet() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "recipes": {
         "foo": {
           "body": [],
           "dependencies": [],
           "doc": null,
           "name": "foo",
+          "namepath": "foo",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -713,17 +947,22 @@ fn quiet() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir" : null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -748,12 +987,15 @@ fn settings() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "recipes": {
         "foo": {
           "body": [["#!bar"]],
           "dependencies": [],
           "doc": null,
           "name": "foo",
+          "namepath": "foo",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -765,11 +1007,14 @@ fn settings() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": "filename",
+        "dotenv_files": null,
         "dotenv_load": true,
+        "dotenv_override": false,
         "dotenv_path": "path",
         "export": true,
         "fallback": true,
         "ignore_comments": true,
+        "lazy": false,
         "positional_arguments": true,
         "shell": {
           "arguments": ["b", "c"],
@@ -778,7 +1023,9 @@ fn settings() {
         "tempdir": null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -795,12 +1042,15 @@ fn shebang() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "recipes": {
         "foo": {
           "body": [["#!bar"]],
           "dependencies": [],
           "doc": null,
           "name": "foo",
+          "namepath": "foo",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -812,17 +1062,22 @@ fn shebang() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir": null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -836,12 +1091,15 @@ fn simple() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "recipes": {
         "foo": {
           "body": [],
           "dependencies": [],
           "doc": null,
           "name": "foo",
+          "namepath": "foo",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -853,17 +1111,22 @@ fn simple() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
         "ignore_comments": false,
+        "lazy": false,
         "positional_arguments": false,
         "shell": null,
         "tempdir": null,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -880,6 +1143,8 @@ fn attribute() {
       "aliases": {},
       "assignments": {},
       "first": "foo",
+      "functions": {},
+      "modules": {},
       "recipes": {
         "foo": {
           "attributes": ["no-exit-message"],
@@ -887,6 +1152,61 @@ fn attribute() {
           "dependencies": [],
           "doc": null,
           "name": "foo",
+          "namepath": "foo",
+          "parameters": [],
+          "priors": 0,
+          "private": false,
+          "quiet": false,
+          "shebang": false,
+        }
+      },
+      "settings": {
+        "allow_duplicate_recipes": false,
+        "dotenv_filename": null,
+        "dotenv_files": null,
+        "dotenv_load": null,
+        "dotenv_override": false,
+        "dotenv_path": null,
+        "export": false,
+        "fallback": false,
+        "positional_arguments": false,
+        "shell": null,
+        "tempdir" : null,
+        "ignore_comments": false,
+        "lazy": false,
+        "windows_powershell": false,
+        "windows_shell": null,
+        "working_directory": null,
+      },
+      "unexports": [],
+      "warnings": [],
+    }),
+  );
+}
+
+#[test]
+fn group_attribute() {
+  test(
+    "
+      [group('b')]
+      [group(\"a\")]
+      [private]
+      foo:
+    ",
+    json!({
+      "aliases": {},
+      "assignments": {},
+      "first": "foo",
+      "functions": {},
+      "modules": {},
+      "recipes": {
+        "foo": {
+          "attributes": [{"group": "a"}, {"group": "b"}, "private"],
+          "body": [],
+          "dependencies": [],
+          "doc": null,
+          "name": "foo",
+          "namepath": "foo",
           "parameters": [],
           "priors": 0,
           "private": false,
@@ -897,7 +1217,9 @@ fn attribute() {
       "settings": {
         "allow_duplicate_recipes": false,
         "dotenv_filename": null,
+        "dotenv_files": null,
         "dotenv_load": null,
+        "dotenv_override": false,
         "dotenv_path": null,
         "export": false,
         "fallback": false,
@@ -905,9 +1227,12 @@ fn attribute() {
         "shell": null,
         "tempdir" : null,
         "ignore_comments": false,
+        "lazy": false,
         "windows_powershell": false,
         "windows_shell": null,
+        "working_directory": null,
       },
+      "unexports": [],
       "warnings": [],
     }),
   );
@@ -0,0 +1,366 @@
+use super::*;
+
+/// Run `just --output-format json-events` with `args` on `justfile`, and
+/// return the exit code, the events written to the events file with their
+/// timestamps and durations removed, and the lines printed to stderr
+fn events(justfile: &str, args: &[&str]) -> (i32, Vec<Value>, Vec<String>) {
+  let tempdir = tempdir();
+
+  fs::write(tempdir.path().join("justfile"), unindent(justfile)).unwrap();
+
+  let output = Command::new(executable_path("just"))
+    .current_dir(tempdir.path())
+    .args([
+      "--output-format",
+      "json-events",
+      "--events-file",
+      "events.json",
+    ])
+    .args(args)
+    .output()
+    .unwrap();
+
+  let events = fs::read_to_string(tempdir.path().join("events.json"))
+    .unwrap()
+    .lines()
+    .map(|line| {
+      let mut event = serde_json::from_str::<Value>(line).unwrap();
+      let event = event.as_object_mut().unwrap();
+      assert!(event.remove("timestamp").unwrap().is_f64());
+      if let Some(duration) = event.remove("duration_ms") {
+        assert!(duration.as_f64().unwrap() >= 0.0);
+      }
+      Value::Object(event.clone())
+    })
+    .collect();
+
+  let lines = str::from_utf8(&output.stderr)
+    .unwrap()
+    .lines()
+    .map(str::to_owned)
+    .collect();
+
+  (output.status.code().unwrap(), events, lines)
+}
+
+#[test]
+fn recipe_and_command_events() {
+  let (code, events, lines) = events(
+    "
+      foo:
+        echo foo
+        @echo bar
+    ",
+    &[],
+  );
+
+  assert_eq!(code, 0);
+
+  assert_eq!(
+    events,
+    [
+      json!({ "event": "recipe-start", "recipe": "foo", "arguments": [] }),
+      json!({ "event": "command-start", "recipe": "foo", "line_number": 2, "command": "echo foo" }),
+      json!({ "event": "command-finish", "recipe": "foo", "line_number": 2, "exit_code": 0, "signal": null }),
+      json!({ "event": "command-start", "recipe": "foo", "line_number": 3, "command": "echo bar" }),
+      json!({ "event": "command-finish", "recipe": "foo", "line_number": 3, "exit_code": 0, "signal": null }),
+      json!({
+        "event": "recipe-finish",
+        "recipe": "foo",
+        "arguments": [],
+        "success": true,
+        "exit_code": 0,
+        "signal": null,
+      }),
+    ],
+  );
+
+  assert!(lines.is_empty());
+}
+
+#[test]
+fn commands_are_evaluated() {
+  let (_, events, _) = events(
+    "
+      x := 'bar'
+
+      foo a:
+        echo {{a}} {{x}} \\
+          baz
+    ",
+    &["foo", "hello"],
+  );
+
+  assert_eq!(
+    events[0],
+    json!({ "event": "recipe-start", "recipe": "foo", "arguments": ["hello"] }),
+  );
+
+  assert_eq!(
+    events[1],
+    json!({
+      "event": "command-start",
+      "recipe": "foo",
+      "line_number": 4,
+      "command": "echo hello bar baz",
+    }),
+  );
+}
+
+#[test]
+fn failing_command() {
+  let (code, events, lines) = events(
+    "
+      foo:
+        -exit 2
+        exit 3
+        echo unreachable
+    ",
+    &[],
+  );
+
+  assert_eq!(code, 3);
+
+  assert_eq!(
+    events,
+    [
+      json!({ "event": "recipe-start", "recipe": "foo", "arguments": [] }),
+      json!({ "event": "command-start", "recipe": "foo", "line_number": 2, "command": "exit 2" }),
+      json!({ "event": "command-finish", "recipe": "foo", "line_number": 2, "exit_code": 2, "signal": null }),
+      json!({ "event": "command-start", "recipe": "foo", "line_number": 3, "command": "exit 3" }),
+      json!({ "event": "command-finish", "recipe": "foo", "line_number": 3, "exit_code": 3, "signal": null }),
+      json!({
+        "event": "recipe-finish",
+        "recipe": "foo",
+        "arguments": [],
+        "success": false,
+        "exit_code": 3,
+        "signal": null,
+      }),
+    ],
+  );
+
+  assert_eq!(
+    lines,
+    ["error: Recipe `foo` failed on line 3 with exit code 3"]
+  );
+}
+
+#[test]
+#[cfg(unix)]
+fn signal() {
+  let (_, events, _) = events(
+    "
+      foo:
+        kill -9 $$
+    ",
+    &[],
+  );
+
+  assert_eq!(
+    events[2],
+    json!({ "event": "command-finish", "recipe": "foo", "line_number": 2, "exit_code": null, "signal": 9 }),
+  );
+
+  assert_eq!(
+    events[3],
+    json!({
+      "event": "recipe-finish",
+      "recipe": "foo",
+      "arguments": [],
+      "success": false,
+      "exit_code": null,
+      "signal": 9,
+    }),
+  );
+}
+
+#[test]
+fn already_ran_dependencies_are_skipped() {
+  let (code, events, _) = events(
+    "
+      foo: bar bar
+      bar:
+    ",
+    &[],
+  );
+
+  assert_eq!(code, 0);
+
+  assert_eq!(
+    events,
+    [
+      json!({ "event": "recipe-start", "recipe": "bar", "arguments": [] }),
+      json!({
+        "event": "recipe-finish",
+        "recipe": "bar",
+        "arguments": [],
+        "success": true,
+        "exit_code": 0,
+        "signal": null,
+      }),
+      json!({ "event": "recipe-skip", "recipe": "bar", "arguments": [], "reason": "already ran" }),
+      json!({ "event": "recipe-start", "recipe": "foo", "arguments": [] }),
+      json!({
+        "event": "recipe-finish",
+        "recipe": "foo",
+        "arguments": [],
+        "success": true,
+        "exit_code": 0,
+        "signal": null,
+      }),
+    ],
+  );
+}
+
+#[test]
+fn up_to_date_recipes_are_skipped() {
+  let (_, events, _) = events(
+    "
+      [outputs('justfile')]
+      foo:
+        echo foo
+    ",
+    &[],
+  );
+
+  assert_eq!(
+    events,
+    [json!({
+      "event": "recipe-skip",
+      "recipe": "foo",
+      "arguments": [],
+      "reason": "outputs exist",
+    })],
+  );
+}
+
+#[test]
+#[cfg(unix)]
+fn shebang_recipe() {
+  let (code, events, _) = events(
+    "
+      foo:
+        #!/bin/sh
+        echo {{'foo'}}
+    ",
+    &[],
+  );
+
+  assert_eq!(code, 0);
+
+  assert_eq!(
+    events[1],
+    json!({
+      "event": "command-start",
+      "recipe": "foo",
+      "line_number": null,
+      "command": "#!/bin/sh\necho foo",
+    }),
+  );
+
+  assert_eq!(
+    events[2],
+    json!({ "event": "command-finish", "recipe": "foo", "line_number": null, "exit_code": 0, "signal": null }),
+  );
+}
+
+#[test]
+fn submodule_recipes_are_identified_by_path() {
+  let tempdir = tempdir();
+
+  fs::write(tempdir.path().join("justfile"), "mod bar\n").unwrap();
+  fs::write(tempdir.path().join("bar.just"), "baz:\n").unwrap();
+
+  let status = Command::new(executable_path("just"))
+    .current_dir(tempdir.path())
+    .args([
+      "--unstable",
+      "--output-format",
+      "json-events",
+      "--events-file",
+      "events.json",
+      "bar::baz",
+    ])
+    .status()
+    .unwrap();
+
+  assert!(status.success());
+
+  let event: Value = serde_json::from_str(
+    fs::read_to_string(tempdir.path().join("events.json"))
+      .unwrap()
+      .lines()
+      .next()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(event["recipe"], "bar::baz");
+}
+
+#[test]
+fn dry_run() {
+  let (code, events, lines) = events(
+    "
+      foo:
+        echo foo
+    ",
+    &["--dry-run"],
+  );
+
+  assert_eq!(code, 0);
+
+  assert_eq!(
+    events[1],
+    json!({ "event": "command-start", "recipe": "foo", "line_number": 2, "command": "echo foo" }),
+  );
+
+  assert_eq!(events.len(), 3);
+
+  assert!(lines.is_empty());
+}
+
+#[test]
+fn text_output_format_is_default() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .stderr("echo foo\n")
+    .run();
+}
+
+#[test]
+fn recipe_stderr_is_not_mixed_with_events() {
+  let (code, events, lines) = events(
+    r#"
+      foo:
+        @echo '{"event": "recipe-start"}' >&2
+        @echo bar >&2
+    "#,
+    &[],
+  );
+
+  assert_eq!(code, 0);
+
+  assert_eq!(events.len(), 6);
+
+  assert!(events.iter().all(|event| event["recipe"] == "foo"));
+
+  assert_eq!(lines, [r#"{"event": "recipe-start"}"#, "bar"]);
+}
+
+#[test]
+fn events_file_is_required() {
+  Test::new()
+    .arg("--output-format")
+    .arg("json-events")
+    .stderr("error: `--output-format json-events` requires `--events-file`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
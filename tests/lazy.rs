@@ -0,0 +1,226 @@
+use super::*;
+
+#[test]
+fn unused_assignments_are_not_evaluated() {
+  Test::new()
+    .justfile(
+      "
+      set lazy
+
+      foo := `exit 1`
+
+      bar:
+        @echo bar
+      ",
+    )
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn assignments_are_evaluated_eagerly_by_default() {
+  Test::new()
+    .justfile(
+      "
+      foo := `exit 1`
+
+      bar:
+        @echo bar
+      ",
+    )
+    .stderr(
+      "
+      error: Backtick failed with exit code 1
+       ——▶ justfile:1:8
+        |
+      1 | foo := `exit 1`
+        |        ^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn referenced_assignments_are_evaluated() {
+  Test::new()
+    .justfile(
+      "
+      set lazy
+
+      foo := `echo foo`
+      bar := foo + 'bar'
+      baz := `exit 1`
+
+      qux:
+        @echo {{ bar }}
+      ",
+    )
+    .stdout("foobar\n")
+    .run();
+}
+
+#[test]
+fn errors_point_at_backtick() {
+  Test::new()
+    .justfile(
+      "
+      set lazy
+
+      foo := 'foo'
+      bar := `exit 1`
+
+      baz:
+        @echo {{ bar }}
+      ",
+    )
+    .stderr(
+      "
+      error: Backtick failed with exit code 1
+       ——▶ justfile:4:8
+        |
+      4 | bar := `exit 1`
+        |        ^^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dependencies() {
+  Test::new()
+    .justfile(
+      "
+      set lazy
+
+      foo := `echo foo`
+      bar := `echo bar`
+      baz := `exit 1`
+
+      a: (b foo)
+        @echo a
+
+      b x: c
+        @echo b {{ x }}
+
+      c y=bar:
+        @echo c {{ y }}
+
+      d:
+        @echo {{ baz }}
+      ",
+    )
+    .arg("a")
+    .stdout("c bar\nb foo\na\n")
+    .run();
+}
+
+#[test]
+fn exported_assignments_are_evaluated() {
+  Test::new()
+    .justfile(
+      "
+      set lazy
+
+      export FOO := `echo foo`
+      bar := `exit 1`
+
+      baz:
+        @echo $FOO
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn export_setting() {
+  Test::new()
+    .justfile(
+      "
+      set lazy
+      set export
+
+      foo := `echo foo`
+
+      bar:
+        @echo $foo
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn confirm_prompt() {
+  Test::new()
+    .justfile(
+      "
+      set lazy
+
+      target := `echo production`
+      unused := `exit 1`
+
+      [confirm('Deploy to ' + target + '?')]
+      deploy:
+        @echo deployed
+      ",
+    )
+    .stderr(
+      "error: Recipe `deploy` requires confirmation, but standard input is not a terminal. Use `--yes` to confirm automatically.\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn evaluate_variable() {
+  Test::new()
+    .justfile(
+      "
+      set lazy
+
+      foo := 'foo'
+      bar := `exit 1`
+      ",
+    )
+    .args(["--evaluate", "foo"])
+    .stdout("foo")
+    .run();
+}
+
+#[test]
+fn overrides() {
+  Test::new()
+    .justfile(
+      "
+      set lazy
+
+      foo := `exit 1`
+
+      bar:
+        @echo {{ foo }}
+      ",
+    )
+    .args(["foo=baz", "bar"])
+    .stdout("baz\n")
+    .run();
+}
+
+#[test]
+fn submodule() {
+  Test::new()
+    .write(
+      "foo.just",
+      "set lazy\nbar := `exit 1`\nbaz := 'baz'\n@qux:\n  echo {{ baz }}",
+    )
+    .justfile(
+      "
+      mod foo
+      ",
+    )
+    .args(["foo", "qux"])
+    .stdout("baz\n")
+    .run();
+}
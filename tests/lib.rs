@@ -36,7 +36,10 @@ mod allow_duplicate_recipes;
 mod assert_stdout;
 mod assert_success;
 mod attributes;
+mod builtin_shell;
 mod byte_order_mark;
+mod cached;
+mod capture;
 mod changelog;
 mod choose;
 mod command;
@@ -46,6 +49,7 @@ mod confirm;
 mod delimiters;
 mod dotenv;
 mod edit;
+mod env_attribute;
 mod equals;
 mod error_messages;
 mod evaluate;
@@ -54,20 +58,31 @@ mod export;
 mod fallback;
 mod fmt;
 mod functions;
+mod global_justfile;
+mod groups;
 mod ignore_comments;
+mod imports;
 mod includes;
 mod init;
 #[cfg(unix)]
 mod interrupts;
 mod invocation_directory;
 mod json;
+mod json_events;
+mod lazy;
 mod line_prefixes;
+mod lists;
+mod lsp;
 mod misc;
+mod modules;
 mod multibyte_char;
 mod newline_escape;
 mod no_cd;
 mod no_exit_message;
+mod options;
 mod os_attributes;
+mod parallel;
+mod parameter_choices;
 mod parser;
 mod positional_arguments;
 mod private;
@@ -77,18 +92,24 @@ mod readme;
 mod recursion_limit;
 mod regexes;
 mod run;
+mod script;
 mod search;
 mod search_arguments;
 mod shadowing_parameters;
 mod shebang;
 mod shell;
+mod shell_attribute;
 mod show;
 mod slash_operator;
+mod sources;
 mod string;
 mod subsequents;
 mod tempdir;
+mod timings;
 mod undefined_variables;
+mod unexport;
 mod unstable;
+mod user_functions;
 #[cfg(target_family = "windows")]
 mod windows_shell;
 mod working_directory;
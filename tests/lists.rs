@@ -0,0 +1,450 @@
+use super::*;
+
+#[test]
+fn variadic_parameter_splat_quotes_arguments() {
+  Test::new()
+    .justfile(
+      "
+      foo *args:
+        @printf '%s\\n' {{ *args }}
+      ",
+    )
+    .args(["foo", "a b", "c'd", "e"])
+    .stdout("a b\nc'd\ne\n")
+    .run();
+}
+
+#[test]
+fn variadic_parameter_interpolation_joins_with_spaces() {
+  Test::new()
+    .justfile(
+      "
+      foo +args:
+        @printf '%s\\n' {{ args }}
+      ",
+    )
+    .args(["foo", "a b", "c"])
+    .stdout("a\nb\nc\n")
+    .run();
+}
+
+#[test]
+fn empty_variadic_parameter_splat() {
+  Test::new()
+    .justfile(
+      "
+      foo *args:
+        @echo x {{ *args }}y
+      ",
+    )
+    .stdout("x y\n")
+    .run();
+}
+
+#[test]
+fn variadic_parameter_default_splat() {
+  Test::new()
+    .justfile(
+      "
+      foo *args=split('a b,c', ','):
+        @printf '%s\\n' {{ *args }}
+      ",
+    )
+    .stdout("a b\nc\n")
+    .run();
+}
+
+#[test]
+fn string_splat_is_single_word() {
+  Test::new()
+    .justfile(
+      "
+      x := 'a b'
+
+      foo:
+        @printf '%s\\n' {{ *x }}
+      ",
+    )
+    .stdout("a b\n")
+    .run();
+}
+
+#[test]
+fn splat_is_echoed() {
+  Test::new()
+    .justfile(
+      "
+      foo *args:
+        printf '%s\\n' {{ *args }}
+      ",
+    )
+    .args(["foo", "a b"])
+    .stdout("a b\n")
+    .stderr("printf '%s\\n' 'a b'\n")
+    .run();
+}
+
+#[test]
+fn split() {
+  Test::new()
+    .justfile(
+      "
+      x := split('a,b,,c', ',')
+
+      foo:
+        @printf '%s\\n' {{ *x }}
+      ",
+    )
+    .stdout("a\nb\n\nc\n")
+    .run();
+}
+
+#[test]
+fn split_with_empty_separator() {
+  Test::new()
+    .justfile(
+      "
+      x := split('abc', '')
+
+      foo:
+      ",
+    )
+    .stderr(
+      "
+      error: Call to function `split` failed: Separator may not be empty
+       ——▶ justfile:1:6
+        |
+      1 | x := split('abc', '')
+        |      ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn len() {
+  Test::new()
+    .justfile(
+      "
+      foo *args:
+        @echo {{ len(args) }} {{ len(split('a b c', ' ')) }} {{ len('a b') }}
+      ",
+    )
+    .args(["foo", "a", "b"])
+    .stdout("2 3 1\n")
+    .run();
+}
+
+#[test]
+fn first() {
+  Test::new()
+    .justfile(
+      "
+      foo +args:
+        @echo {{ first(args) }}
+      ",
+    )
+    .args(["foo", "a b", "c"])
+    .stdout("a b\n")
+    .run();
+}
+
+#[test]
+fn first_of_empty_list() {
+  Test::new()
+    .justfile(
+      "
+      foo *args:
+        @echo {{ first(args) }}
+      ",
+    )
+    .stderr(
+      "
+      error: Call to function `first` failed: List is empty
+       ——▶ justfile:2:12
+        |
+      2 |   @echo {{ first(args) }}
+        |            ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn join_with() {
+  Test::new()
+    .justfile(
+      "
+      foo +args:
+        @echo {{ join_with(args, ',') }}
+      ",
+    )
+    .args(["foo", "a b", "c"])
+    .stdout("a b,c\n")
+    .run();
+}
+
+#[test]
+fn map() {
+  Test::new()
+    .justfile(
+      "
+      foo +args:
+        @printf '%s\\n' {{ *map(args, 'uppercase') }}
+      ",
+    )
+    .args(["foo", "a b", "c"])
+    .stdout("A B\nC\n")
+    .run();
+}
+
+#[test]
+fn map_with_unknown_function() {
+  Test::new()
+    .justfile(
+      "
+      foo +args:
+        @echo {{ map(args, 'bar') }}
+      ",
+    )
+    .arg("foo")
+    .arg("a")
+    .stderr(
+      "
+      error: Call to function `map` failed: Unknown function `bar`
+       ——▶ justfile:2:12
+        |
+      2 |   @echo {{ map(args, 'bar') }}
+        |            ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn map_with_function_that_does_not_take_one_argument() {
+  Test::new()
+    .justfile(
+      "
+      foo +args:
+        @echo {{ map(args, 'replace') }}
+      ",
+    )
+    .arg("foo")
+    .arg("a")
+    .stderr(
+      "
+      error: Call to function `map` failed: Function `replace` cannot be used with `map` because it does not take one argument
+       ——▶ justfile:2:12
+        |
+      2 |   @echo {{ map(args, 'replace') }}
+        |            ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn filter() {
+  Test::new()
+    .justfile(
+      "
+      foo +args:
+        @echo {{ filter(args, '^a') }}
+      ",
+    )
+    .args(["foo", "ab", "ba", "ac"])
+    .stdout("ab ac\n")
+    .run();
+}
+
+#[test]
+fn list_functions_compose() {
+  Test::new()
+    .justfile(
+      "
+      files := 'src/a.rs src/b.txt src/c.rs'
+
+      foo:
+        @echo {{ join_with(map(filter(split(files, ' '), '\\.rs$'), 'file_stem'), ',') }}
+      ",
+    )
+    .stdout("a,c\n")
+    .run();
+}
+
+#[test]
+fn list_assignment_is_joined_when_exported() {
+  Test::new()
+    .justfile(
+      "
+      export X := split('a,b', ',')
+
+      foo:
+        @echo $X
+      ",
+    )
+    .stdout("a b\n")
+    .run();
+}
+
+#[test]
+fn list_assignment_is_joined_when_evaluated() {
+  Test::new()
+    .justfile(
+      "
+      x := split('a,b', ',')
+      ",
+    )
+    .args(["--evaluate", "x"])
+    .stdout("a b")
+    .run();
+}
+
+#[test]
+fn conditional_preserves_list() {
+  Test::new()
+    .justfile(
+      "
+      foo *args:
+        @printf '%s\\n' {{ *if len(args) == '0' { split('x y', ',') } else { args } }}
+      ",
+    )
+    .stdout("x y\n")
+    .run();
+}
+
+#[test]
+fn variadic_dependency_arguments_are_spliced() {
+  Test::new()
+    .justfile(
+      "
+      outer *args: (inner 'x' args)
+
+      inner first *rest:
+        @printf '%s\\n' {{ first }} {{ len(rest) }} {{ *rest }}
+      ",
+    )
+    .args(["outer", "a b", "it's"])
+    .stdout("x\n2\na b\nit's\n")
+    .run();
+}
+
+#[test]
+fn empty_list_dependency_argument_to_plus_parameter() {
+  Test::new()
+    .justfile(
+      "
+      outer *args: (inner args)
+
+      inner +rest:
+      ",
+    )
+    .arg("outer")
+    .stderr(
+      "
+      error: Recipe `inner` got 0 arguments but takes at least 1
+      usage:
+          just inner +rest
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn splat_is_quoted_for_powershell() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['pwsh', '-NoProfile', '-Command']
+
+      foo *args:
+        Write-Output {{ *args }}
+      ",
+    )
+    .args(["--dry-run", "foo", "a b", "it's"])
+    .stderr("Write-Output 'a b' 'it''s'\n")
+    .run();
+}
+
+#[test]
+fn splat_with_unsupported_shell() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['python3', '-c']
+
+      foo *args:
+        print({{ *args }})
+      ",
+    )
+    .args(["foo", "a"])
+    .stderr(
+      "
+      error: Splat interpolation `{{ *… }}` is not supported with `python3`, only with POSIX shells and PowerShell
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn splat_in_shebang_recipe() {
+  Test::new()
+    .justfile(
+      "
+      foo *args:
+        #!/usr/bin/env bash
+        printf '%s\\n' {{ *args }}
+      ",
+    )
+    .args(["foo", "a b", "it's"])
+    .stdout("a b\nit's\n")
+    .run();
+}
+
+#[test]
+fn splat_in_shebang_recipe_with_unsupported_interpreter() {
+  Test::new()
+    .justfile(
+      "
+      foo *args:
+        #!/usr/bin/env python3
+        print({{ *args }})
+      ",
+    )
+    .args(["foo", "a"])
+    .stderr(
+      "
+      error: Splat interpolation `{{ *… }}` is not supported with `python3`, only with POSIX shells and PowerShell
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dump_splat() {
+  Test::new()
+    .justfile(
+      "
+      foo *args:
+        echo {{ *args }}
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+      foo *args:
+          echo {{ *args }}
+      ",
+    )
+    .run();
+}
@@ -0,0 +1,442 @@
+use super::*;
+
+/// Run a language server session in `tempdir` with a justfile containing
+/// `justfile`, sending `requests` after opening it, and return the messages
+/// sent by the server
+fn session(justfile: &str, requests: &[Value]) -> Vec<Value> {
+  let tempdir = tempdir();
+
+  let path = tempdir.path().join("justfile");
+
+  fs::write(&path, justfile).unwrap();
+
+  let uri = format!("file://{}", path.display());
+
+  let mut messages = vec![
+    json!({ "jsonrpc": "2.0", "id": 0, "method": "initialize", "params": {} }),
+    json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+    json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/didOpen",
+      "params": {
+        "textDocument": { "uri": uri, "languageId": "just", "version": 1, "text": justfile },
+      },
+    }),
+  ];
+
+  for request in requests {
+    let mut request = request.clone();
+    request["jsonrpc"] = json!("2.0");
+    if request["params"]["textDocument"].is_object() {
+      request["params"]["textDocument"]["uri"] = json!(uri);
+    }
+    messages.push(request);
+  }
+
+  messages.push(json!({ "jsonrpc": "2.0", "id": 1000, "method": "shutdown" }));
+  messages.push(json!({ "jsonrpc": "2.0", "method": "exit" }));
+
+  let mut input = Vec::new();
+
+  for message in messages {
+    let content = message.to_string();
+    write!(input, "Content-Length: {}\r\n\r\n{content}", content.len()).unwrap();
+  }
+
+  let mut child = Command::new(executable_path("just"))
+    .arg("--lsp")
+    .current_dir(tempdir.path())
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .spawn()
+    .unwrap();
+
+  child.stdin.take().unwrap().write_all(&input).unwrap();
+
+  let output = child.wait_with_output().unwrap();
+
+  assert!(output.status.success());
+
+  let mut stdout = str::from_utf8(&output.stdout).unwrap();
+
+  let mut responses = Vec::new();
+
+  while !stdout.is_empty() {
+    let (header, rest) = stdout.split_once("\r\n\r\n").unwrap();
+    let length = header
+      .strip_prefix("Content-Length: ")
+      .unwrap()
+      .parse::<usize>()
+      .unwrap();
+    responses.push(serde_json::from_str(&rest[..length]).unwrap());
+    stdout = &rest[length..];
+  }
+
+  responses
+    .into_iter()
+    .map(|mut response: Value| {
+      if let Some(uri) = response["params"]["uri"].as_str() {
+        if uri.starts_with("file://") {
+          response["params"]["uri"] = json!("justfile");
+        }
+      }
+      if let Some(uri) = response["result"]["uri"].as_str() {
+        if uri.starts_with("file://") {
+          response["result"]["uri"] = json!(uri.rsplit('/').next().unwrap());
+        }
+      }
+      response
+    })
+    .collect()
+}
+
+fn response(responses: &[Value], id: u64) -> &Value {
+  &responses
+    .iter()
+    .find(|response| response["id"] == json!(id))
+    .unwrap()["result"]
+}
+
+fn range(start: (u64, u64), end: (u64, u64)) -> Value {
+  json!({
+    "start": { "line": start.0, "character": start.1 },
+    "end": { "line": end.0, "character": end.1 },
+  })
+}
+
+#[test]
+fn initialize() {
+  let responses = session("", &[]);
+
+  assert_eq!(
+    responses[0],
+    json!({
+      "jsonrpc": "2.0",
+      "id": 0,
+      "result": {
+        "capabilities": {
+          "completionProvider": {},
+          "definitionProvider": true,
+          "documentFormattingProvider": true,
+          "hoverProvider": true,
+          "textDocumentSync": 1,
+        },
+        "serverInfo": {
+          "name": "just",
+          "version": env!("CARGO_PKG_VERSION"),
+        },
+      },
+    }),
+  );
+
+  assert_eq!(
+    responses.last().unwrap(),
+    &json!({ "jsonrpc": "2.0", "id": 1000, "result": null }),
+  );
+}
+
+#[test]
+fn no_diagnostics_for_valid_justfile() {
+  let responses = session("foo:\n  echo foo\n", &[]);
+
+  assert_eq!(
+    responses[1],
+    json!({
+      "jsonrpc": "2.0",
+      "method": "textDocument/publishDiagnostics",
+      "params": { "uri": "justfile", "diagnostics": [] },
+    }),
+  );
+}
+
+#[test]
+fn diagnostics_for_compile_error() {
+  let responses = session("foo: bar\n  echo foo\n", &[]);
+
+  assert_eq!(
+    responses[1]["params"]["diagnostics"],
+    json!([{
+      "range": range((0, 5), (0, 8)),
+      "severity": 1,
+      "source": "just",
+      "message": "Recipe `foo` has unknown dependency `bar`",
+    }]),
+  );
+}
+
+#[test]
+fn diagnostics_are_updated_on_change() {
+  let responses = session(
+    "foo:\n",
+    &[json!({
+      "method": "textDocument/didChange",
+      "params": {
+        "textDocument": { "version": 2 },
+        "contentChanges": [{ "text": "foo := bar\n" }],
+      },
+    })],
+  );
+
+  assert_eq!(responses[1]["params"]["diagnostics"], json!([]));
+
+  assert_eq!(
+    responses[2]["params"]["diagnostics"],
+    json!([{
+      "range": range((0, 7), (0, 10)),
+      "severity": 1,
+      "source": "just",
+      "message": "Variable `bar` not defined",
+    }]),
+  );
+}
+
+#[test]
+fn definition_of_dependency() {
+  let responses = session(
+    "foo: bar\n\nbar:\n  echo bar\n",
+    &[json!({
+      "id": 1,
+      "method": "textDocument/definition",
+      "params": { "textDocument": {}, "position": { "line": 0, "character": 6 } },
+    })],
+  );
+
+  assert_eq!(
+    response(&responses, 1),
+    &json!({ "uri": "justfile", "range": range((2, 0), (2, 3)) }),
+  );
+}
+
+#[test]
+fn definition_of_variable() {
+  let responses = session(
+    "x := 'a'\n\nfoo y=x:\n  echo {{x}} {{y}}\n",
+    &[
+      json!({
+        "id": 1,
+        "method": "textDocument/definition",
+        "params": { "textDocument": {}, "position": { "line": 3, "character": 9 } },
+      }),
+      json!({
+        "id": 2,
+        "method": "textDocument/definition",
+        "params": { "textDocument": {}, "position": { "line": 3, "character": 15 } },
+      }),
+      json!({
+        "id": 3,
+        "method": "textDocument/definition",
+        "params": { "textDocument": {}, "position": { "line": 2, "character": 6 } },
+      }),
+    ],
+  );
+
+  assert_eq!(
+    response(&responses, 1),
+    &json!({ "uri": "justfile", "range": range((0, 0), (0, 1)) }),
+  );
+
+  assert_eq!(
+    response(&responses, 2),
+    &json!({ "uri": "justfile", "range": range((2, 4), (2, 5)) }),
+  );
+
+  assert_eq!(
+    response(&responses, 3),
+    &json!({ "uri": "justfile", "range": range((0, 0), (0, 1)) }),
+  );
+}
+
+#[test]
+fn hover_over_recipe() {
+  let responses = session(
+    "foo: bar\n\n# build bar\nbar:\n  echo bar\n",
+    &[json!({
+      "id": 1,
+      "method": "textDocument/hover",
+      "params": { "textDocument": {}, "position": { "line": 0, "character": 5 } },
+    })],
+  );
+
+  assert_eq!(
+    response(&responses, 1),
+    &json!({
+      "contents": {
+        "kind": "markdown",
+        "value": "```just\n# build bar\nbar:\n    echo bar\n```",
+      },
+      "range": range((0, 5), (0, 8)),
+    }),
+  );
+}
+
+#[test]
+fn hover_over_variable() {
+  let responses = session(
+    "x := 'a' + 'b'\n\nfoo:\n  echo {{x}}\n",
+    &[json!({
+      "id": 1,
+      "method": "textDocument/hover",
+      "params": { "textDocument": {}, "position": { "line": 3, "character": 9 } },
+    })],
+  );
+
+  assert_eq!(
+    response(&responses, 1),
+    &json!({
+      "contents": {
+        "kind": "markdown",
+        "value": "```just\nx := 'a' + 'b'\n```",
+      },
+      "range": range((3, 9), (3, 10)),
+    }),
+  );
+}
+
+#[test]
+fn hover_over_nothing() {
+  let responses = session(
+    "foo:\n  echo foo\n",
+    &[json!({
+      "id": 1,
+      "method": "textDocument/hover",
+      "params": { "textDocument": {}, "position": { "line": 1, "character": 4 } },
+    })],
+  );
+
+  assert_eq!(response(&responses, 1), &Value::Null);
+}
+
+#[test]
+fn completion_of_functions_variables_and_recipes() {
+  let responses = session(
+    "x := ''\n\nfoo:\n  echo {{}}\n",
+    &[json!({
+      "id": 1,
+      "method": "textDocument/completion",
+      "params": { "textDocument": {}, "position": { "line": 3, "character": 9 } },
+    })],
+  );
+
+  let items = response(&responses, 1).as_array().unwrap();
+
+  for (label, detail) in [
+    ("arch", "function"),
+    ("sha256_file", "function"),
+    ("x", "variable"),
+    ("foo", "recipe"),
+  ] {
+    assert!(
+      items
+        .iter()
+        .any(|item| item["label"] == label && item["detail"] == detail),
+      "missing completion `{label}`",
+    );
+  }
+}
+
+#[test]
+fn completion_of_parameters() {
+  let responses = session(
+    "foo bar:\n  echo {{}}\n\nbaz qux:\n",
+    &[json!({
+      "id": 1,
+      "method": "textDocument/completion",
+      "params": { "textDocument": {}, "position": { "line": 1, "character": 9 } },
+    })],
+  );
+
+  let items = response(&responses, 1).as_array().unwrap();
+
+  assert!(items
+    .iter()
+    .any(|item| item["label"] == "bar" && item["detail"] == "parameter"));
+
+  assert!(!items.iter().any(|item| item["label"] == "qux"));
+}
+
+#[test]
+fn completion_of_settings() {
+  let responses = session(
+    "set \n",
+    &[json!({
+      "id": 1,
+      "method": "textDocument/completion",
+      "params": { "textDocument": {}, "position": { "line": 0, "character": 4 } },
+    })],
+  );
+
+  let items = response(&responses, 1).as_array().unwrap();
+
+  assert!(items
+    .iter()
+    .all(|item| item["detail"] == "setting" && item["kind"] == 10));
+
+  assert!(items.iter().any(|item| item["label"] == "dotenv-load"));
+  assert!(items
+    .iter()
+    .any(|item| item["label"] == "working-directory"));
+}
+
+#[test]
+fn formatting() {
+  let responses = session(
+    "foo:\n  echo {{'foo'}}\nbar := 'x'\n",
+    &[json!({
+      "id": 1,
+      "method": "textDocument/formatting",
+      "params": { "textDocument": {}, "options": { "tabSize": 2, "insertSpaces": true } },
+    })],
+  );
+
+  assert_eq!(
+    response(&responses, 1),
+    &json!([{
+      "range": range((0, 0), (3, 0)),
+      "newText": "foo:\n    echo {{ 'foo' }}\n\nbar := 'x'\n",
+    }]),
+  );
+}
+
+#[test]
+fn formatting_formatted_justfile() {
+  let responses = session(
+    "foo:\n    echo foo\n",
+    &[json!({
+      "id": 1,
+      "method": "textDocument/formatting",
+      "params": { "textDocument": {}, "options": {} },
+    })],
+  );
+
+  assert_eq!(response(&responses, 1), &json!([]));
+}
+
+#[test]
+fn formatting_matches_fmt() {
+  let responses = session(
+    "# a comment\na := 'a'\n\nb := 'b'\n",
+    &[json!({
+      "id": 1,
+      "method": "textDocument/formatting",
+      "params": { "textDocument": {}, "options": {} },
+    })],
+  );
+
+  assert_eq!(response(&responses, 1), &json!([]));
+}
+
+#[test]
+fn unknown_method() {
+  let responses = session(
+    "",
+    &[json!({ "id": 1, "method": "workspace/symbol", "params": { "query": "" } })],
+  );
+
+  assert_eq!(
+    responses
+      .iter()
+      .find(|response| response["id"] == json!(1))
+      .unwrap()["error"],
+    json!({ "code": -32601, "message": "Method `workspace/symbol` not found" }),
+  );
+}
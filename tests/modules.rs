@@ -0,0 +1,333 @@
+use super::*;
+
+#[test]
+fn module_recipes_can_be_run_as_subcommands() {
+  Test::new()
+    .write("foo.just", "foo:\n @echo FOO")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .args(["foo", "foo"])
+    .stdout("FOO\n")
+    .run();
+}
+
+#[test]
+fn module_recipes_can_be_run_with_path_syntax() {
+  Test::new()
+    .write("foo.just", "foo:\n @echo FOO")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .arg("foo::foo")
+    .stdout("FOO\n")
+    .run();
+}
+
+#[test]
+fn nested_module_recipes_can_be_run() {
+  Test::new()
+    .write("foo.just", "mod bar")
+    .write("bar.just", "baz:\n @echo BAZ")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .args(["foo::bar::baz", "foo", "bar", "baz"])
+    .stdout("BAZ\n")
+    .run();
+}
+
+#[test]
+fn module_without_recipe_runs_default_recipe() {
+  Test::new()
+    .write("foo.just", "foo:\n @echo FOO\nbar:\n @echo BAR")
+    .justfile(
+      "
+        mod foo
+
+        baz:
+          @echo BAZ
+      ",
+    )
+    .args(["foo", "baz"])
+    .stdout("FOO\nBAZ\n")
+    .run();
+}
+
+#[test]
+fn module_recipes_take_arguments() {
+  Test::new()
+    .write("foo.just", "foo bar:\n @echo {{bar}}")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .args(["foo", "foo", "hello"])
+    .stdout("hello\n")
+    .run();
+}
+
+#[test]
+fn module_source_file_locations() {
+  for (path, name) in [
+    ("foo.just", "foo.just"),
+    ("foo/mod.just", "mod.just"),
+    ("foo/justfile", "justfile"),
+    ("foo/.justfile", ".justfile"),
+  ] {
+    Test::new()
+      .write(path, format!("foo:\n @echo {name}"))
+      .justfile(
+        "
+          mod foo
+        ",
+      )
+      .arg("foo::foo")
+      .stdout(format!("{name}\n"))
+      .run();
+  }
+}
+
+#[test]
+fn module_with_explicit_path() {
+  Test::new()
+    .write("some/path.just", "foo:\n @echo FOO")
+    .justfile(
+      "
+        mod foo 'some/path.just'
+      ",
+    )
+    .arg("foo::foo")
+    .stdout("FOO\n")
+    .run();
+}
+
+#[test]
+fn modules_have_their_own_variables() {
+  Test::new()
+    .write("foo.just", "x := 'module'\nfoo:\n @echo {{x}}")
+    .justfile(
+      "
+        mod foo
+
+        x := 'root'
+
+        bar:
+          @echo {{x}}
+      ",
+    )
+    .args(["bar", "foo::foo"])
+    .stdout("root\nmodule\n")
+    .run();
+}
+
+#[test]
+fn modules_use_their_own_working_directory() {
+  Test::new()
+    .write("foo/mod.just", "foo:\n @cat data.txt")
+    .write("foo/data.txt", "DATA")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .arg("foo::foo")
+    .stdout("DATA")
+    .run();
+}
+
+#[test]
+fn modules_are_listed() {
+  Test::new()
+    .write("foo.just", "foo:\nbar:")
+    .justfile(
+      "
+        mod foo
+
+        baz:
+      ",
+    )
+    .arg("--list")
+    .stdout(
+      "
+        Available recipes:
+            baz
+            foo:
+                bar
+                foo
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn module_recipes_are_summarized() {
+  Test::new()
+    .write("foo.just", "foo:")
+    .justfile(
+      "
+        mod foo
+
+        bar:
+      ",
+    )
+    .arg("--summary")
+    .stdout("bar foo::foo\n")
+    .run();
+}
+
+#[test]
+fn module_recipes_can_be_shown() {
+  Test::new()
+    .write("foo.just", "foo:\n @echo FOO")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .args(["--show", "foo::foo"])
+    .stdout("foo:\n    @echo FOO\n")
+    .run();
+}
+
+#[test]
+fn dependencies_run_once_per_module() {
+  Test::new()
+    .write("foo.just", "a:\n @echo FOO")
+    .justfile(
+      "
+        mod foo
+
+        a:
+          @echo ROOT
+      ",
+    )
+    .args(["a", "foo::a", "a"])
+    .stdout("ROOT\nFOO\n")
+    .run();
+}
+
+#[test]
+fn missing_module_file_error() {
+  Test::new()
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Could not find source file for module `foo`.
+         ——▶ justfile:1:5
+          |
+        1 | mod foo
+          |     ^^^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn ambiguous_module_file_error() {
+  Test::new()
+    .write("foo.just", "")
+    .write("foo/justfile", "")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Found multiple source files for module `foo`: `foo.just` and `foo/justfile`
+         ——▶ justfile:1:5
+          |
+        1 | mod foo
+          |     ^^^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn duplicate_module_error() {
+  Test::new()
+    .write("foo.just", "")
+    .justfile(
+      "
+        mod foo
+        mod foo
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Module `foo` first defined on line 1 is redefined on line 2
+         ——▶ justfile:2:5
+          |
+        2 | mod foo
+          |     ^^^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn errors_in_modules_report_correct_location() {
+  Test::new()
+    .write("foo.just", "\nfoo:\n @echo {{bar}}")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .stderr(
+      "
+        error: Variable `bar` not defined
+         ——▶ foo.just:3:10
+          |
+        3 |  @echo {{bar}}
+          |          ^^^
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn unknown_module_recipe() {
+  Test::new()
+    .write("foo.just", "foo:")
+    .justfile(
+      "
+        mod foo
+      ",
+    )
+    .arg("foo::bar")
+    .status(EXIT_FAILURE)
+    .stderr("error: Justfile does not contain recipe `foo::bar`.\n")
+    .run();
+}
+
+#[test]
+fn mod_is_still_a_valid_recipe_name() {
+  Test::new()
+    .justfile(
+      "
+        mod foo:
+          @echo {{foo}}
+      ",
+    )
+    .args(["mod", "bar"])
+    .stdout("bar\n")
+    .run();
+}
@@ -72,6 +72,7 @@ fn newline_escape_deps_invalid_esc() {
     .stderr(
       "
         error: `\\ ` is not a valid escape sequence
+         ——▶ justfile:1:11
           |
         1 | default: a\\ b
           |           ^
@@ -92,6 +93,7 @@ fn newline_escape_unpaired_linefeed() {
     .stderr(
       "
         error: Unpaired carriage return
+         ——▶ justfile:1:9
           |
         1 | default:\\\ra
           |         ^
@@ -0,0 +1,360 @@
+use super::*;
+
+const JUSTFILE: &str = r#"
+[arg('region', long='region', short='r')]
+[arg('dry', long='dry', value='true')]
+deploy target region='us' dry='false':
+  @echo {{target}} {{region}} {{dry}}
+"#;
+
+#[test]
+fn defaults_are_used_when_options_are_not_passed() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "web"])
+    .stdout("web us false\n")
+    .run();
+}
+
+#[test]
+fn long_option_with_inline_value() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--region=eu", "web"])
+    .stdout("web eu false\n")
+    .run();
+}
+
+#[test]
+fn long_option_with_separate_value() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "web", "--region", "eu"])
+    .stdout("web eu false\n")
+    .run();
+}
+
+#[test]
+fn short_option() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "-r", "ap", "web"])
+    .stdout("web ap false\n")
+    .run();
+}
+
+#[test]
+fn flag() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--dry", "web"])
+    .stdout("web us true\n")
+    .run();
+}
+
+#[test]
+fn flag_without_default_is_empty() {
+  Test::new()
+    .justfile(
+      "
+      [arg('verbose', short='v', value='-v')]
+      build verbose:
+        @echo 'verbose:{{verbose}}'
+      ",
+    )
+    .args(["build"])
+    .stdout("verbose:\n")
+    .run();
+}
+
+#[test]
+fn flag_with_value_is_an_error() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--dry=yes", "web"])
+    .stderr("error: Recipe `deploy` flag `--dry` does not take a value\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn option_missing_value() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "web", "--region"])
+    .stderr("error: Recipe `deploy` option `--region` requires a value\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn unknown_option() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--zone=a", "web"])
+    .stderr("error: Recipe `deploy` does not have option `--zone=a`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn required_option() {
+  Test::new()
+    .justfile(
+      "
+      [arg('region', long='region')]
+      deploy region:
+        @echo {{region}}
+      ",
+    )
+    .args(["deploy"])
+    .stderr("error: Recipe `deploy` requires option `--region`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dependency_with_required_option() {
+  Test::new()
+    .justfile(
+      "
+      [arg('region', long='region')]
+      deploy region:
+        @echo {{region}}
+
+      release: deploy
+      ",
+    )
+    .args(["release"])
+    .stderr(
+      "
+      error: Dependency `deploy` requires option `--region`, but options cannot be passed to dependencies
+       ——▶ justfile:5:10
+        |
+      5 | release: deploy
+        |          ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dependency_with_optional_options() {
+  Test::new()
+    .justfile(
+      "
+      [arg('region', long='region')]
+      [arg('dry', long='dry', value='true')]
+      deploy target dry region='us':
+        @echo {{target}} {{region}} {{dry}}
+
+      release: (deploy 'prod')
+      ",
+    )
+    .args(["release"])
+    .stdout("prod us\n")
+    .run();
+}
+
+#[test]
+fn options_are_not_counted_as_positional_arguments() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "--dry"])
+    .stderr(
+      "
+      error: Recipe `deploy` got 0 arguments but takes 1
+      usage:
+          just deploy target [-r|--region='us'] [--dry]
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn options_end_at_next_recipe() {
+  Test::new()
+    .justfile(
+      "
+      [arg('region', long='region')]
+      deploy region='us':
+        @echo deploy {{region}}
+
+      test:
+        @echo test
+      ",
+    )
+    .args(["deploy", "--region", "eu", "test"])
+    .stdout("deploy eu\ntest\n")
+    .run();
+}
+
+#[test]
+fn option_values_are_checked_against_choices() {
+  Test::new()
+    .justfile(
+      r#"
+      [arg('region', long='region')]
+      deploy region: ("us" | "eu") = "us":
+        @echo {{region}}
+      "#,
+    )
+    .args(["deploy", "--region=ap"])
+    .stderr(
+      "error: Argument `ap` passed to recipe `deploy` parameter `region` is not `us` or `eu`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn options_are_exported() {
+  Test::new()
+    .justfile(
+      "
+      [arg('region', long='region')]
+      deploy $region='us':
+        @echo $region
+      ",
+    )
+    .args(["deploy", "--region=eu"])
+    .stdout("eu\n")
+    .run();
+}
+
+#[test]
+fn short_option_not_declared_is_positional() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["deploy", "-x"])
+    .stdout("-x us false\n")
+    .run();
+}
+
+#[test]
+fn list_shows_options() {
+  Test::new()
+    .justfile(
+      "
+      [arg('region', long='region', short='r')]
+      [arg('dry', long='dry', value='true')]
+      [arg('token', long='token')]
+      deploy target token region='us' dry='false':
+      ",
+    )
+    .args(["--list"])
+    .stdout(
+      "
+      Available recipes:
+          deploy target --token=<token> [-r|--region='us'] [--dry]
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn show_shows_options() {
+  Test::new()
+    .justfile(JUSTFILE)
+    .args(["--show", "deploy"])
+    .stdout(
+      "
+      [arg('dry', long='dry', value='true')]
+      [arg('region', long='region', short='r')]
+      deploy target region='us' dry='false':
+          @echo {{ target }} {{ region }} {{ dry }}
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn duplicate_option() {
+  Test::new()
+    .justfile(
+      "
+      [arg('a', long='x')]
+      [arg('b', long='x')]
+      foo a b:
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe option `--x` is defined more than once
+       ——▶ justfile:2:2
+        |
+      2 | [arg('b', long='x')]
+        |  ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn variadic_option() {
+  Test::new()
+    .justfile(
+      "
+      [arg('a', long='a')]
+      foo +a:
+      ",
+    )
+    .stderr(
+      "
+      error: Variadic parameter `a` may not be an option
+       ——▶ justfile:1:2
+        |
+      1 | [arg('a', long='a')]
+        |  ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_short_option() {
+  Test::new()
+    .justfile(
+      "
+      [arg('a', short='ab')]
+      foo a:
+      ",
+    )
+    .stderr(
+      "
+      error: Short option `ab` must be a single character other than `-`
+       ——▶ justfile:1:11
+        |
+      1 | [arg('a', short='ab')]
+        |           ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn value_requires_option() {
+  Test::new()
+    .justfile(
+      "
+      [arg('a', value='1')]
+      foo a:
+      ",
+    )
+    .stderr(
+      "
+      error: Argument attribute `value` requires a `long` or `short` option
+       ——▶ justfile:1:11
+        |
+      1 | [arg('a', value='1')]
+        |           ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
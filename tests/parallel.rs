@@ -0,0 +1,185 @@
+use super::*;
+
+// In these tests `a` waits for `b` to create a file, so they only succeed if
+// `a` and `b` run concurrently.
+#[test]
+fn parallel_attribute() {
+  Test::new()
+    .justfile(
+      "
+      [parallel]
+      foo: a b
+
+      a:
+        @for i in $(seq 100); do test -f b.done && exit 0; sleep 0.05; done; exit 1
+
+      b:
+        @touch b.done
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn jobs_flag() {
+  Test::new()
+    .justfile(
+      "
+      foo: a b
+
+      a:
+        @for i in $(seq 100); do test -f b.done && exit 0; sleep 0.05; done; exit 1
+
+      b:
+        @touch b.done
+      ",
+    )
+    .args(["--jobs", "2"])
+    .run();
+}
+
+#[test]
+fn sequential_by_default() {
+  Test::new()
+    .justfile(
+      "
+      foo: a b
+
+      a:
+        @test ! -f b.done
+
+      b:
+        @touch b.done
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn single_job_is_sequential() {
+  Test::new()
+    .justfile(
+      "
+      foo: a b
+
+      a:
+        @test ! -f b.done
+
+      b:
+        @touch b.done
+      ",
+    )
+    .args(["--jobs", "1"])
+    .run();
+}
+
+#[test]
+fn shared_dependency_runs_once() {
+  Test::new()
+    .justfile(
+      "
+      [parallel]
+      foo: a b
+
+      a: c
+      b: c
+
+      c:
+        @echo c
+      ",
+    )
+    .stdout("c\n")
+    .run();
+}
+
+#[test]
+fn shared_dependency_with_arguments() {
+  Test::new()
+    .justfile(
+      "
+      foo: a b
+        @echo foo
+
+      a: (c '1')
+      b: (c '1') (c '2')
+
+      c x:
+        @echo {{x}}
+      ",
+    )
+    .args(["--jobs", "4"])
+    .stdout_regex("(1\n2\n|2\n1\n)foo\n")
+    .run();
+}
+
+#[test]
+fn dependency_failure() {
+  Test::new()
+    .justfile(
+      "
+      [parallel]
+      foo: a b
+        @echo foo
+
+      a:
+        @exit 3
+
+      b:
+        @echo b
+      ",
+    )
+    .stdout("b\n")
+    .stderr("error: Recipe `a` failed on line 6 with exit code 3\n")
+    .status(3)
+    .run();
+}
+
+#[test]
+fn failed_shared_dependency_runs_once() {
+  Test::new()
+    .justfile(
+      "
+      [parallel]
+      foo: a b
+
+      a: c
+
+      b: c
+
+      c:
+        @echo c
+        @exit 3
+      ",
+    )
+    .stdout("c\n")
+    .stderr("error: Recipe `c` failed on line 10 with exit code 3\n")
+    .status(3)
+    .run();
+}
+
+#[test]
+fn subsequents() {
+  Test::new()
+    .justfile(
+      "
+      [parallel]
+      foo: && a b
+
+      a:
+        @for i in $(seq 100); do test -f b.done && exit 0; sleep 0.05; done; exit 1
+
+      b:
+        @touch b.done
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn invalid_jobs() {
+  Test::new()
+    .args(["--jobs", "0"])
+    .stderr("error: Invalid value `0` for `--jobs`, expected a positive integer\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
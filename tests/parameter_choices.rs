@@ -0,0 +1,275 @@
+use super::*;
+
+#[test]
+fn valid_choice_is_accepted() {
+  Test::new()
+    .justfile(
+      r#"
+      deploy env: ("dev" | "staging" | "prod"):
+        @echo {{env}}
+      "#,
+    )
+    .args(["deploy", "staging"])
+    .stdout("staging\n")
+    .run();
+}
+
+#[test]
+fn invalid_choice_is_rejected() {
+  Test::new()
+    .justfile(
+      r#"
+      deploy env: ("dev" | "staging" | "prod"):
+        @echo {{env}}
+      "#,
+    )
+    .args(["deploy", "qa"])
+    .stderr(
+      "error: Argument `qa` passed to recipe `deploy` parameter `env` is not `dev`, `staging`, or `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_choice_is_rejected_before_dependencies_run() {
+  Test::new()
+    .justfile(
+      r#"
+      build:
+        @echo build
+
+      deploy env: ("dev" | "prod"): build
+        @echo {{env}}
+      "#,
+    )
+    .args(["deploy", "qa"])
+    .stderr(
+      "error: Argument `qa` passed to recipe `deploy` parameter `env` is not `dev` or `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_choice_is_rejected_before_earlier_recipes_run() {
+  Test::new()
+    .justfile(
+      r#"
+      build:
+        @echo build
+
+      deploy env: ("dev" | "prod"):
+        @echo {{env}}
+      "#,
+    )
+    .args(["build", "deploy", "qa"])
+    .stderr(
+      "error: Argument `qa` passed to recipe `deploy` parameter `env` is not `dev` or `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn default_must_be_a_choice() {
+  Test::new()
+    .justfile(
+      r#"
+      deploy env: ("dev" | "prod") = "qa":
+        @echo {{env}}
+      "#,
+    )
+    .arg("deploy")
+    .stderr(
+      "error: Argument `qa` passed to recipe `deploy` parameter `env` is not `dev` or `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn default_choice() {
+  Test::new()
+    .justfile(
+      r#"
+      deploy env: ("dev" | "prod") = "dev":
+        @echo {{env}}
+      "#,
+    )
+    .arg("deploy")
+    .stdout("dev\n")
+    .run();
+}
+
+#[test]
+fn dependency_arguments_are_checked() {
+  Test::new()
+    .justfile(
+      r#"
+      foo: (deploy "qa")
+
+      deploy env: ("dev" | "prod"):
+        @echo {{env}}
+      "#,
+    )
+    .arg("foo")
+    .stderr(
+      "error: Argument `qa` passed to recipe `deploy` parameter `env` is not `dev` or `prod`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn variadic_arguments_are_each_checked() {
+  Test::new()
+    .justfile(
+      r#"
+      test +targets: ('unit' | 'integration'):
+        @echo {{targets}}
+      "#,
+    )
+    .args(["test", "unit", "e2e"])
+    .stderr("error: Argument `e2e` passed to recipe `test` parameter `targets` is not `unit` or `integration`\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn pattern_match_is_accepted() {
+  Test::new()
+    .justfile(
+      "
+      [arg('version', pattern='[0-9]+\\.[0-9]+')]
+      release version:
+        @echo {{version}}
+      ",
+    )
+    .args(["release", "1.2"])
+    .stdout("1.2\n")
+    .run();
+}
+
+#[test]
+fn pattern_must_match_entire_argument() {
+  Test::new()
+    .justfile(
+      "
+      [arg('version', pattern='[0-9]+\\.[0-9]+')]
+      release version:
+        @echo {{version}}
+      ",
+    )
+    .args(["release", "v1.2"])
+    .stderr(
+      "
+      error: Argument `v1.2` passed to recipe `release` parameter `version` does not match pattern `[0-9]+\\.[0-9]+`
+       ——▶ justfile:1:2
+        |
+      1 | [arg('version', pattern='[0-9]+\\.[0-9]+')]
+        |  ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_pattern() {
+  Test::new()
+    .justfile(
+      "
+      [arg('version', pattern='(')]
+      release version:
+      ",
+    )
+    .stderr_regex(
+      "error: Failed to parse argument pattern `\\(`: regex parse error:(.|\\n)*——▶ justfile:1:2(.|\\n)*",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn arg_attribute_for_undefined_parameter() {
+  Test::new()
+    .justfile(
+      "
+      [arg('bar', pattern='x')]
+      foo:
+      ",
+    )
+    .stderr(
+      "
+      error: Argument attribute for undefined parameter `bar`
+       ——▶ justfile:1:2
+        |
+      1 | [arg('bar', pattern='x')]
+        |  ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn unknown_arg_attribute_keyword() {
+  Test::new()
+    .justfile(
+      "
+      [arg('bar', regex='x')]
+      foo bar:
+      ",
+    )
+    .stderr(
+      "
+      error: Unknown keyword `regex` for `arg` attribute
+       ——▶ justfile:1:13
+        |
+      1 | [arg('bar', regex='x')]
+        |             ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn show_exposes_choices_and_patterns() {
+  Test::new()
+    .justfile(
+      r#"
+      [arg('version', pattern='[0-9]+')]
+      release version env: ("dev" | 'prod') = "dev":
+        @echo {{version}} {{env}}
+      "#,
+    )
+    .args(["--show", "release"])
+    .stdout(
+      r#"
+      [arg('version', pattern='[0-9]+')]
+      release version env: ("dev" | 'prod')="dev":
+          @echo {{ version }} {{ env }}
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn list_exposes_choices() {
+  Test::new()
+    .justfile(
+      r#"
+      deploy env: ("dev" | "prod"):
+      "#,
+    )
+    .arg("--list")
+    .stdout(
+      r#"
+      Available recipes:
+          deploy env: ("dev" | "prod")
+      "#,
+    )
+    .run();
+}
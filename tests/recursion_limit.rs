@@ -16,6 +16,7 @@ fn bugfix() {
 #[cfg(not(windows))]
 const RECURSION_LIMIT_REACHED: &str = "
 error: Parsing recursion depth exceeded
+ ——▶ justfile:1:265
   |
 1 | foo: (x ((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((
   |                                                                                                                                                                                                                                                                         ^
@@ -24,6 +25,7 @@ error: Parsing recursion depth exceeded
 #[cfg(windows)]
 const RECURSION_LIMIT_REACHED: &str = "
 error: Parsing recursion depth exceeded
+ ——▶ justfile:1:57
   |
 1 | foo: (x ((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((
   |                                                         ^
@@ -64,3 +64,20 @@ fn bad_regex_fails_at_runtime() {
     .status(EXIT_FAILURE)
     .run();
 }
+
+#[test]
+fn mismatch() {
+  Test::new()
+    .justfile(
+      "
+        foo := if 'Foo' !~ '^ab+c' { 'no' } else { 'yes' }
+        bar := if 'abbbc' !~ '^ab+c' { 'no' } else { 'yes' }
+
+        default:
+          echo {{ foo }} {{ bar }}
+      ",
+    )
+    .stderr("echo no yes\n")
+    .stdout("no yes\n")
+    .run();
+}
@@ -0,0 +1,300 @@
+use super::*;
+
+#[test]
+fn script_attribute() {
+  Test::new()
+    .justfile(
+      "
+      [script('python3')]
+      foo:
+        x = 'foo'
+        print(x * 2)
+      ",
+    )
+    .stdout("foofoo\n")
+    .run();
+}
+
+#[test]
+fn interpreter_arguments() {
+  Test::new()
+    .justfile(
+      "
+      [script('sh', '-eu')]
+      foo:
+        echo foo
+        false
+        echo bar
+      ",
+    )
+    .stdout("foo\n")
+    .stderr("error: Recipe `foo` failed with exit code 1\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn indentation_is_preserved() {
+  Test::new()
+    .justfile(
+      "
+      [script('python3')]
+      foo:
+        for x in ['a', 'b']:
+            print(x)
+      ",
+    )
+    .stdout("a\nb\n")
+    .run();
+}
+
+#[test]
+fn line_numbers_are_preserved() {
+  Test::new()
+    .justfile(
+      "
+      bar:
+
+      [script('python3')]
+      foo:
+        import sys
+        print(sys._getframe().f_lineno)
+      ",
+    )
+    .arg("foo")
+    .stdout("6\n")
+    .run();
+}
+
+#[test]
+fn positional_arguments() {
+  Test::new()
+    .justfile(
+      "
+      set positional-arguments
+
+      [script('sh')]
+      foo bar:
+        echo $1
+      ",
+    )
+    .args(["foo", "baz"])
+    .stdout("baz\n")
+    .run();
+}
+
+#[test]
+fn extension() {
+  Test::new()
+    .justfile(
+      "
+      [script('sh')]
+      [extension('.sh')]
+      foo:
+        basename $0
+      ",
+    )
+    .stdout("foo.sh\n")
+    .run();
+}
+
+#[test]
+fn extension_on_shebang_recipe() {
+  Test::new()
+    .justfile(
+      "
+      [extension('.sh')]
+      foo:
+        #!/bin/sh
+        basename $0
+      ",
+    )
+    .stdout("foo.sh\n")
+    .run();
+}
+
+#[test]
+fn extension_without_leading_dot() {
+  Test::new()
+    .justfile(
+      "
+      [script('sh')]
+      [extension('sh')]
+      foo:
+        basename $0
+      ",
+    )
+    .stdout("foo.sh\n")
+    .run();
+}
+
+#[test]
+fn extension_on_linewise_recipe() {
+  Test::new()
+    .justfile(
+      "
+      [extension('.sh')]
+      foo:
+        echo foo
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe `foo` has an `[extension]` attribute but is not a script or shebang recipe
+       ——▶ justfile:2:1
+        |
+      2 | foo:
+        | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn no_extension_by_default() {
+  Test::new()
+    .justfile(
+      "
+      [script('sh')]
+      foo:
+        basename $0
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn tempdir_setting() {
+  Test::new()
+    .justfile(
+      "
+      set tempdir := 'scripts'
+
+      [script('sh')]
+      foo:
+        dirname $(dirname $0) | xargs basename
+      ",
+    )
+    .write("scripts/.gitkeep", "")
+    .stdout("scripts\n")
+    .run();
+}
+
+#[test]
+fn working_directory() {
+  Test::new()
+    .justfile(
+      "
+      [script('sh')]
+      [working-directory('bar')]
+      foo:
+        cat file.txt
+      ",
+    )
+    .write("bar/file.txt", "bar")
+    .stdout("bar")
+    .run();
+}
+
+#[test]
+fn interpreter_not_found() {
+  Test::new()
+    .justfile(
+      "
+      [script('nonexistent-interpreter')]
+      foo:
+        echo foo
+      ",
+    )
+    .stderr_regex(
+      "error: Recipe `foo` with script interpreter `nonexistent-interpreter` execution error: .*\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn script_and_shell() {
+  Test::new()
+    .justfile(
+      "
+      [script('sh')]
+      [shell('sh', '-c')]
+      foo:
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe `foo` has both `[script]` and `[shell]` attributes
+       ——▶ justfile:3:1
+        |
+      3 | foo:
+        | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn script_and_shebang() {
+  Test::new()
+    .justfile(
+      "
+      [script('sh')]
+      foo:
+        #!/bin/sh
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe `foo` has a shebang and a `[script]` attribute
+       ——▶ justfile:2:1
+        |
+      2 | foo:
+        | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn duplicate_script_attributes() {
+  Test::new()
+    .justfile(
+      "
+      [script('sh')]
+      [script('bash')]
+      foo:
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe attribute `script` first used on line 1 is duplicated on line 2
+       ——▶ justfile:2:2
+        |
+      2 | [script('bash')]
+        |  ^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dry_run() {
+  Test::new()
+    .justfile(
+      "
+      [script('sh')]
+      foo:
+        echo foo
+      ",
+    )
+    .arg("--dry-run")
+    .stderr("echo foo\n")
+    .run();
+}
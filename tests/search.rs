@@ -183,3 +183,128 @@ fn dot_justfile_conflicts_with_justfile() {
     .status(EXIT_FAILURE)
     .run();
 }
+
+#[test]
+fn justfile_names_extend_candidates() {
+  Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .write("Justfile.build", "@foo:\n  echo ok")
+    .env("JUST_JUSTFILE_NAMES", "Justfile.build, Justfile.test")
+    .stdout("ok\n")
+    .run();
+}
+
+#[test]
+fn justfile_names_flag() {
+  Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .write("Justfile.build", "@foo:\n  echo ok")
+    .args(["--justfile-names", "Justfile.build", "foo"])
+    .stdout("ok\n")
+    .run();
+}
+
+#[test]
+fn justfile_names_are_case_insensitive() {
+  Test::new()
+    .no_justfile()
+    .test_round_trip(false)
+    .write("justfile.BUILD", "@foo:\n  echo ok")
+    .env("JUST_JUSTFILE_NAMES", "Justfile.build")
+    .stdout("ok\n")
+    .run();
+}
+
+#[test]
+fn justfile_names_keep_default_names() {
+  Test::new()
+    .justfile("@foo:\n  echo ok")
+    .env("JUST_JUSTFILE_NAMES", "Justfile.build")
+    .stdout("ok\n")
+    .run();
+}
+
+#[test]
+fn justfile_names_conflict_with_default_names() {
+  Test::new()
+    .justfile("foo:")
+    .write("Justfile.build", "foo:")
+    .env("JUST_JUSTFILE_NAMES", "Justfile.build")
+    .stderr_regex(
+      "error: Multiple candidate justfiles found in `.*`: `Justfile.build` and `justfile`\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn root_markers_stop_search() {
+  Test::new()
+    .justfile("@foo:\n  echo bad")
+    .tree(tree! {
+      workspace: {
+        ".just-root": "",
+        sub: {},
+      },
+    })
+    .current_dir("workspace/sub")
+    .env("JUST_ROOT_MARKERS", ".just-root")
+    .stderr("error: No justfile found\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn root_markers_flag() {
+  Test::new()
+    .justfile("@foo:\n  echo bad")
+    .tree(tree! {
+      workspace: {
+        ".just-root": "",
+        sub: {},
+      },
+    })
+    .current_dir("workspace/sub")
+    .args(["--root-markers", ".just-root", "foo"])
+    .stderr("error: No justfile found\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn justfile_in_root_marker_directory_is_found() {
+  Test::new()
+    .justfile("@foo:\n  echo bad")
+    .tree(tree! {
+      workspace: {
+        ".just-root": "",
+        "Justfile.build": "@foo:\n  echo ok",
+        sub: {},
+      },
+    })
+    .current_dir("workspace/sub")
+    .env("JUST_JUSTFILE_NAMES", "Justfile.build")
+    .env("JUST_ROOT_MARKERS", ".just-root")
+    .stdout("ok\n")
+    .run();
+}
+
+#[test]
+fn root_markers_stop_fallback() {
+  Test::new()
+    .justfile("@bar:\n  echo bad")
+    .tree(tree! {
+      workspace: {
+        ".just-root": "",
+        justfile: "set fallback\n@foo:\n  echo foo",
+      },
+    })
+    .current_dir("workspace")
+    .env("JUST_ROOT_MARKERS", ".just-root")
+    .arg("bar")
+    .stderr("error: Justfile does not contain recipe `bar`.\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,189 @@
+use super::*;
+
+#[test]
+fn shell_attribute() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      [shell('bash', '-c')]
+      foo:
+        @echo $0
+      ",
+    )
+    .stdout("bash\n")
+    .run();
+}
+
+#[test]
+fn overrides_shell_setting() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set shell := ['sh', '-c']
+
+      [shell('bash', '-euo', 'pipefail', '-c')]
+      foo:
+        @echo $0 $-
+
+      bar:
+        @echo $0
+      ",
+    )
+    .args(["foo", "bar"])
+    .stdout_regex("bash .*e.*u.*\nsh\n")
+    .run();
+}
+
+#[test]
+fn overrides_shell_flag() {
+  Test::new()
+    .justfile(
+      "
+      [shell('sh', '-c')]
+      foo:
+        @echo $0
+      ",
+    )
+    .stdout("sh\n")
+    .run();
+}
+
+#[test]
+fn lines_are_echoed() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      [shell('python3', '-c')]
+      foo:
+        print('foo')
+        print('bar')
+      ",
+    )
+    .stdout("foo\nbar\n")
+    .stderr("print('foo')\nprint('bar')\n")
+    .run();
+}
+
+#[test]
+fn errors_report_line_number() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      [shell('python3', '-c')]
+      foo:
+        @print('foo')
+        @exit(3)
+      ",
+    )
+    .stdout("foo\n")
+    .stderr("error: Recipe `foo` failed on line 4 with exit code 3\n")
+    .status(3)
+    .run();
+}
+
+#[test]
+fn positional_arguments() {
+  Test::new()
+    .shell(false)
+    .justfile(
+      "
+      set positional-arguments
+
+      [shell('bash', '-c')]
+      foo bar:
+        @echo $0 $1
+      ",
+    )
+    .args(["foo", "baz"])
+    .stdout("foo baz\n")
+    .run();
+}
+
+#[test]
+fn builtin() {
+  Test::new()
+    .justfile(
+      "
+      [shell('builtin')]
+      foo:
+        @echo *
+      ",
+    )
+    .stderr(
+      "error: Recipe `foo` line 3 could not be run by the builtin shell: `*` is not supported by the builtin shell\n",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn shebang_recipe() {
+  Test::new()
+    .justfile(
+      "
+      [shell('bash', '-c')]
+      foo:
+        #!/bin/sh
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe `foo` has a shebang and a `[shell]` attribute
+       ——▶ justfile:2:1
+        |
+      2 | foo:
+        | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn requires_command() {
+  Test::new()
+    .justfile(
+      "
+      [shell]
+      foo:
+      ",
+    )
+    .stderr(
+      "
+      error: Attribute `shell` called with 0 arguments but takes 1 or more arguments
+       ——▶ justfile:1:2
+        |
+      1 | [shell]
+        |  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn duplicate_shell_attributes() {
+  Test::new()
+    .justfile(
+      "
+      [shell('bash', '-c')]
+      [shell('sh', '-c')]
+      foo:
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe attribute `shell` first used on line 1 is duplicated on line 2
+       ——▶ justfile:2:2
+        |
+      2 | [shell('sh', '-c')]
+        |  ^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -48,6 +48,7 @@ fn no_rhs_once() {
     .stderr(
       "
       error: Expected backtick, identifier, '(', '/', or string, but found end of file
+       ——▶ justfile:1:11
         |
       1 | x := 'a' /
         |           ^
@@ -69,6 +70,7 @@ fn default_un_parenthesized() {
     .stderr(
       "
       error: Expected '*', ':', '$', identifier, or '+', but found '/'
+       ——▶ justfile:1:11
         |
       1 | foo x='a' / 'b':
         |           ^
@@ -90,6 +92,7 @@ fn no_lhs_un_parenthesized() {
     .stderr(
       "
       error: Expected backtick, identifier, '(', or string, but found '/'
+       ——▶ justfile:1:7
         |
       1 | foo x=/ 'a' / 'b':
         |       ^
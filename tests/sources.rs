@@ -0,0 +1,344 @@
+use super::*;
+
+const GENERATE: &str = "
+  [sources('proto/*.proto')]
+  [outputs('gen/*')]
+  generate:
+    @echo generating
+    @mkdir -p gen
+    @cat proto/*.proto > gen/out
+";
+
+#[test]
+fn recipe_runs_when_outputs_do_not_exist() {
+  Test::new()
+    .justfile(GENERATE)
+    .write("proto/a.proto", "a")
+    .stdout("generating\n")
+    .run();
+}
+
+#[test]
+fn recipe_is_skipped_when_outputs_are_newer_than_sources() {
+  let output = Test::new()
+    .justfile(GENERATE)
+    .write("proto/a.proto", "a")
+    .stdout("generating\n")
+    .run();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(GENERATE)
+    .arg("--verbose")
+    .stderr("===> Skipping recipe `generate`: outputs are newer than sources\n")
+    .run();
+}
+
+#[test]
+fn recipe_runs_when_source_is_newer_than_outputs() {
+  let output = Test::new()
+    .justfile(GENERATE)
+    .write("proto/a.proto", "a")
+    .stdout("generating\n")
+    .run();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(GENERATE)
+    .write("proto/a.proto", "b")
+    .arg("--verbose")
+    .stdout("generating\n")
+    .stderr(
+      "
+      ===> Recipe `generate` is out of date: source `proto/a.proto` is newer than output `gen/out`
+      ===> Running recipe `generate`...
+      echo generating
+      mkdir -p gen
+      cat proto/*.proto > gen/out
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn recipe_is_skipped_when_touched_sources_are_unchanged() {
+  let output = Test::new()
+    .justfile(GENERATE)
+    .write("proto/a.proto", "a")
+    .stdout("generating\n")
+    .run();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(GENERATE)
+    .write("proto/a.proto", "a")
+    .arg("--verbose")
+    .stderr("===> Skipping recipe `generate`: sources unchanged since last run\n")
+    .run();
+}
+
+#[test]
+fn recipe_runs_when_output_is_missing() {
+  let output = Test::new()
+    .justfile(GENERATE)
+    .write("proto/a.proto", "a")
+    .stdout("generating\n")
+    .run();
+
+  fs::remove_file(output.tempdir.path().join("gen/out")).unwrap();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(GENERATE)
+    .arg("--verbose")
+    .stdout("generating\n")
+    .stderr(
+      "
+      ===> Recipe `generate` is out of date: output `gen/*` does not exist
+      ===> Running recipe `generate`...
+      echo generating
+      mkdir -p gen
+      cat proto/*.proto > gen/out
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn force_runs_up_to_date_recipe() {
+  let output = Test::new()
+    .justfile(GENERATE)
+    .write("proto/a.proto", "a")
+    .stdout("generating\n")
+    .run();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(GENERATE)
+    .args(["--force", "--verbose"])
+    .stdout("generating\n")
+    .stderr(
+      "
+      ===> Running recipe `generate` with `--force`: outputs are newer than sources
+      ===> Running recipe `generate`...
+      echo generating
+      mkdir -p gen
+      cat proto/*.proto > gen/out
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn sources_without_outputs_use_digest() {
+  let justfile = "
+    [sources('*.txt')]
+    build:
+      @echo building
+  ";
+
+  let output = Test::new()
+    .justfile(justfile)
+    .write("a.txt", "a")
+    .arg("--verbose")
+    .stdout("building\n")
+    .stderr(
+      "
+      ===> Recipe `build` is out of date: no record of previous run
+      ===> Running recipe `build`...
+      echo building
+      ",
+    )
+    .run();
+
+  let output = Test::with_tempdir(output.tempdir)
+    .justfile(justfile)
+    .arg("--verbose")
+    .stderr("===> Skipping recipe `build`: sources unchanged since last run\n")
+    .run();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(justfile)
+    .write("b.txt", "b")
+    .arg("--verbose")
+    .stdout("building\n")
+    .stderr(
+      "
+      ===> Recipe `build` is out of date: sources changed since last run
+      ===> Running recipe `build`...
+      echo building
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn digest_is_recorded_per_argument() {
+  let justfile = "
+    [sources('*.txt')]
+    build target:
+      @echo building {{target}}
+  ";
+
+  let output = Test::new()
+    .justfile(justfile)
+    .write("a.txt", "a")
+    .args(["build", "x"])
+    .stdout("building x\n")
+    .run();
+
+  let output = Test::with_tempdir(output.tempdir)
+    .justfile(justfile)
+    .args(["build", "y"])
+    .stdout("building y\n")
+    .run();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(justfile)
+    .args(["build", "x"])
+    .run();
+}
+
+#[test]
+fn digest_is_not_recorded_on_failure() {
+  let justfile = "
+    [sources('*.txt')]
+    build:
+      @echo building
+      @exit 1
+  ";
+
+  let output = Test::new()
+    .justfile(justfile)
+    .write("a.txt", "a")
+    .stdout("building\n")
+    .stderr("error: Recipe `build` failed on line 4 with exit code 1\n")
+    .status(EXIT_FAILURE)
+    .run();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(justfile)
+    .stdout("building\n")
+    .stderr("error: Recipe `build` failed on line 4 with exit code 1\n")
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn digest_is_not_recorded_on_dry_run() {
+  let justfile = "
+    [sources('*.txt')]
+    build:
+      @echo building
+  ";
+
+  let output = Test::new()
+    .justfile(justfile)
+    .write("a.txt", "a")
+    .arg("--dry-run")
+    .stderr("echo building\n")
+    .run();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(justfile)
+    .stdout("building\n")
+    .run();
+}
+
+#[test]
+fn dependencies_of_skipped_recipes_run() {
+  let justfile = "
+    [sources('*.txt')]
+    build: setup
+      @echo building
+
+    setup:
+      @echo setup
+  ";
+
+  let output = Test::new()
+    .justfile(justfile)
+    .write("a.txt", "a")
+    .stdout("setup\nbuilding\n")
+    .run();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(justfile)
+    .stdout("setup\n")
+    .run();
+}
+
+#[test]
+fn multiple_globs() {
+  let output = Test::new()
+    .justfile(
+      "
+      [sources('a.txt', 'b.txt')]
+      [outputs('c.txt')]
+      build:
+        @cat a.txt b.txt > c.txt
+      ",
+    )
+    .write("a.txt", "a")
+    .write("b.txt", "b")
+    .run();
+
+  Test::with_tempdir(output.tempdir)
+    .justfile(
+      "
+      [sources('a.txt', 'b.txt')]
+      [outputs('c.txt')]
+      build:
+        @cat a.txt b.txt > c.txt
+      ",
+    )
+    .write("b.txt", "c")
+    .args(["--verbose"])
+    .stderr(
+      "
+      ===> Recipe `build` is out of date: source `b.txt` is newer than output `c.txt`
+      ===> Running recipe `build`...
+      cat a.txt b.txt > c.txt
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn sources_attribute_requires_arguments() {
+  Test::new()
+    .justfile(
+      "
+      [sources]
+      build:
+      ",
+    )
+    .stderr(
+      "
+      error: Attribute `sources` called with 0 arguments but takes 1 or more arguments
+       ——▶ justfile:1:2
+        |
+      1 | [sources]
+        |  ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn invalid_glob() {
+  Test::new()
+    .justfile(
+      "
+      [outputs('a/***')]
+      build:
+      ",
+    )
+    .stderr(
+      "
+      error: Invalid glob `a/***`: wildcards are either regular `*` or recursive `**`
+       ——▶ justfile:1:2
+        |
+      1 | [outputs('a/***')]
+        |  ^^^^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
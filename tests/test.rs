@@ -200,6 +200,12 @@ impl Test {
       command.args(["--shell", "bash"]);
     }
 
+    // Ignore any global justfile of the user running the tests
+    command.env(
+      "JUST_GLOBAL_JUSTFILE",
+      self.tempdir.path().join("missing-global-justfile"),
+    );
+
     let mut child = command
       .args(self.args)
       .envs(&self.env)
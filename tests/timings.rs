@@ -0,0 +1,196 @@
+use super::*;
+
+#[test]
+fn timings_are_printed_after_recipes_run() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .arg("--timings")
+    .stdout("foo\n")
+    .stderr_regex(
+      "  Duration  Recipe  Line  Command
+ *\\d+\\.\\d{3}s  foo
+ *\\d+\\.\\d{3}s  foo        2  echo foo
+",
+    )
+    .run();
+}
+
+#[test]
+fn timings_are_sorted_slowest_first() {
+  Test::new()
+    .justfile(
+      "
+        foo: bar
+          @sleep 0.2
+
+        bar:
+          @sleep 0.1
+      ",
+    )
+    .arg("--timings")
+    .stderr_regex(
+      "  Duration  Recipe  Line  Command
+ *0\\.2\\d\\ds  foo
+ *0\\.2\\d\\ds  foo        2  sleep 0.2
+ *0\\.1\\d\\ds  bar
+ *0\\.1\\d\\ds  bar        5  sleep 0.1
+",
+    )
+    .run();
+}
+
+#[test]
+fn timings_are_printed_when_recipe_fails() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @exit 1
+      ",
+    )
+    .arg("--timings")
+    .stderr_regex(
+      "  Duration  Recipe  Line  Command
+ *\\d+\\.\\d{3}s  foo
+ *\\d+\\.\\d{3}s  foo        2  exit 1
+error: Recipe `foo` failed on line 2 with exit code 1
+",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn shebang_recipes_are_timed_as_one_command() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          #!/usr/bin/env sh
+          echo foo
+      ",
+    )
+    .arg("--timings")
+    .stdout("foo\n")
+    .stderr_regex(
+      "  Duration  Recipe  Line  Command
+ *\\d+\\.\\d{3}s  foo
+ *\\d+\\.\\d{3}s  foo           #!/usr/bin/env sh
+",
+    )
+    .run();
+}
+
+#[test]
+fn timings_are_not_printed_by_default() {
+  Test::new()
+    .justfile(
+      "
+        foo:
+          @echo foo
+      ",
+    )
+    .stdout("foo\n")
+    .run();
+}
+
+#[test]
+fn trace_is_written_in_chrome_trace_event_format() {
+  let output = Test::new()
+    .justfile(
+      "
+        foo: bar
+          @echo foo
+
+        bar:
+          @echo bar
+      ",
+    )
+    .args(["--timings", "--timings-trace", "trace.json"])
+    .stdout("bar\nfoo\n")
+    .stderr_regex("(.*\n){5}")
+    .run();
+
+  let trace: Value =
+    serde_json::from_str(&fs::read_to_string(output.tempdir.path().join("trace.json")).unwrap())
+      .unwrap();
+
+  assert_eq!(trace["displayTimeUnit"], "ms");
+
+  let mut events = trace["traceEvents"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|event| {
+      assert_eq!(event["ph"], "X");
+      assert_eq!(event["pid"], 1);
+      assert!(event["ts"].as_f64().unwrap() >= 0.0);
+      assert!(event["dur"].as_f64().unwrap() >= 0.0);
+      (
+        event["cat"].as_str().unwrap().to_owned(),
+        event["name"].as_str().unwrap().to_owned(),
+        event["args"].clone(),
+      )
+    })
+    .collect::<Vec<(String, String, Value)>>();
+
+  events.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+  assert_eq!(
+    events,
+    [
+      (
+        "command".into(),
+        "echo bar".into(),
+        json!({ "recipe": "bar", "line_number": 5, "command": "echo bar" }),
+      ),
+      (
+        "command".into(),
+        "echo foo".into(),
+        json!({ "recipe": "foo", "line_number": 2, "command": "echo foo" }),
+      ),
+      (
+        "recipe".into(),
+        "bar".into(),
+        json!({ "recipe": "bar", "line_number": null, "command": null }),
+      ),
+      (
+        "recipe".into(),
+        "foo".into(),
+        json!({ "recipe": "foo", "line_number": null, "command": null }),
+      ),
+    ],
+  );
+}
+
+#[test]
+fn trace_requires_timings() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--timings-trace", "trace.json"])
+    .stderr_regex(
+      "error: The following required arguments were not provided:\n    --timings\n(.*\n)*",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn trace_write_error() {
+  Test::new()
+    .justfile("foo:")
+    .args(["--timings", "--timings-trace", "missing/trace.json"])
+    .stderr_regex(
+      "  Duration  Recipe  Line  Command
+ *\\d+\\.\\d{3}s  foo
+error: Failed to write timings trace to `.*missing.trace\\.json`: .*
+",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -0,0 +1,244 @@
+use super::*;
+
+#[test]
+fn unexported_variable_is_removed_from_linewise_recipe_environment() {
+  Test::new()
+    .justfile(
+      "
+        unexport JUST_TEST_VARIABLE
+
+        foo:
+          @echo ${JUST_TEST_VARIABLE:-unset}
+      ",
+    )
+    .env("JUST_TEST_VARIABLE", "foo")
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+#[cfg(unix)]
+fn unexported_variable_is_removed_from_shebang_recipe_environment() {
+  Test::new()
+    .justfile(
+      "
+        unexport JUST_TEST_VARIABLE
+
+        foo:
+          #!/bin/sh
+          echo ${JUST_TEST_VARIABLE:-unset}
+      ",
+    )
+    .env("JUST_TEST_VARIABLE", "foo")
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+fn unexported_variable_is_removed_from_backtick_environment() {
+  Test::new()
+    .justfile(
+      "
+        unexport JUST_TEST_VARIABLE
+
+        x := `echo ${JUST_TEST_VARIABLE:-unset}`
+
+        foo:
+          @echo {{x}}
+      ",
+    )
+    .env("JUST_TEST_VARIABLE", "foo")
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+fn unexported_variable_is_removed_from_dotenv_variables() {
+  Test::new()
+    .justfile(
+      "
+        set dotenv-load
+
+        unexport JUST_TEST_VARIABLE
+
+        foo:
+          @echo ${JUST_TEST_VARIABLE:-unset}
+      ",
+    )
+    .write(".env", "JUST_TEST_VARIABLE=foo")
+    .stdout("unset\n")
+    .run();
+}
+
+#[test]
+fn other_variables_are_inherited() {
+  Test::new()
+    .justfile(
+      "
+        unexport JUST_TEST_VARIABLE
+
+        foo:
+          @echo $JUST_TEST_OTHER_VARIABLE
+      ",
+    )
+    .env("JUST_TEST_VARIABLE", "foo")
+    .env("JUST_TEST_OTHER_VARIABLE", "bar")
+    .stdout("bar\n")
+    .run();
+}
+
+#[test]
+fn unexport_is_not_a_reserved_word() {
+  Test::new()
+    .justfile(
+      "
+        unexport := 'a'
+
+        unexport:
+          @echo {{unexport}}
+      ",
+    )
+    .stdout("a\n")
+    .run();
+}
+
+#[test]
+fn duplicate_unexport() {
+  Test::new()
+    .justfile(
+      "
+        unexport FOO
+        unexport FOO
+      ",
+    )
+    .stderr(
+      "
+        error: Variable `FOO` first unexported on line 1 is unexported again on line 2
+         ——▶ justfile:2:10
+          |
+        2 | unexport FOO
+          |          ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn exported_variable_cannot_be_unexported() {
+  Test::new()
+    .justfile(
+      "
+        unexport FOO
+        export FOO := 'a'
+      ",
+    )
+    .stderr(
+      "
+        error: Variable `FOO` is both exported and unexported
+         ——▶ justfile:2:8
+          |
+        2 | export FOO := 'a'
+          |        ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn evaluate_reports_environment() {
+  Test::new()
+    .justfile(
+      "
+        export foo := 'a'
+        bar := 'b'
+
+        unexport BAZ
+      ",
+    )
+    .arg("--evaluate")
+    .stdout(
+      r#"
+        bar        := "b"
+        export foo := "a"
+        unexport BAZ
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn evaluate_reports_variables_exported_with_setting() {
+  Test::new()
+    .justfile(
+      "
+        set export
+
+        foo := 'a'
+      ",
+    )
+    .arg("--evaluate")
+    .stdout(
+      r#"
+        export foo := "a"
+      "#,
+    )
+    .run();
+}
+
+#[test]
+fn show_reports_unexported_variables() {
+  Test::new()
+    .justfile(
+      "
+        unexport FOO
+
+        bar:
+          echo bar
+      ",
+    )
+    .args(["--show", "bar"])
+    .stdout(
+      "
+        unexport FOO
+        bar:
+            echo bar
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn unexports_are_formatted() {
+  Test::new()
+    .justfile(
+      "
+        unexport   FOO # comment
+      ",
+    )
+    .arg("--dump")
+    .stdout("unexport FOO\n")
+    .run();
+}
+
+#[test]
+fn unexports_in_modules_only_apply_to_module() {
+  Test::new()
+    .write(
+      "foo.just",
+      "unexport JUST_TEST_VARIABLE\nbar:\n @echo ${JUST_TEST_VARIABLE:-unset}\n",
+    )
+    .justfile(
+      "
+        mod foo
+
+        baz:
+          @echo ${JUST_TEST_VARIABLE:-unset}
+      ",
+    )
+    .env("JUST_TEST_VARIABLE", "set")
+    .args(["--unstable", "foo::bar", "baz"])
+    .stdout("unset\nset\n")
+    .run();
+}
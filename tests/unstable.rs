@@ -2,49 +2,36 @@ use super::*;
 
 #[test]
 fn set_unstable_true_with_env_var() {
-  let justfile = r#"
-default:
-    echo 'foo'
-  "#;
-
   for val in ["true", "some-arbitrary-string"] {
     Test::new()
-      .justfile(justfile)
-      .args(["--fmt"])
+      .justfile("!include ./include.justfile")
+      .write("include.justfile", "default:\n  @echo foo")
       .env("JUST_UNSTABLE", val)
-      .status(EXIT_SUCCESS)
-      .stderr_regex("Wrote justfile to `.*`\n")
+      .test_round_trip(false)
+      .stdout("foo\n")
       .run();
   }
 }
 
 #[test]
 fn set_unstable_false_with_env_var() {
-  let justfile = r#"
-default:
-    echo 'foo'
-  "#;
   for val in ["0", "", "false"] {
     Test::new()
-    .justfile(justfile)
-    .args(["--fmt"])
+    .justfile("!include ./include.justfile")
+    .write("include.justfile", "default:\n  @echo foo")
     .env("JUST_UNSTABLE", val)
     .status(EXIT_FAILURE)
-    .stderr("error: The `--fmt` command is currently unstable. Invoke `just` with the `--unstable` flag to enable unstable features.\n")
+    .stderr("error: The !include directive is currently unstable. Invoke `just` with the `--unstable` flag to enable unstable features.\n")
     .run();
   }
 }
 
 #[test]
 fn set_unstable_false_with_env_var_unset() {
-  let justfile = r#"
-default:
-    echo 'foo'
-  "#;
   Test::new()
-    .justfile(justfile)
-    .args(["--fmt"])
+    .justfile("!include ./include.justfile")
+    .write("include.justfile", "default:\n  @echo foo")
     .status(EXIT_FAILURE)
-    .stderr("error: The `--fmt` command is currently unstable. Invoke `just` with the `--unstable` flag to enable unstable features.\n")
+    .stderr("error: The !include directive is currently unstable. Invoke `just` with the `--unstable` flag to enable unstable features.\n")
     .run();
 }
@@ -0,0 +1,339 @@
+use super::*;
+
+#[test]
+fn call_in_interpolation() {
+  Test::new()
+    .justfile(
+      "
+      fn greet(greeting, name) := greeting + ', ' + name + '!'
+
+      foo:
+        @echo {{ greet('Hello', 'world') }}
+      ",
+    )
+    .stdout("Hello, world!\n")
+    .run();
+}
+
+#[test]
+fn call_in_assignment() {
+  Test::new()
+    .justfile(
+      "
+      x := slug('  Foo  Bar ')
+
+      fn slug(s) := replace_regex(trim(lowercase(s)), '\\s+', '-')
+
+      foo:
+        @echo {{ x }}
+      ",
+    )
+    .stdout("foo-bar\n")
+    .run();
+}
+
+#[test]
+fn call_in_parameter_default_and_dependency_argument() {
+  Test::new()
+    .justfile(
+      "
+      fn twice(s) := s + s
+
+      foo: (bar twice('a'))
+
+      bar x y=twice('b'):
+        @echo {{ x }} {{ y }}
+      ",
+    )
+    .stdout("aa bb\n")
+    .run();
+}
+
+#[test]
+fn functions_may_call_functions() {
+  Test::new()
+    .justfile(
+      "
+      fn outer(s) := inner(s) + inner(s)
+      fn inner(s) := '[' + s + ']'
+
+      foo:
+        @echo {{ outer('a') }}
+      ",
+    )
+    .stdout("[a][a]\n")
+    .run();
+}
+
+#[test]
+fn body_may_refer_to_assignments() {
+  Test::new()
+    .justfile(
+      "
+      prefix := 'v'
+
+      fn tag(version) := prefix + version
+
+      foo:
+        @echo {{ tag('1.0') }}
+      ",
+    )
+    .stdout("v1.0\n")
+    .run();
+}
+
+#[test]
+fn parameters_shadow_assignments() {
+  Test::new()
+    .justfile(
+      "
+      x := 'assignment'
+
+      fn f(x) := x
+
+      foo:
+        @echo {{ f('argument') }}
+      ",
+    )
+    .stdout("argument\n")
+    .run();
+}
+
+#[test]
+fn recipe_parameters_are_not_visible_in_body() {
+  Test::new()
+    .justfile(
+      "
+      x := 'assignment'
+
+      fn f() := x
+
+      foo x:
+        @echo {{ f() }} {{ x }}
+      ",
+    )
+    .args(["foo", "parameter"])
+    .stdout("assignment parameter\n")
+    .run();
+}
+
+#[test]
+fn arguments_are_not_visible_in_assignments() {
+  Test::new()
+    .justfile(
+      "
+      a := f('argument')
+      b := x
+      x := 'assignment'
+
+      fn f(x) := b
+      ",
+    )
+    .args(["--evaluate", "a"])
+    .stdout("assignment")
+    .run();
+}
+
+#[test]
+fn list_arguments() {
+  Test::new()
+    .justfile(
+      "
+      fn upper(list) := map(list, 'uppercase')
+
+      foo *args:
+        @printf '%s\\n' {{ *upper(args) }}
+      ",
+    )
+    .args(["foo", "a b", "c"])
+    .stdout("A B\nC\n")
+    .run();
+}
+
+#[test]
+fn lazy() {
+  Test::new()
+    .justfile(
+      "
+      set lazy
+
+      x := 'x'
+      y := `exit 1`
+
+      fn f() := x
+
+      foo:
+        @echo {{ f() }}
+      ",
+    )
+    .stdout("x\n")
+    .run();
+}
+
+#[test]
+fn evaluate() {
+  Test::new()
+    .justfile(
+      "
+      x := f('a')
+
+      fn f(a) := a + '!'
+      fn g() := 'g'
+      ",
+    )
+    .arg("--evaluate")
+    .stdout(
+      "
+      x := \"a!\"
+      fn f(a) := a + '!'
+      fn g() := 'g'
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn dump() {
+  Test::new()
+    .justfile(
+      "
+      fn   f( a ,b ) :=   a+b
+
+      x := f('a', 'b')
+      ",
+    )
+    .arg("--dump")
+    .stdout(
+      "
+      fn f(a, b) := a + b
+
+      x := f('a', 'b')
+      ",
+    )
+    .run();
+}
+
+#[test]
+fn imported_functions() {
+  Test::new()
+    .justfile(
+      "
+      import 'import.just'
+
+      foo:
+        @echo {{ f() }}
+      ",
+    )
+    .write("import.just", "fn f() := 'imported'\n")
+    .stdout("imported\n")
+    .run();
+}
+
+#[test]
+fn unknown_function() {
+  Test::new()
+    .justfile(
+      "
+      fn f() := g()
+      ",
+    )
+    .stderr(
+      "
+      error: Call to unknown function `g`
+       ——▶ justfile:1:11
+        |
+      1 | fn f() := g()
+        |           ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn argument_count_mismatch() {
+  Test::new()
+    .justfile(
+      "
+      fn f(a, b) := a + b
+
+      foo:
+        echo {{ f('a') }}
+      ",
+    )
+    .stderr(
+      "
+      error: Function `f` called with 1 argument but takes 2
+       ——▶ justfile:4:11
+        |
+      4 |   echo {{ f('a') }}
+        |           ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn recursion() {
+  Test::new()
+    .justfile(
+      "
+      fn f(a) := g(a)
+      fn g(a) := f(a)
+      ",
+    )
+    .stderr(
+      "
+      error: Function `f` has circular dependency `f -> g -> f`
+       ——▶ justfile:2:12
+        |
+      2 | fn g(a) := f(a)
+        |            ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn shadowing_builtin_is_an_error() {
+  Test::new()
+    .justfile(
+      "
+      fn trim(s) := s
+      ",
+    )
+    .stderr(
+      "
+      error: Function `trim` shadows built-in function of the same name
+       ——▶ justfile:1:4
+        |
+      1 | fn trim(s) := s
+        |    ^^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn undefined_variable_in_body() {
+  Test::new()
+    .justfile(
+      "
+      fn f(a) := a + b
+      ",
+    )
+    .stderr(
+      "
+      error: Variable `b` not defined
+       ——▶ justfile:1:16
+        |
+      1 | fn f(a) := a + b
+        |                ^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
@@ -181,3 +181,209 @@ fn search_dir_parent() -> Result<(), Box<dyn Error>> {
 
   Ok(())
 }
+
+#[test]
+fn attribute() {
+  Test::new()
+    .justfile(
+      "
+      [working-directory('bar')]
+      foo:
+        cat data
+      ",
+    )
+    .write("bar/data", "BAR")
+    .stdout("BAR")
+    .stderr("cat data\n")
+    .run();
+}
+
+#[test]
+fn attribute_shebang() {
+  Test::new()
+    .justfile(
+      "
+      [working-directory('bar')]
+      foo:
+        #!/usr/bin/env sh
+        cat data
+      ",
+    )
+    .write("bar/data", "BAR")
+    .stdout("BAR")
+    .run();
+}
+
+#[test]
+fn setting() {
+  Test::new()
+    .justfile(
+      "
+      set working-directory := 'bar'
+
+      foo:
+        cat data
+      ",
+    )
+    .write("bar/data", "BAR")
+    .stdout("BAR")
+    .stderr("cat data\n")
+    .run();
+}
+
+#[test]
+fn attribute_is_relative_to_setting() {
+  Test::new()
+    .justfile(
+      "
+      set working-directory := 'bar'
+
+      [working-directory('baz')]
+      foo:
+        cat data
+      ",
+    )
+    .write("bar/baz/data", "BAR/BAZ")
+    .write("baz/data", "BAZ")
+    .stdout("BAR/BAZ")
+    .stderr("cat data\n")
+    .run();
+}
+
+#[test]
+fn attribute_is_relative_to_justfile_directory() {
+  Test::new()
+    .justfile(
+      "
+      [working-directory('bar')]
+      foo:
+        cat data
+      ",
+    )
+    .write("bar/data", "BAR")
+    .write("sub/.keep", "")
+    .current_dir("sub")
+    .stdout("BAR")
+    .stderr("cat data\n")
+    .run();
+}
+
+#[test]
+fn attribute_ignores_working_directory_flag() {
+  Test::new()
+    .justfile(
+      "
+      [working-directory('bar')]
+      foo:
+        cat data
+      ",
+    )
+    .args([
+      "--justfile",
+      "justfile",
+      "--working-directory",
+      "sub",
+      "foo",
+    ])
+    .write("bar/data", "BAR")
+    .write("sub/bar/data", "SUB/BAR")
+    .stdout("BAR")
+    .stderr("cat data\n")
+    .run();
+}
+
+#[test]
+fn setting_ignores_working_directory_flag() {
+  Test::new()
+    .justfile(
+      "
+      set working-directory := 'bar'
+
+      foo:
+        cat data
+      ",
+    )
+    .args([
+      "--justfile",
+      "justfile",
+      "--working-directory",
+      "sub",
+      "foo",
+    ])
+    .write("bar/data", "BAR")
+    .write("sub/bar/data", "SUB/BAR")
+    .stdout("BAR")
+    .stderr("cat data\n")
+    .run();
+}
+
+#[test]
+fn no_cd_ignores_setting() {
+  Test::new()
+    .justfile(
+      "
+      set working-directory := 'bar'
+
+      [no-cd]
+      foo:
+        cat data
+      ",
+    )
+    .write("bar/data", "BAR")
+    .write("data", "ROOT")
+    .stdout("ROOT")
+    .stderr("cat data\n")
+    .run();
+}
+
+#[test]
+fn no_cd_and_attribute_conflict() {
+  Test::new()
+    .justfile(
+      "
+      [no-cd]
+      [working-directory('bar')]
+      foo:
+      ",
+    )
+    .stderr(
+      "
+      error: Recipe `foo` has both `[no-cd]` and `[working-directory]` attributes
+       ——▶ justfile:3:1
+        |
+      3 | foo:
+        | ^^^
+      ",
+    )
+    .status(EXIT_FAILURE)
+    .run();
+}
+
+#[test]
+fn dry_run_shows_working_directory() {
+  Test::new()
+    .justfile(
+      "
+      [working-directory('bar')]
+      foo:
+        cat data
+      ",
+    )
+    .arg("--dry-run")
+    .stderr_regex(r"cd .*bar\ncat data\n")
+    .run();
+}
+
+#[test]
+fn dry_run_without_working_directory() {
+  Test::new()
+    .justfile(
+      "
+      foo:
+        cat data
+      ",
+    )
+    .arg("--dry-run")
+    .stderr("cat data\n")
+    .run();
+}